@@ -1,11 +1,79 @@
+use crate::domain::error;
+
 pub trait PasswordHasher {
     fn hash(&self, raw: &str) -> String;
+
+    fn dummy_hash(&self) -> &str;
+
+    fn needs_rehash(&self, hashed: &str) -> bool;
 }
 
 pub trait PasswordValidator {
     fn verify(&self, raw: &str, hashed: &str) -> bool;
 }
 
+pub trait BreachedPasswordChecker {
+    fn is_breached(&self, raw: &str) -> bool;
+}
+
 pub trait TokenIssuer {
-    fn issue(&self, username: &str) -> String;
+    fn issue(&self, subject: &TokenSubject) -> String;
+}
+
+pub trait TokenVerifier {
+    fn verify(&self, token: &str) -> Result<TokenClaims, error::InvalidToken>;
+}
+
+#[derive(Clone, Default)]
+pub struct TokenSubject {
+    pub sub: String,
+    pub jti: Option<String>,
+    pub fid: Option<String>,
+    pub email: Option<String>,
+    pub scope: Option<String>,
+}
+
+#[derive(Clone)]
+pub struct TokenClaims {
+    pub sub: String,
+    pub iss: String,
+    pub aud: String,
+    pub iat: u64,
+    pub exp: u64,
+    pub jti: Option<String>,
+    pub fid: Option<String>,
+    pub email: Option<String>,
+    pub scope: Option<String>,
+}
+
+pub trait TotpAuthenticator {
+    fn generate_secret(&self) -> String;
+
+    fn provisioning_uri(&self, secret: &str, account: &str) -> String;
+
+    fn verify(&self, secret: &str, code: &str, now: u64) -> Option<u64>;
+}
+
+pub trait PasskeyVerifier {
+    fn verify_registration(
+        &self,
+        challenge: &str,
+        client_data_json: &[u8],
+        attestation_object: &[u8],
+    ) -> Result<AttestedCredential, error::InvalidPasskeyResponse>;
+
+    fn verify_assertion(
+        &self,
+        challenge: &str,
+        public_key: &str,
+        client_data_json: &[u8],
+        authenticator_data: &[u8],
+        signature: &[u8],
+    ) -> Result<u32, error::InvalidPasskeyResponse>;
+}
+
+pub struct AttestedCredential {
+    pub id: String,
+    pub public_key: String,
+    pub sign_count: u32,
 }
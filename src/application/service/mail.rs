@@ -0,0 +1,11 @@
+use crate::domain::error;
+
+pub struct Mail {
+    pub to: String,
+    pub subject: String,
+    pub body: String,
+}
+
+pub trait MailSender {
+    fn send(&self, mail: &Mail) -> Result<(), error::MailDeliveryFailed>;
+}
@@ -0,0 +1,666 @@
+use super::begin_authorization::{
+    AuthorizationRequest, AuthorizationRequestError, validate_authorization_request,
+};
+use super::signin::{LoginThrottle, PasswordAuthenticator};
+use super::signin_mfa::SecondFactor;
+use crate::domain::{
+    entity::AuthorizationCode,
+    repository::{AuthorizationCodeRepository, OAuthClientRepository},
+    value_object::{EmailAddress, UserId},
+};
+
+pub struct AuthorizeUseCase<'a> {
+    password_authenticator: PasswordAuthenticator<'a>,
+    second_factor: SecondFactor<'a>,
+    oauth_client_repository: &'a dyn OAuthClientRepository,
+    login_throttle: LoginThrottle<'a>,
+    authorization_code_issuer: AuthorizationCodeIssuer<'a>,
+    require_verified_email: bool,
+    get_timestamp: fn() -> u64,
+}
+
+impl<'a> AuthorizeUseCase<'a> {
+    pub fn new(
+        password_authenticator: PasswordAuthenticator<'a>,
+        second_factor: SecondFactor<'a>,
+        oauth_client_repository: &'a dyn OAuthClientRepository,
+        login_throttle: LoginThrottle<'a>,
+        authorization_code_issuer: AuthorizationCodeIssuer<'a>,
+        require_verified_email: bool,
+        get_timestamp: fn() -> u64,
+    ) -> Self {
+        AuthorizeUseCase {
+            password_authenticator,
+            second_factor,
+            oauth_client_repository,
+            login_throttle,
+            authorization_code_issuer,
+            require_verified_email,
+            get_timestamp,
+        }
+    }
+
+    pub fn execute(
+        mut self,
+        request: &AuthorizationRequest,
+        email: EmailAddress,
+        password: &str,
+        mfa_code: Option<&str>,
+        client_ip: &str,
+    ) -> Result<String, AuthorizeFailReason> {
+        let client = validate_authorization_request(self.oauth_client_repository, request)
+            .map_err(AuthorizeFailReason::InvalidRequest)?;
+        let now = (self.get_timestamp)();
+        let email_key = format!("email:{}", email.as_str());
+        let ip_key = format!("ip:{}", client_ip);
+        if let Some(blocked_until) = self
+            .login_throttle
+            .locked_until(&[&email_key, &ip_key], now)
+        {
+            return Err(AuthorizeFailReason::AccountLocked {
+                retry_after: blocked_until - now,
+            });
+        }
+
+        let policy = self.login_throttle.policy();
+        let user = match self.password_authenticator.authenticate(email, password) {
+            Ok(user) => user,
+            Err(_) => {
+                self.login_throttle
+                    .record_failure(&email_key, policy.max_failures, now);
+                self.login_throttle
+                    .record_failure(&ip_key, policy.ip_max_failures, now);
+                return Err(AuthorizeFailReason::InvalidCredentials);
+            }
+        };
+        self.login_throttle.reset(&email_key);
+        if self.require_verified_email && !user.email_verified {
+            return Err(AuthorizeFailReason::EmailNotVerified);
+        }
+        if let Some(credential) = self.second_factor.get_credential(&user.id) {
+            let code = mfa_code
+                .filter(|code| !code.trim().is_empty())
+                .ok_or(AuthorizeFailReason::MfaRequired)?;
+            let mfa_key = format!("mfa:{}", user.id.as_str());
+            if let Some(blocked_until) = self.login_throttle.locked_until(&[&mfa_key], now) {
+                return Err(AuthorizeFailReason::AccountLocked {
+                    retry_after: blocked_until - now,
+                });
+            }
+            if !self.second_factor.consume(credential, code, now) {
+                self.login_throttle
+                    .record_failure(&mfa_key, policy.max_failures, now);
+                return Err(AuthorizeFailReason::InvalidMfaCode);
+            }
+            self.login_throttle.reset(&mfa_key);
+        }
+
+        Ok(self
+            .authorization_code_issuer
+            .issue(client.id, user.id, request, now))
+    }
+}
+
+pub struct AuthorizationCodeIssuer<'a> {
+    authorization_code_repository: &'a mut dyn AuthorizationCodeRepository,
+    code_valid_seconds: u64,
+    generate_secret: fn() -> String,
+    hash_secret: fn(&str) -> String,
+}
+
+impl<'a> AuthorizationCodeIssuer<'a> {
+    pub fn new(
+        authorization_code_repository: &'a mut dyn AuthorizationCodeRepository,
+        code_valid_seconds: u64,
+        generate_secret: fn() -> String,
+        hash_secret: fn(&str) -> String,
+    ) -> Self {
+        AuthorizationCodeIssuer {
+            authorization_code_repository,
+            code_valid_seconds,
+            generate_secret,
+            hash_secret,
+        }
+    }
+
+    fn issue(
+        self,
+        client_id: String,
+        user_id: UserId,
+        request: &AuthorizationRequest,
+        now: u64,
+    ) -> String {
+        let code = (self.generate_secret)();
+        self.authorization_code_repository
+            .create(AuthorizationCode {
+                code_hash: (self.hash_secret)(&code),
+                client_id,
+                user_id,
+                redirect_uri: request.redirect_uri.clone(),
+                code_challenge: request.code_challenge.clone(),
+                expire_at: now + self.code_valid_seconds,
+            })
+            .expect("generated authorization code should be unique");
+
+        code
+    }
+}
+
+#[derive(Debug)]
+pub enum AuthorizeFailReason {
+    InvalidRequest(AuthorizationRequestError),
+    InvalidCredentials,
+    AccountLocked { retry_after: u64 },
+    EmailNotVerified,
+    MfaRequired,
+    InvalidMfaCode,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::application::use_case::LoginThrottlePolicy;
+    use crate::domain::entity::{LoginAttempt, OAuthClient, TotpCredential, User};
+    use crate::test_support::{
+        application::service::{FakePasswordHasher, FakePasswordValidator, FakeTotpAuthenticator},
+        domain::repository::{
+            FakeAuthorizationCodeRepository, FakeLoginAttemptRepository, FakeOAuthClientRepository,
+            FakeTotpCredentialRepository, FakeUserRepository,
+        },
+    };
+
+    const USER_ID: &str = "0196e2a4-5c1b-4f7e-9a3d-2b8c6d4e1f00";
+
+    fn fake_generate_secret() -> String {
+        "authorization_code".to_string()
+    }
+
+    fn fake_hash_secret(secret: &str) -> String {
+        format!("hashed_{}", secret)
+    }
+
+    fn fake_get_timestamp() -> u64 {
+        1747636936
+    }
+
+    fn login_throttle_policy() -> LoginThrottlePolicy {
+        LoginThrottlePolicy {
+            max_failures: 3,
+            ip_max_failures: 10,
+            backoff_base_seconds: 1,
+            backoff_max_seconds: 60,
+            lockout_seconds: 900,
+        }
+    }
+
+    fn authorization_request() -> AuthorizationRequest {
+        AuthorizationRequest {
+            response_type: "code".to_string(),
+            client_id: "spa".to_string(),
+            redirect_uri: "https://app.example.com/callback".to_string(),
+            code_challenge: "E9Melhoa2OwvFrEMTJguCHaoeK1t8URWbuGJSstw-cM".to_string(),
+            code_challenge_method: "S256".to_string(),
+        }
+    }
+
+    fn setup_oauth_client_repository() -> FakeOAuthClientRepository {
+        let mut repo = FakeOAuthClientRepository::new();
+        repo.data.insert(
+            "spa".to_string(),
+            OAuthClient {
+                id: "spa".to_string(),
+                name: "Example SPA".to_string(),
+                redirect_uris: vec!["https://app.example.com/callback".to_string()],
+                secret_hash: None,
+                scopes: vec![],
+            },
+        );
+        repo
+    }
+
+    fn setup_user_repository(email_verified: bool) -> FakeUserRepository {
+        let mut repo = FakeUserRepository::new();
+        repo.data.insert(
+            USER_ID.to_string(),
+            User {
+                id: UserId::new(USER_ID).unwrap(),
+                email: EmailAddress::new("example@example.com").unwrap(),
+                email_verified,
+                pending_email: None,
+                username: "foo".to_string(),
+                password: "bar".to_string(),
+                create_at: 1747636936,
+                update_at: 1747636936,
+            },
+        );
+        repo
+    }
+
+    fn setup_totp_credential_repository(confirmed: bool) -> FakeTotpCredentialRepository {
+        let mut repo = FakeTotpCredentialRepository::new();
+        repo.data.insert(
+            USER_ID.to_string(),
+            TotpCredential {
+                user_id: UserId::new(USER_ID).unwrap(),
+                secret: "secret".to_string(),
+                confirmed,
+                recovery_codes: vec![],
+                last_used_step: 58232897,
+            },
+        );
+        repo
+    }
+
+    #[test]
+    fn execute_given_valid_credentials_should_store_hashed_single_use_code() {
+        let stub_password_hasher = FakePasswordHasher::new("rehashed");
+        let stub_password_validator = FakePasswordValidator::new(true);
+        let stub_totp_authenticator = FakeTotpAuthenticator::new("secret", "123456", 58232898);
+        let stub_oauth_client_repository = setup_oauth_client_repository();
+        let mut stub_user_repository = setup_user_repository(true);
+        let mut mock_login_attempt_repository = FakeLoginAttemptRepository::new();
+        mock_login_attempt_repository.data.insert(
+            "email:example@example.com".to_string(),
+            LoginAttempt {
+                key: "email:example@example.com".to_string(),
+                failures: 1,
+                blocked_until: 1747636930,
+                expire_at: 1747637836,
+            },
+        );
+        let mut stub_totp_credential_repository = setup_totp_credential_repository(false);
+        let mut mock_authorization_code_repository = FakeAuthorizationCodeRepository::new();
+        let stub_login_throttle_policy = login_throttle_policy();
+        let authorize = AuthorizeUseCase::new(
+            PasswordAuthenticator::new(
+                &stub_password_hasher,
+                &stub_password_validator,
+                &mut stub_user_repository,
+            ),
+            SecondFactor::new(
+                &stub_totp_authenticator,
+                &mut stub_totp_credential_repository,
+                fake_hash_secret,
+            ),
+            &stub_oauth_client_repository,
+            LoginThrottle::new(
+                &mut mock_login_attempt_repository,
+                &stub_login_throttle_policy,
+            ),
+            AuthorizationCodeIssuer::new(
+                &mut mock_authorization_code_repository,
+                60,
+                fake_generate_secret,
+                fake_hash_secret,
+            ),
+            true,
+            fake_get_timestamp,
+        );
+
+        let result = authorize.execute(
+            &authorization_request(),
+            EmailAddress::new("example@example.com").unwrap(),
+            "bar",
+            None,
+            "127.0.0.1",
+        );
+
+        assert!(result.is_ok_and(|code| code == "authorization_code"));
+        let stored = &mock_authorization_code_repository.data["hashed_authorization_code"];
+        assert_eq!(stored.client_id, "spa");
+        assert_eq!(stored.user_id.as_str(), USER_ID);
+        assert_eq!(stored.redirect_uri, "https://app.example.com/callback");
+        assert_eq!(
+            stored.code_challenge,
+            "E9Melhoa2OwvFrEMTJguCHaoeK1t8URWbuGJSstw-cM"
+        );
+        assert_eq!(stored.expire_at, 1747636996);
+        assert!(mock_login_attempt_repository.data.is_empty());
+    }
+
+    #[test]
+    fn execute_given_invalid_authorization_request_should_not_authenticate() {
+        let stub_password_hasher = FakePasswordHasher::new("rehashed");
+        let stub_password_validator = FakePasswordValidator::new(true);
+        let stub_totp_authenticator = FakeTotpAuthenticator::new("secret", "123456", 58232898);
+        let stub_oauth_client_repository = setup_oauth_client_repository();
+        let mut stub_user_repository = setup_user_repository(true);
+        let mut stub_login_attempt_repository = FakeLoginAttemptRepository::new();
+        let mut stub_totp_credential_repository = FakeTotpCredentialRepository::new();
+        let mut mock_authorization_code_repository = FakeAuthorizationCodeRepository::new();
+        let stub_login_throttle_policy = login_throttle_policy();
+        let authorize = AuthorizeUseCase::new(
+            PasswordAuthenticator::new(
+                &stub_password_hasher,
+                &stub_password_validator,
+                &mut stub_user_repository,
+            ),
+            SecondFactor::new(
+                &stub_totp_authenticator,
+                &mut stub_totp_credential_repository,
+                fake_hash_secret,
+            ),
+            &stub_oauth_client_repository,
+            LoginThrottle::new(
+                &mut stub_login_attempt_repository,
+                &stub_login_throttle_policy,
+            ),
+            AuthorizationCodeIssuer::new(
+                &mut mock_authorization_code_repository,
+                60,
+                fake_generate_secret,
+                fake_hash_secret,
+            ),
+            false,
+            fake_get_timestamp,
+        );
+
+        let result = authorize.execute(
+            &AuthorizationRequest {
+                redirect_uri: "https://evil.example.com/callback".to_string(),
+                ..authorization_request()
+            },
+            EmailAddress::new("example@example.com").unwrap(),
+            "bar",
+            None,
+            "127.0.0.1",
+        );
+
+        assert!(matches!(
+            result,
+            Err(AuthorizeFailReason::InvalidRequest(
+                AuthorizationRequestError::InvalidRedirectUri
+            ))
+        ));
+        assert!(mock_authorization_code_repository.data.is_empty());
+    }
+
+    #[test]
+    fn execute_given_wrong_password_should_record_failures() {
+        let stub_password_hasher = FakePasswordHasher::new("rehashed");
+        let stub_password_validator = FakePasswordValidator::new(false);
+        let stub_totp_authenticator = FakeTotpAuthenticator::new("secret", "123456", 58232898);
+        let stub_oauth_client_repository = setup_oauth_client_repository();
+        let mut stub_user_repository = setup_user_repository(true);
+        let mut mock_login_attempt_repository = FakeLoginAttemptRepository::new();
+        let mut stub_totp_credential_repository = FakeTotpCredentialRepository::new();
+        let mut mock_authorization_code_repository = FakeAuthorizationCodeRepository::new();
+        let stub_login_throttle_policy = login_throttle_policy();
+        let authorize = AuthorizeUseCase::new(
+            PasswordAuthenticator::new(
+                &stub_password_hasher,
+                &stub_password_validator,
+                &mut stub_user_repository,
+            ),
+            SecondFactor::new(
+                &stub_totp_authenticator,
+                &mut stub_totp_credential_repository,
+                fake_hash_secret,
+            ),
+            &stub_oauth_client_repository,
+            LoginThrottle::new(
+                &mut mock_login_attempt_repository,
+                &stub_login_throttle_policy,
+            ),
+            AuthorizationCodeIssuer::new(
+                &mut mock_authorization_code_repository,
+                60,
+                fake_generate_secret,
+                fake_hash_secret,
+            ),
+            false,
+            fake_get_timestamp,
+        );
+
+        let result = authorize.execute(
+            &authorization_request(),
+            EmailAddress::new("example@example.com").unwrap(),
+            "wrong",
+            None,
+            "127.0.0.1",
+        );
+
+        assert!(matches!(
+            result,
+            Err(AuthorizeFailReason::InvalidCredentials)
+        ));
+        assert_eq!(
+            mock_login_attempt_repository.data["email:example@example.com"].failures,
+            1
+        );
+        assert_eq!(
+            mock_login_attempt_repository.data["ip:127.0.0.1"].failures,
+            1
+        );
+        assert!(mock_authorization_code_repository.data.is_empty());
+    }
+
+    #[test]
+    fn execute_given_locked_account_should_return_account_locked() {
+        let stub_password_hasher = FakePasswordHasher::new("rehashed");
+        let stub_password_validator = FakePasswordValidator::new(true);
+        let stub_totp_authenticator = FakeTotpAuthenticator::new("secret", "123456", 58232898);
+        let stub_oauth_client_repository = setup_oauth_client_repository();
+        let mut stub_user_repository = setup_user_repository(true);
+        let mut stub_login_attempt_repository = FakeLoginAttemptRepository::new();
+        stub_login_attempt_repository.data.insert(
+            "ip:127.0.0.1".to_string(),
+            LoginAttempt {
+                key: "ip:127.0.0.1".to_string(),
+                failures: 10,
+                blocked_until: 1747637836,
+                expire_at: 1747637836,
+            },
+        );
+        let mut stub_totp_credential_repository = FakeTotpCredentialRepository::new();
+        let mut mock_authorization_code_repository = FakeAuthorizationCodeRepository::new();
+        let stub_login_throttle_policy = login_throttle_policy();
+        let authorize = AuthorizeUseCase::new(
+            PasswordAuthenticator::new(
+                &stub_password_hasher,
+                &stub_password_validator,
+                &mut stub_user_repository,
+            ),
+            SecondFactor::new(
+                &stub_totp_authenticator,
+                &mut stub_totp_credential_repository,
+                fake_hash_secret,
+            ),
+            &stub_oauth_client_repository,
+            LoginThrottle::new(
+                &mut stub_login_attempt_repository,
+                &stub_login_throttle_policy,
+            ),
+            AuthorizationCodeIssuer::new(
+                &mut mock_authorization_code_repository,
+                60,
+                fake_generate_secret,
+                fake_hash_secret,
+            ),
+            false,
+            fake_get_timestamp,
+        );
+
+        let result = authorize.execute(
+            &authorization_request(),
+            EmailAddress::new("example@example.com").unwrap(),
+            "bar",
+            None,
+            "127.0.0.1",
+        );
+
+        assert!(matches!(
+            result,
+            Err(AuthorizeFailReason::AccountLocked { retry_after: 900 })
+        ));
+        assert!(mock_authorization_code_repository.data.is_empty());
+    }
+
+    #[test]
+    fn execute_given_unverified_email_should_require_verification_when_configured() {
+        let stub_password_hasher = FakePasswordHasher::new("rehashed");
+        let stub_password_validator = FakePasswordValidator::new(true);
+        let stub_totp_authenticator = FakeTotpAuthenticator::new("secret", "123456", 58232898);
+        let stub_oauth_client_repository = setup_oauth_client_repository();
+        let mut stub_user_repository = setup_user_repository(false);
+        let mut stub_login_attempt_repository = FakeLoginAttemptRepository::new();
+        let mut stub_totp_credential_repository = FakeTotpCredentialRepository::new();
+        let mut mock_authorization_code_repository = FakeAuthorizationCodeRepository::new();
+        let stub_login_throttle_policy = login_throttle_policy();
+        let authorize = AuthorizeUseCase::new(
+            PasswordAuthenticator::new(
+                &stub_password_hasher,
+                &stub_password_validator,
+                &mut stub_user_repository,
+            ),
+            SecondFactor::new(
+                &stub_totp_authenticator,
+                &mut stub_totp_credential_repository,
+                fake_hash_secret,
+            ),
+            &stub_oauth_client_repository,
+            LoginThrottle::new(
+                &mut stub_login_attempt_repository,
+                &stub_login_throttle_policy,
+            ),
+            AuthorizationCodeIssuer::new(
+                &mut mock_authorization_code_repository,
+                60,
+                fake_generate_secret,
+                fake_hash_secret,
+            ),
+            true,
+            fake_get_timestamp,
+        );
+
+        let result = authorize.execute(
+            &authorization_request(),
+            EmailAddress::new("example@example.com").unwrap(),
+            "bar",
+            None,
+            "127.0.0.1",
+        );
+
+        assert!(matches!(result, Err(AuthorizeFailReason::EmailNotVerified)));
+        assert!(mock_authorization_code_repository.data.is_empty());
+    }
+
+    #[test]
+    fn execute_given_confirmed_totp_should_require_valid_unused_code() {
+        let test_cases = vec![
+            (None, 58232898, true),
+            (Some(""), 58232898, true),
+            (Some("000000"), 58232898, false),
+            (Some("123456"), 58232897, false),
+        ];
+
+        for (mfa_code, step, is_required) in test_cases {
+            let stub_password_hasher = FakePasswordHasher::new("rehashed");
+            let stub_password_validator = FakePasswordValidator::new(true);
+            let stub_totp_authenticator = FakeTotpAuthenticator::new("secret", "123456", step);
+            let stub_oauth_client_repository = setup_oauth_client_repository();
+            let mut stub_user_repository = setup_user_repository(true);
+            let mut stub_login_attempt_repository = FakeLoginAttemptRepository::new();
+            let mut stub_totp_credential_repository = setup_totp_credential_repository(true);
+            let mut mock_authorization_code_repository = FakeAuthorizationCodeRepository::new();
+            let stub_login_throttle_policy = login_throttle_policy();
+            let authorize = AuthorizeUseCase::new(
+                PasswordAuthenticator::new(
+                    &stub_password_hasher,
+                    &stub_password_validator,
+                    &mut stub_user_repository,
+                ),
+                SecondFactor::new(
+                    &stub_totp_authenticator,
+                    &mut stub_totp_credential_repository,
+                    fake_hash_secret,
+                ),
+                &stub_oauth_client_repository,
+                LoginThrottle::new(
+                    &mut stub_login_attempt_repository,
+                    &stub_login_throttle_policy,
+                ),
+                AuthorizationCodeIssuer::new(
+                    &mut mock_authorization_code_repository,
+                    60,
+                    fake_generate_secret,
+                    fake_hash_secret,
+                ),
+                false,
+                fake_get_timestamp,
+            );
+
+            let result = authorize.execute(
+                &authorization_request(),
+                EmailAddress::new("example@example.com").unwrap(),
+                "bar",
+                mfa_code,
+                "127.0.0.1",
+            );
+
+            match result {
+                Err(AuthorizeFailReason::MfaRequired) => assert!(is_required),
+                Err(AuthorizeFailReason::InvalidMfaCode) => assert!(!is_required),
+                _ => panic!("second factor should be enforced"),
+            }
+            assert!(mock_authorization_code_repository.data.is_empty());
+        }
+    }
+
+    #[test]
+    fn execute_given_confirmed_totp_and_valid_code_should_consume_step() {
+        let stub_password_hasher = FakePasswordHasher::new("rehashed");
+        let stub_password_validator = FakePasswordValidator::new(true);
+        let stub_totp_authenticator = FakeTotpAuthenticator::new("secret", "123456", 58232898);
+        let stub_oauth_client_repository = setup_oauth_client_repository();
+        let mut stub_user_repository = setup_user_repository(true);
+        let mut stub_login_attempt_repository = FakeLoginAttemptRepository::new();
+        let mut mock_totp_credential_repository = setup_totp_credential_repository(true);
+        let mut mock_authorization_code_repository = FakeAuthorizationCodeRepository::new();
+        let stub_login_throttle_policy = login_throttle_policy();
+        let authorize = AuthorizeUseCase::new(
+            PasswordAuthenticator::new(
+                &stub_password_hasher,
+                &stub_password_validator,
+                &mut stub_user_repository,
+            ),
+            SecondFactor::new(
+                &stub_totp_authenticator,
+                &mut mock_totp_credential_repository,
+                fake_hash_secret,
+            ),
+            &stub_oauth_client_repository,
+            LoginThrottle::new(
+                &mut stub_login_attempt_repository,
+                &stub_login_throttle_policy,
+            ),
+            AuthorizationCodeIssuer::new(
+                &mut mock_authorization_code_repository,
+                60,
+                fake_generate_secret,
+                fake_hash_secret,
+            ),
+            false,
+            fake_get_timestamp,
+        );
+
+        let result = authorize.execute(
+            &authorization_request(),
+            EmailAddress::new("example@example.com").unwrap(),
+            "bar",
+            Some("123456"),
+            "127.0.0.1",
+        );
+
+        assert!(result.is_ok());
+        assert_eq!(
+            mock_totp_credential_repository.data[USER_ID].last_used_step,
+            58232898
+        );
+        assert!(
+            mock_authorization_code_repository
+                .data
+                .contains_key("hashed_authorization_code")
+        );
+    }
+}
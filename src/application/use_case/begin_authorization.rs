@@ -0,0 +1,185 @@
+use crate::domain::{entity::OAuthClient, repository::OAuthClientRepository};
+
+pub struct BeginAuthorizationUseCase<'a> {
+    oauth_client_repository: &'a dyn OAuthClientRepository,
+}
+
+impl<'a> BeginAuthorizationUseCase<'a> {
+    pub fn new(oauth_client_repository: &'a dyn OAuthClientRepository) -> Self {
+        BeginAuthorizationUseCase {
+            oauth_client_repository,
+        }
+    }
+
+    pub fn execute(
+        self,
+        request: &AuthorizationRequest,
+    ) -> Result<OAuthClient, AuthorizationRequestError> {
+        validate_authorization_request(self.oauth_client_repository, request)
+    }
+}
+
+pub(super) fn validate_authorization_request(
+    oauth_client_repository: &dyn OAuthClientRepository,
+    request: &AuthorizationRequest,
+) -> Result<OAuthClient, AuthorizationRequestError> {
+    let client = oauth_client_repository
+        .get(&request.client_id)
+        .map_err(|_| AuthorizationRequestError::InvalidClient)?;
+    if !client.redirect_uris.contains(&request.redirect_uri) {
+        return Err(AuthorizationRequestError::InvalidRedirectUri);
+    }
+    if request.response_type != "code" {
+        return Err(AuthorizationRequestError::UnsupportedResponseType);
+    }
+    if request.code_challenge_method != "S256" || !is_pkce_value(&request.code_challenge) {
+        return Err(AuthorizationRequestError::InvalidCodeChallenge);
+    }
+
+    Ok(client)
+}
+
+pub(super) fn is_pkce_value(value: &str) -> bool {
+    (43..=128).contains(&value.len())
+        && value
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'-' | b'.' | b'_' | b'~'))
+}
+
+pub struct AuthorizationRequest {
+    pub response_type: String,
+    pub client_id: String,
+    pub redirect_uri: String,
+    pub code_challenge: String,
+    pub code_challenge_method: String,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum AuthorizationRequestError {
+    InvalidClient,
+    InvalidRedirectUri,
+    UnsupportedResponseType,
+    InvalidCodeChallenge,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_support::domain::repository::FakeOAuthClientRepository;
+
+    const CODE_CHALLENGE: &str = "E9Melhoa2OwvFrEMTJguCHaoeK1t8URWbuGJSstw-cM";
+
+    fn setup_repository() -> FakeOAuthClientRepository {
+        let mut repo = FakeOAuthClientRepository::new();
+        repo.data.insert(
+            "spa".to_string(),
+            OAuthClient {
+                id: "spa".to_string(),
+                name: "Example SPA".to_string(),
+                redirect_uris: vec!["https://app.example.com/callback".to_string()],
+                secret_hash: None,
+                scopes: vec![],
+            },
+        );
+        repo
+    }
+
+    fn authorization_request() -> AuthorizationRequest {
+        AuthorizationRequest {
+            response_type: "code".to_string(),
+            client_id: "spa".to_string(),
+            redirect_uri: "https://app.example.com/callback".to_string(),
+            code_challenge: CODE_CHALLENGE.to_string(),
+            code_challenge_method: "S256".to_string(),
+        }
+    }
+
+    #[test]
+    fn execute_given_valid_request_should_return_client() {
+        let stub_oauth_client_repository = setup_repository();
+        let begin_authorization = BeginAuthorizationUseCase::new(&stub_oauth_client_repository);
+
+        let result = begin_authorization.execute(&authorization_request());
+
+        assert!(result.is_ok_and(|client| client.name == "Example SPA"));
+    }
+
+    #[test]
+    fn execute_given_unknown_client_or_redirect_uri_should_not_trust_redirect() {
+        let test_cases = vec![
+            (
+                AuthorizationRequest {
+                    client_id: "unknown".to_string(),
+                    ..authorization_request()
+                },
+                AuthorizationRequestError::InvalidClient,
+            ),
+            (
+                AuthorizationRequest {
+                    redirect_uri: "https://app.example.com/callback/".to_string(),
+                    ..authorization_request()
+                },
+                AuthorizationRequestError::InvalidRedirectUri,
+            ),
+            (
+                AuthorizationRequest {
+                    redirect_uri: "https://app.example.com/callback?next=evil".to_string(),
+                    ..authorization_request()
+                },
+                AuthorizationRequestError::InvalidRedirectUri,
+            ),
+        ];
+
+        for (request, expected) in test_cases {
+            let stub_oauth_client_repository = setup_repository();
+            let begin_authorization = BeginAuthorizationUseCase::new(&stub_oauth_client_repository);
+
+            let result = begin_authorization.execute(&request);
+
+            assert!(result.is_err_and(|err| err == expected));
+        }
+    }
+
+    #[test]
+    fn execute_given_unsupported_response_type_or_missing_pkce_should_return_error() {
+        let test_cases = vec![
+            (
+                AuthorizationRequest {
+                    response_type: "token".to_string(),
+                    ..authorization_request()
+                },
+                AuthorizationRequestError::UnsupportedResponseType,
+            ),
+            (
+                AuthorizationRequest {
+                    code_challenge_method: "plain".to_string(),
+                    ..authorization_request()
+                },
+                AuthorizationRequestError::InvalidCodeChallenge,
+            ),
+            (
+                AuthorizationRequest {
+                    code_challenge: "".to_string(),
+                    ..authorization_request()
+                },
+                AuthorizationRequestError::InvalidCodeChallenge,
+            ),
+            (
+                AuthorizationRequest {
+                    code_challenge: format!("{}+", &CODE_CHALLENGE[..42]),
+                    ..authorization_request()
+                },
+                AuthorizationRequestError::InvalidCodeChallenge,
+            ),
+        ];
+
+        for (request, expected) in test_cases {
+            let stub_oauth_client_repository = setup_repository();
+            let begin_authorization = BeginAuthorizationUseCase::new(&stub_oauth_client_repository);
+
+            let result = begin_authorization.execute(&request);
+
+            assert!(result.is_err_and(|err| err == expected));
+        }
+    }
+}
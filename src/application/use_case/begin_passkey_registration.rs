@@ -0,0 +1,169 @@
+use crate::domain::{
+    entity::PasskeyChallenge,
+    error,
+    repository::{PasskeyChallengeRepository, PasskeyCredentialRepository, UserRepository},
+    value_object::UserId,
+};
+
+pub struct BeginPasskeyRegistrationUseCase<'a> {
+    user_repository: &'a dyn UserRepository,
+    passkey_credential_repository: &'a dyn PasskeyCredentialRepository,
+    passkey_challenge_repository: &'a mut dyn PasskeyChallengeRepository,
+    challenge_valid_seconds: u64,
+    generate_secret: fn() -> String,
+    get_timestamp: fn() -> u64,
+}
+
+impl<'a> BeginPasskeyRegistrationUseCase<'a> {
+    pub fn new(
+        user_repository: &'a dyn UserRepository,
+        passkey_credential_repository: &'a dyn PasskeyCredentialRepository,
+        passkey_challenge_repository: &'a mut dyn PasskeyChallengeRepository,
+        challenge_valid_seconds: u64,
+        generate_secret: fn() -> String,
+        get_timestamp: fn() -> u64,
+    ) -> Self {
+        BeginPasskeyRegistrationUseCase {
+            user_repository,
+            passkey_credential_repository,
+            passkey_challenge_repository,
+            challenge_valid_seconds,
+            generate_secret,
+            get_timestamp,
+        }
+    }
+
+    pub fn execute(self, id: &UserId) -> Result<PasskeyRegistrationOptions, error::EntityNotExist> {
+        let user = self.user_repository.get_by_id(id)?;
+        let challenge = (self.generate_secret)();
+        self.passkey_challenge_repository
+            .create(PasskeyChallenge {
+                challenge: challenge.clone(),
+                user_id: Some(user.id.clone()),
+                expire_at: (self.get_timestamp)() + self.challenge_valid_seconds,
+            })
+            .expect("generated passkey challenge should be unique");
+
+        Ok(PasskeyRegistrationOptions {
+            challenge,
+            exclude_credentials: self
+                .passkey_credential_repository
+                .list_by_user(&user.id)
+                .into_iter()
+                .map(|credential| credential.id)
+                .collect(),
+            user_id: user.id.as_str().to_string(),
+            username: user.username,
+            email: user.email.as_str().to_string(),
+        })
+    }
+}
+
+pub struct PasskeyRegistrationOptions {
+    pub challenge: String,
+    pub exclude_credentials: Vec<String>,
+    pub user_id: String,
+    pub username: String,
+    pub email: String,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::domain::{
+        entity::{PasskeyCredential, User},
+        value_object::EmailAddress,
+    };
+    use crate::test_support::domain::repository::{
+        FakePasskeyChallengeRepository, FakePasskeyCredentialRepository, FakeUserRepository,
+    };
+
+    const USER_ID: &str = "0196e2a4-5c1b-4f7e-9a3d-2b8c6d4e1f00";
+
+    fn fake_generate_secret() -> String {
+        "challenge".to_string()
+    }
+
+    fn fake_get_timestamp() -> u64 {
+        1747636936
+    }
+
+    fn setup_user_repository() -> FakeUserRepository {
+        let mut repo = FakeUserRepository::new();
+        repo.data.insert(
+            USER_ID.to_string(),
+            User {
+                id: UserId::new(USER_ID).unwrap(),
+                email: EmailAddress::new("example@example.com").unwrap(),
+                email_verified: true,
+                pending_email: None,
+                username: "foo".to_string(),
+                password: "hashed".to_string(),
+                create_at: 1747636936,
+                update_at: 1747636936,
+            },
+        );
+        repo
+    }
+
+    #[test]
+    fn execute_given_user_should_store_challenge_and_exclude_existing_credentials() {
+        let stub_user_repository = setup_user_repository();
+        let mut stub_passkey_credential_repository = FakePasskeyCredentialRepository::new();
+        stub_passkey_credential_repository.data.insert(
+            "existing_credential".to_string(),
+            PasskeyCredential {
+                id: "existing_credential".to_string(),
+                user_id: UserId::new(USER_ID).unwrap(),
+                public_key: "public_key".to_string(),
+                sign_count: 0,
+                create_at: 1747636936,
+            },
+        );
+        let mut mock_passkey_challenge_repository = FakePasskeyChallengeRepository::new();
+        let begin_passkey_registration = BeginPasskeyRegistrationUseCase::new(
+            &stub_user_repository,
+            &stub_passkey_credential_repository,
+            &mut mock_passkey_challenge_repository,
+            300,
+            fake_generate_secret,
+            fake_get_timestamp,
+        );
+
+        let result = begin_passkey_registration.execute(&UserId::new(USER_ID).unwrap());
+
+        assert!(result.is_ok_and(|options| {
+            options.challenge == "challenge"
+                && options.exclude_credentials == vec!["existing_credential"]
+                && options.user_id == USER_ID
+                && options.username == "foo"
+                && options.email == "example@example.com"
+        }));
+        let challenge = &mock_passkey_challenge_repository.data["challenge"];
+        assert_eq!(
+            challenge.user_id.as_ref().map(UserId::as_str),
+            Some(USER_ID)
+        );
+        assert_eq!(challenge.expire_at, 1747637236);
+    }
+
+    #[test]
+    fn execute_given_not_exist_user_should_return_entity_not_exist() {
+        let stub_user_repository = FakeUserRepository::new();
+        let stub_passkey_credential_repository = FakePasskeyCredentialRepository::new();
+        let mut mock_passkey_challenge_repository = FakePasskeyChallengeRepository::new();
+        let begin_passkey_registration = BeginPasskeyRegistrationUseCase::new(
+            &stub_user_repository,
+            &stub_passkey_credential_repository,
+            &mut mock_passkey_challenge_repository,
+            300,
+            fake_generate_secret,
+            fake_get_timestamp,
+        );
+
+        let result = begin_passkey_registration.execute(&UserId::new(USER_ID).unwrap());
+
+        assert!(result.is_err());
+        assert!(mock_passkey_challenge_repository.data.is_empty());
+    }
+}
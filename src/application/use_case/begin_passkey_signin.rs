@@ -0,0 +1,69 @@
+use crate::domain::{entity::PasskeyChallenge, repository::PasskeyChallengeRepository};
+
+pub struct BeginPasskeySignInUseCase<'a> {
+    passkey_challenge_repository: &'a mut dyn PasskeyChallengeRepository,
+    challenge_valid_seconds: u64,
+    generate_secret: fn() -> String,
+    get_timestamp: fn() -> u64,
+}
+
+impl<'a> BeginPasskeySignInUseCase<'a> {
+    pub fn new(
+        passkey_challenge_repository: &'a mut dyn PasskeyChallengeRepository,
+        challenge_valid_seconds: u64,
+        generate_secret: fn() -> String,
+        get_timestamp: fn() -> u64,
+    ) -> Self {
+        BeginPasskeySignInUseCase {
+            passkey_challenge_repository,
+            challenge_valid_seconds,
+            generate_secret,
+            get_timestamp,
+        }
+    }
+
+    pub fn execute(self) -> String {
+        let challenge = (self.generate_secret)();
+        self.passkey_challenge_repository
+            .create(PasskeyChallenge {
+                challenge: challenge.clone(),
+                user_id: None,
+                expire_at: (self.get_timestamp)() + self.challenge_valid_seconds,
+            })
+            .expect("generated passkey challenge should be unique");
+
+        challenge
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_support::domain::repository::FakePasskeyChallengeRepository;
+
+    fn fake_generate_secret() -> String {
+        "challenge".to_string()
+    }
+
+    fn fake_get_timestamp() -> u64 {
+        1747636936
+    }
+
+    #[test]
+    fn execute_should_store_challenge_without_user() {
+        let mut mock_passkey_challenge_repository = FakePasskeyChallengeRepository::new();
+        let begin_passkey_signin = BeginPasskeySignInUseCase::new(
+            &mut mock_passkey_challenge_repository,
+            300,
+            fake_generate_secret,
+            fake_get_timestamp,
+        );
+
+        let challenge = begin_passkey_signin.execute();
+
+        assert_eq!(challenge, "challenge");
+        let stored = &mock_passkey_challenge_repository.data["challenge"];
+        assert!(stored.user_id.is_none());
+        assert_eq!(stored.expire_at, 1747637236);
+    }
+}
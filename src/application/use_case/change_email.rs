@@ -0,0 +1,211 @@
+use crate::application::service::auth::PasswordValidator;
+use crate::domain::{
+    repository::UserRepository,
+    value_object::{EmailAddress, UserId},
+};
+
+pub struct ChangeEmailUseCase<'a> {
+    password_validator: &'a dyn PasswordValidator,
+    user_repository: &'a mut dyn UserRepository,
+    get_timestamp: fn() -> u64,
+}
+
+impl<'a> ChangeEmailUseCase<'a> {
+    pub fn new(
+        password_validator: &'a dyn PasswordValidator,
+        user_repository: &'a mut dyn UserRepository,
+        get_timestamp: fn() -> u64,
+    ) -> Self {
+        ChangeEmailUseCase {
+            password_validator,
+            user_repository,
+            get_timestamp,
+        }
+    }
+
+    pub fn execute(
+        self,
+        id: &UserId,
+        current_password: &str,
+        email: EmailAddress,
+    ) -> Result<Option<EmailAddress>, ChangeEmailFailReason> {
+        let mut user = self
+            .user_repository
+            .get_by_id(id)
+            .map_err(|_| ChangeEmailFailReason::UserNotExist)?;
+        if !self
+            .password_validator
+            .verify(current_password, &user.password)
+        {
+            return Err(ChangeEmailFailReason::IncorrectPassword);
+        }
+        if user.email.as_str() == email.as_str() {
+            if user.pending_email.is_none() {
+                return Ok(None);
+            }
+            user.pending_email = None;
+        } else {
+            if self.user_repository.get(email.clone()).is_ok() {
+                return Err(ChangeEmailFailReason::EmailConflict);
+            }
+            user.pending_email = Some(email);
+        }
+
+        let pending_email = user.pending_email.clone();
+        user.update_at = (self.get_timestamp)();
+        self.user_repository
+            .update(user)
+            .map_err(|_| ChangeEmailFailReason::UserNotExist)?;
+
+        Ok(pending_email)
+    }
+}
+
+#[derive(Debug)]
+pub enum ChangeEmailFailReason {
+    UserNotExist,
+    IncorrectPassword,
+    EmailConflict,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::domain::entity::User;
+    use crate::test_support::{
+        application::service::FakePasswordValidator, domain::repository::FakeUserRepository,
+    };
+
+    const USER_ID: &str = "0196e2a4-5c1b-4f7e-9a3d-2b8c6d4e1f00";
+    const OTHER_USER_ID: &str = "0196e2a4-5c1b-4f7e-9a3d-2b8c6d4e1f01";
+
+    fn fake_get_timestamp() -> u64 {
+        1747640536
+    }
+
+    fn create_user(id: &str, email: &str) -> User {
+        User {
+            id: UserId::new(id).unwrap(),
+            email: EmailAddress::new(email).unwrap(),
+            email_verified: true,
+            pending_email: None,
+            username: "foo".to_string(),
+            password: "hashed".to_string(),
+            create_at: 1747636936,
+            update_at: 1747636936,
+        }
+    }
+
+    fn setup_repository() -> FakeUserRepository {
+        let mut repo = FakeUserRepository::new();
+        repo.data.insert(
+            USER_ID.to_string(),
+            create_user(USER_ID, "example@example.com"),
+        );
+        repo.data.insert(
+            OTHER_USER_ID.to_string(),
+            create_user(OTHER_USER_ID, "taken@example.com"),
+        );
+        repo
+    }
+
+    #[test]
+    fn execute_given_new_email_should_store_it_as_pending() {
+        let stub_password_validator = FakePasswordValidator::new(true);
+        let mut mock_user_repository = setup_repository();
+        let change_email = ChangeEmailUseCase::new(
+            &stub_password_validator,
+            &mut mock_user_repository,
+            fake_get_timestamp,
+        );
+
+        let result = change_email.execute(
+            &UserId::new(USER_ID).unwrap(),
+            "password",
+            EmailAddress::new("changed@example.com").unwrap(),
+        );
+
+        assert!(result.is_ok_and(|pending| {
+            pending.is_some_and(|email| email.as_str() == "changed@example.com")
+        }));
+        let user = &mock_user_repository.data[USER_ID];
+        assert_eq!(user.email.as_str(), "example@example.com");
+        assert!(user.email_verified);
+        assert!(
+            user.pending_email
+                .as_ref()
+                .is_some_and(|email| email.as_str() == "changed@example.com")
+        );
+        assert_eq!(user.update_at, 1747640536);
+    }
+
+    #[test]
+    fn execute_given_incorrect_password_should_return_incorrect_password() {
+        let stub_password_validator = FakePasswordValidator::new(false);
+        let mut mock_user_repository = setup_repository();
+        let change_email = ChangeEmailUseCase::new(
+            &stub_password_validator,
+            &mut mock_user_repository,
+            fake_get_timestamp,
+        );
+
+        let result = change_email.execute(
+            &UserId::new(USER_ID).unwrap(),
+            "wrong",
+            EmailAddress::new("changed@example.com").unwrap(),
+        );
+
+        assert!(matches!(
+            result,
+            Err(ChangeEmailFailReason::IncorrectPassword)
+        ));
+        assert!(mock_user_repository.data[USER_ID].pending_email.is_none());
+    }
+
+    #[test]
+    fn execute_given_email_of_another_user_should_return_email_conflict() {
+        let stub_password_validator = FakePasswordValidator::new(true);
+        let mut mock_user_repository = setup_repository();
+        let change_email = ChangeEmailUseCase::new(
+            &stub_password_validator,
+            &mut mock_user_repository,
+            fake_get_timestamp,
+        );
+
+        let result = change_email.execute(
+            &UserId::new(USER_ID).unwrap(),
+            "password",
+            EmailAddress::new("taken@example.com").unwrap(),
+        );
+
+        assert!(matches!(result, Err(ChangeEmailFailReason::EmailConflict)));
+        assert!(mock_user_repository.data[USER_ID].pending_email.is_none());
+    }
+
+    #[test]
+    fn execute_given_current_email_should_cancel_pending_change() {
+        let stub_password_validator = FakePasswordValidator::new(true);
+        let mut mock_user_repository = setup_repository();
+        mock_user_repository
+            .data
+            .get_mut(USER_ID)
+            .unwrap()
+            .pending_email = Some(EmailAddress::new("changed@example.com").unwrap());
+        let change_email = ChangeEmailUseCase::new(
+            &stub_password_validator,
+            &mut mock_user_repository,
+            fake_get_timestamp,
+        );
+
+        let result = change_email.execute(
+            &UserId::new(USER_ID).unwrap(),
+            "password",
+            EmailAddress::new("example@example.com").unwrap(),
+        );
+
+        assert!(result.is_ok_and(|pending| pending.is_none()));
+        let user = &mock_user_repository.data[USER_ID];
+        assert!(user.email_verified);
+        assert!(user.pending_email.is_none());
+    }
+}
@@ -0,0 +1,206 @@
+use super::signup::PasswordRules;
+use crate::application::service::auth::{PasswordHasher, PasswordValidator};
+use crate::domain::{error, repository::UserRepository, value_object::UserId};
+
+pub struct ChangePasswordUseCase<'a> {
+    password_hasher: &'a dyn PasswordHasher,
+    password_validator: &'a dyn PasswordValidator,
+    password_rules: PasswordRules<'a>,
+    user_repository: &'a mut dyn UserRepository,
+    get_timestamp: fn() -> u64,
+}
+
+impl<'a> ChangePasswordUseCase<'a> {
+    pub fn new(
+        password_hasher: &'a dyn PasswordHasher,
+        password_validator: &'a dyn PasswordValidator,
+        password_rules: PasswordRules<'a>,
+        user_repository: &'a mut dyn UserRepository,
+        get_timestamp: fn() -> u64,
+    ) -> Self {
+        ChangePasswordUseCase {
+            password_hasher,
+            password_validator,
+            password_rules,
+            user_repository,
+            get_timestamp,
+        }
+    }
+
+    pub fn execute(
+        self,
+        id: &UserId,
+        current_password: &str,
+        new_password: &str,
+    ) -> Result<(), ChangePasswordFailReason> {
+        let mut user = self
+            .user_repository
+            .get_by_id(id)
+            .map_err(|_| ChangePasswordFailReason::UserNotExist)?;
+        if !self
+            .password_validator
+            .verify(current_password, &user.password)
+        {
+            return Err(ChangePasswordFailReason::IncorrectPassword);
+        }
+        let password = self
+            .password_rules
+            .check(new_password, &user.email, &user.username)
+            .map_err(ChangePasswordFailReason::InvalidPassword)?;
+
+        user.password = self.password_hasher.hash(password.as_str());
+        user.update_at = (self.get_timestamp)();
+        self.user_repository
+            .update(user)
+            .map_err(|_| ChangePasswordFailReason::UserNotExist)
+    }
+}
+
+#[derive(Debug)]
+pub enum ChangePasswordFailReason {
+    UserNotExist,
+    IncorrectPassword,
+    InvalidPassword(error::ValidationError),
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::domain::{
+        entity::User,
+        value_object::{EmailAddress, PasswordPolicy},
+    };
+    use crate::test_support::{
+        application::service::{
+            FakeBreachedPasswordChecker, FakePasswordHasher, FakePasswordValidator,
+        },
+        domain::repository::FakeUserRepository,
+    };
+
+    const USER_ID: &str = "0196e2a4-5c1b-4f7e-9a3d-2b8c6d4e1f00";
+
+    fn fake_get_timestamp() -> u64 {
+        1747640536
+    }
+
+    fn password_policy() -> PasswordPolicy {
+        PasswordPolicy {
+            min_length: 8,
+            max_length: 64,
+            require_lowercase: false,
+            require_uppercase: false,
+            require_digit: false,
+            require_symbol: false,
+            forbid_common: true,
+        }
+    }
+
+    fn setup_repository() -> FakeUserRepository {
+        let mut repo = FakeUserRepository::new();
+        repo.data.insert(
+            USER_ID.to_string(),
+            User {
+                id: UserId::new(USER_ID).unwrap(),
+                email: EmailAddress::new("example@example.com").unwrap(),
+                email_verified: true,
+                pending_email: None,
+                username: "foo".to_string(),
+                password: "old_hashed".to_string(),
+                create_at: 1747636936,
+                update_at: 1747636936,
+            },
+        );
+        repo
+    }
+
+    #[test]
+    fn execute_given_correct_current_password_should_persist_new_hash() {
+        let stub_password_hasher = FakePasswordHasher::new("new_hashed");
+        let mock_password_validator = FakePasswordValidator::new(true);
+        let stub_password_policy = password_policy();
+        let stub_breached_password_checker = FakeBreachedPasswordChecker::new(false);
+        let mut mock_user_repository = setup_repository();
+        let change_password = ChangePasswordUseCase::new(
+            &stub_password_hasher,
+            &mock_password_validator,
+            PasswordRules::new(&stub_password_policy, &stub_breached_password_checker),
+            &mut mock_user_repository,
+            fake_get_timestamp,
+        );
+
+        let result = change_password.execute(
+            &UserId::new(USER_ID).unwrap(),
+            "current password",
+            "correct horse",
+        );
+
+        assert!(result.is_ok());
+        assert_eq!(
+            *mock_password_validator.verified_hashes.borrow(),
+            vec!["old_hashed"]
+        );
+        let user = &mock_user_repository.data[USER_ID];
+        assert_eq!(user.password, "new_hashed");
+        assert_eq!(user.update_at, 1747640536);
+    }
+
+    #[test]
+    fn execute_given_incorrect_current_password_should_keep_password() {
+        let stub_password_hasher = FakePasswordHasher::new("new_hashed");
+        let stub_password_validator = FakePasswordValidator::new(false);
+        let stub_password_policy = password_policy();
+        let stub_breached_password_checker = FakeBreachedPasswordChecker::new(false);
+        let mut mock_user_repository = setup_repository();
+        let change_password = ChangePasswordUseCase::new(
+            &stub_password_hasher,
+            &stub_password_validator,
+            PasswordRules::new(&stub_password_policy, &stub_breached_password_checker),
+            &mut mock_user_repository,
+            fake_get_timestamp,
+        );
+
+        let result = change_password.execute(
+            &UserId::new(USER_ID).unwrap(),
+            "wrong password",
+            "correct horse",
+        );
+
+        assert!(matches!(
+            result,
+            Err(ChangePasswordFailReason::IncorrectPassword)
+        ));
+        assert_eq!(mock_user_repository.data[USER_ID].password, "old_hashed");
+    }
+
+    #[test]
+    fn execute_given_password_violating_policy_should_return_invalid_password() {
+        let test_cases = vec![("short", false), ("correct horse", true)];
+
+        for (password, is_breached) in test_cases {
+            let stub_password_hasher = FakePasswordHasher::new("new_hashed");
+            let stub_password_validator = FakePasswordValidator::new(true);
+            let stub_password_policy = password_policy();
+            let stub_breached_password_checker = FakeBreachedPasswordChecker::new(is_breached);
+            let mut mock_user_repository = setup_repository();
+            let change_password = ChangePasswordUseCase::new(
+                &stub_password_hasher,
+                &stub_password_validator,
+                PasswordRules::new(&stub_password_policy, &stub_breached_password_checker),
+                &mut mock_user_repository,
+                fake_get_timestamp,
+            );
+
+            let result = change_password.execute(
+                &UserId::new(USER_ID).unwrap(),
+                "current password",
+                password,
+            );
+
+            assert!(matches!(
+                result,
+                Err(ChangePasswordFailReason::InvalidPassword(_))
+            ));
+            assert_eq!(mock_user_repository.data[USER_ID].password, "old_hashed");
+        }
+    }
+}
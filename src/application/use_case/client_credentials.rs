@@ -0,0 +1,272 @@
+use crate::application::service::auth::{
+    PasswordHasher, PasswordValidator, TokenIssuer, TokenSubject,
+};
+use crate::domain::{entity::OAuthClient, repository::OAuthClientRepository};
+
+pub struct ClientCredentialsUseCase<'a> {
+    password_hasher: &'a dyn PasswordHasher,
+    password_validator: &'a dyn PasswordValidator,
+    access_token_issuer: &'a dyn TokenIssuer,
+    oauth_client_repository: &'a dyn OAuthClientRepository,
+    generate_id: fn() -> String,
+}
+
+impl<'a> ClientCredentialsUseCase<'a> {
+    pub fn new(
+        password_hasher: &'a dyn PasswordHasher,
+        password_validator: &'a dyn PasswordValidator,
+        access_token_issuer: &'a dyn TokenIssuer,
+        oauth_client_repository: &'a dyn OAuthClientRepository,
+        generate_id: fn() -> String,
+    ) -> Self {
+        ClientCredentialsUseCase {
+            password_hasher,
+            password_validator,
+            access_token_issuer,
+            oauth_client_repository,
+            generate_id,
+        }
+    }
+
+    pub fn execute(
+        self,
+        client_id: &str,
+        client_secret: &str,
+        scope: Option<&str>,
+    ) -> Result<ClientCredentialsResult, ClientCredentialsFailReason> {
+        let client = authenticate_client(
+            self.password_hasher,
+            self.password_validator,
+            self.oauth_client_repository,
+            client_id,
+            client_secret,
+        )
+        .ok_or(ClientCredentialsFailReason::InvalidClient)?;
+
+        let mut scopes: Vec<&str> = vec![];
+        for requested in scope.unwrap_or_default().split_whitespace() {
+            if !client.scopes.iter().any(|allowed| allowed == requested) {
+                return Err(ClientCredentialsFailReason::InvalidScope);
+            }
+            if !scopes.contains(&requested) {
+                scopes.push(requested);
+            }
+        }
+        if scopes.is_empty() {
+            scopes = client.scopes.iter().map(String::as_str).collect();
+        }
+        let scope = Some(scopes.join(" ")).filter(|scope| !scope.is_empty());
+
+        Ok(ClientCredentialsResult {
+            access_token: self.access_token_issuer.issue(&TokenSubject {
+                sub: client.id.clone(),
+                jti: Some((self.generate_id)()),
+                fid: None,
+                email: None,
+                scope: scope.clone(),
+            }),
+            scope,
+        })
+    }
+}
+
+pub struct ClientAuthenticator<'a> {
+    password_hasher: &'a dyn PasswordHasher,
+    password_validator: &'a dyn PasswordValidator,
+    oauth_client_repository: &'a dyn OAuthClientRepository,
+}
+
+impl<'a> ClientAuthenticator<'a> {
+    pub fn new(
+        password_hasher: &'a dyn PasswordHasher,
+        password_validator: &'a dyn PasswordValidator,
+        oauth_client_repository: &'a dyn OAuthClientRepository,
+    ) -> Self {
+        ClientAuthenticator {
+            password_hasher,
+            password_validator,
+            oauth_client_repository,
+        }
+    }
+
+    pub(super) fn is_confidential(&self, client_id: &str) -> bool {
+        self.oauth_client_repository
+            .get(client_id)
+            .is_ok_and(|client| client.secret_hash.is_some())
+    }
+
+    pub(super) fn authenticate(&self, client_id: &str, client_secret: &str) -> Option<OAuthClient> {
+        authenticate_client(
+            self.password_hasher,
+            self.password_validator,
+            self.oauth_client_repository,
+            client_id,
+            client_secret,
+        )
+    }
+}
+
+pub(super) fn authenticate_client(
+    password_hasher: &dyn PasswordHasher,
+    password_validator: &dyn PasswordValidator,
+    oauth_client_repository: &dyn OAuthClientRepository,
+    client_id: &str,
+    client_secret: &str,
+) -> Option<OAuthClient> {
+    let client = oauth_client_repository
+        .get(client_id)
+        .ok()
+        .filter(|client| client.secret_hash.is_some());
+    let secret_hash = client
+        .as_ref()
+        .and_then(|client| client.secret_hash.as_deref())
+        .unwrap_or(password_hasher.dummy_hash());
+    if !password_validator.verify(client_secret, secret_hash) {
+        return None;
+    }
+
+    client
+}
+
+pub struct ClientCredentialsResult {
+    pub access_token: String,
+    pub scope: Option<String>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ClientCredentialsFailReason {
+    InvalidClient,
+    InvalidScope,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_support::{
+        application::service::{FakePasswordHasher, FakePasswordValidator, FakeTokenIssuer},
+        domain::repository::FakeOAuthClientRepository,
+    };
+
+    fn fake_generate_id() -> String {
+        "generated_id".to_string()
+    }
+
+    fn setup_oauth_client_repository() -> FakeOAuthClientRepository {
+        let mut repo = FakeOAuthClientRepository::new();
+        repo.data.insert(
+            "billing-job".to_string(),
+            OAuthClient {
+                id: "billing-job".to_string(),
+                name: "Billing job".to_string(),
+                redirect_uris: vec![],
+                secret_hash: Some("hashed_secret".to_string()),
+                scopes: vec!["invoices:read".to_string(), "invoices:write".to_string()],
+            },
+        );
+        repo.data.insert(
+            "spa".to_string(),
+            OAuthClient {
+                id: "spa".to_string(),
+                name: "Example SPA".to_string(),
+                redirect_uris: vec!["https://app.example.com/callback".to_string()],
+                secret_hash: None,
+                scopes: vec![],
+            },
+        );
+        repo
+    }
+
+    #[test]
+    fn execute_given_valid_credentials_should_issue_token_for_client() {
+        let test_cases = vec![
+            (None, Some("invoices:read invoices:write")),
+            (Some(""), Some("invoices:read invoices:write")),
+            (Some("invoices:read"), Some("invoices:read")),
+            (
+                Some("invoices:write invoices:read invoices:write"),
+                Some("invoices:write invoices:read"),
+            ),
+        ];
+
+        for (requested_scope, expected_scope) in test_cases {
+            let stub_password_hasher = FakePasswordHasher::new("hashed");
+            let mock_password_validator = FakePasswordValidator::new(true);
+            let mock_access_token_issuer = FakeTokenIssuer::new("access_token");
+            let stub_oauth_client_repository = setup_oauth_client_repository();
+            let client_credentials = ClientCredentialsUseCase::new(
+                &stub_password_hasher,
+                &mock_password_validator,
+                &mock_access_token_issuer,
+                &stub_oauth_client_repository,
+                fake_generate_id,
+            );
+
+            let result = client_credentials.execute("billing-job", "secret", requested_scope);
+
+            assert!(result.is_ok_and(|result| {
+                result.access_token == "access_token" && result.scope.as_deref() == expected_scope
+            }));
+            assert_eq!(
+                *mock_password_validator.verified_hashes.borrow(),
+                vec!["hashed_secret"]
+            );
+            let issued_subjects = mock_access_token_issuer.issued_subjects.borrow();
+            assert_eq!(issued_subjects[0].sub, "billing-job");
+            assert_eq!(issued_subjects[0].jti.as_deref(), Some("generated_id"));
+            assert_eq!(issued_subjects[0].scope.as_deref(), expected_scope);
+        }
+    }
+
+    #[test]
+    fn execute_given_invalid_client_should_verify_dummy_hash_and_return_invalid_client() {
+        let test_cases = vec![
+            ("not_exist", true, "dummy_hash"),
+            ("spa", true, "dummy_hash"),
+            ("billing-job", false, "hashed_secret"),
+        ];
+
+        for (client_id, is_valid, expected_hash) in test_cases {
+            let stub_password_hasher = FakePasswordHasher::new("hashed");
+            let mock_password_validator = FakePasswordValidator::new(is_valid);
+            let mock_access_token_issuer = FakeTokenIssuer::new("access_token");
+            let stub_oauth_client_repository = setup_oauth_client_repository();
+            let client_credentials = ClientCredentialsUseCase::new(
+                &stub_password_hasher,
+                &mock_password_validator,
+                &mock_access_token_issuer,
+                &stub_oauth_client_repository,
+                fake_generate_id,
+            );
+
+            let result = client_credentials.execute(client_id, "secret", None);
+
+            assert!(result.is_err_and(|err| err == ClientCredentialsFailReason::InvalidClient));
+            assert_eq!(
+                *mock_password_validator.verified_hashes.borrow(),
+                vec![expected_hash]
+            );
+            assert!(mock_access_token_issuer.issued_subjects.borrow().is_empty());
+        }
+    }
+
+    #[test]
+    fn execute_given_scope_not_allowed_should_return_invalid_scope() {
+        let stub_password_hasher = FakePasswordHasher::new("hashed");
+        let stub_password_validator = FakePasswordValidator::new(true);
+        let mock_access_token_issuer = FakeTokenIssuer::new("access_token");
+        let stub_oauth_client_repository = setup_oauth_client_repository();
+        let client_credentials = ClientCredentialsUseCase::new(
+            &stub_password_hasher,
+            &stub_password_validator,
+            &mock_access_token_issuer,
+            &stub_oauth_client_repository,
+            fake_generate_id,
+        );
+
+        let result =
+            client_credentials.execute("billing-job", "secret", Some("invoices:read admin"));
+
+        assert!(result.is_err_and(|err| err == ClientCredentialsFailReason::InvalidScope));
+        assert!(mock_access_token_issuer.issued_subjects.borrow().is_empty());
+    }
+}
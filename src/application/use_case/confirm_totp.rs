@@ -0,0 +1,189 @@
+use crate::application::service::auth::TotpAuthenticator;
+use crate::domain::{repository::TotpCredentialRepository, value_object::UserId};
+
+const RECOVERY_CODE_COUNT: usize = 10;
+
+pub struct ConfirmTotpUseCase<'a> {
+    totp_authenticator: &'a dyn TotpAuthenticator,
+    totp_credential_repository: &'a mut dyn TotpCredentialRepository,
+    generate_recovery_code: fn() -> String,
+    hash_secret: fn(&str) -> String,
+    get_timestamp: fn() -> u64,
+}
+
+impl<'a> ConfirmTotpUseCase<'a> {
+    pub fn new(
+        totp_authenticator: &'a dyn TotpAuthenticator,
+        totp_credential_repository: &'a mut dyn TotpCredentialRepository,
+        generate_recovery_code: fn() -> String,
+        hash_secret: fn(&str) -> String,
+        get_timestamp: fn() -> u64,
+    ) -> Self {
+        ConfirmTotpUseCase {
+            totp_authenticator,
+            totp_credential_repository,
+            generate_recovery_code,
+            hash_secret,
+            get_timestamp,
+        }
+    }
+
+    pub fn execute(self, id: &UserId, code: &str) -> Result<Vec<String>, ConfirmTotpFailReason> {
+        let mut credential = self
+            .totp_credential_repository
+            .get(id)
+            .map_err(|_| ConfirmTotpFailReason::NotEnrolled)?;
+        if credential.confirmed {
+            return Err(ConfirmTotpFailReason::AlreadyEnabled);
+        }
+        let step = self
+            .totp_authenticator
+            .verify(&credential.secret, code, (self.get_timestamp)())
+            .ok_or(ConfirmTotpFailReason::InvalidCode)?;
+
+        let recovery_codes: Vec<String> = (0..RECOVERY_CODE_COUNT)
+            .map(|_| (self.generate_recovery_code)())
+            .collect();
+        credential.confirmed = true;
+        credential.last_used_step = step;
+        credential.recovery_codes = recovery_codes
+            .iter()
+            .map(|recovery_code| (self.hash_secret)(recovery_code))
+            .collect();
+        self.totp_credential_repository.save(credential);
+
+        Ok(recovery_codes)
+    }
+}
+
+#[derive(Debug)]
+pub enum ConfirmTotpFailReason {
+    NotEnrolled,
+    AlreadyEnabled,
+    InvalidCode,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::domain::entity::TotpCredential;
+    use crate::test_support::{
+        application::service::FakeTotpAuthenticator,
+        domain::repository::FakeTotpCredentialRepository,
+    };
+
+    const USER_ID: &str = "0196e2a4-5c1b-4f7e-9a3d-2b8c6d4e1f00";
+
+    fn fake_generate_recovery_code() -> String {
+        "abcde-fghij".to_string()
+    }
+
+    fn fake_hash_secret(secret: &str) -> String {
+        format!("hashed_{}", secret)
+    }
+
+    fn fake_get_timestamp() -> u64 {
+        1747636936
+    }
+
+    fn setup_repository(confirmed: bool) -> FakeTotpCredentialRepository {
+        let mut repo = FakeTotpCredentialRepository::new();
+        repo.data.insert(
+            USER_ID.to_string(),
+            TotpCredential {
+                user_id: UserId::new(USER_ID).unwrap(),
+                secret: "SECRET".to_string(),
+                confirmed,
+                recovery_codes: vec![],
+                last_used_step: 0,
+            },
+        );
+        repo
+    }
+
+    #[test]
+    fn execute_given_valid_code_should_confirm_and_return_recovery_codes() {
+        let stub_totp_authenticator = FakeTotpAuthenticator::new("SECRET", "123456", 58231231);
+        let mut mock_totp_credential_repository = setup_repository(false);
+        let confirm_totp = ConfirmTotpUseCase::new(
+            &stub_totp_authenticator,
+            &mut mock_totp_credential_repository,
+            fake_generate_recovery_code,
+            fake_hash_secret,
+            fake_get_timestamp,
+        );
+
+        let result = confirm_totp.execute(&UserId::new(USER_ID).unwrap(), "123456");
+
+        assert!(result.is_ok_and(|recovery_codes| {
+            recovery_codes.len() == RECOVERY_CODE_COUNT
+                && recovery_codes.iter().all(|code| code == "abcde-fghij")
+        }));
+        let credential = &mock_totp_credential_repository.data[USER_ID];
+        assert!(credential.confirmed);
+        assert_eq!(credential.last_used_step, 58231231);
+        assert_eq!(credential.recovery_codes.len(), RECOVERY_CODE_COUNT);
+        assert!(
+            credential
+                .recovery_codes
+                .iter()
+                .all(|code| code == "hashed_abcde-fghij")
+        );
+    }
+
+    #[test]
+    fn execute_given_invalid_code_should_keep_credential_unconfirmed() {
+        let stub_totp_authenticator = FakeTotpAuthenticator::new("SECRET", "123456", 58231231);
+        let mut mock_totp_credential_repository = setup_repository(false);
+        let confirm_totp = ConfirmTotpUseCase::new(
+            &stub_totp_authenticator,
+            &mut mock_totp_credential_repository,
+            fake_generate_recovery_code,
+            fake_hash_secret,
+            fake_get_timestamp,
+        );
+
+        let result = confirm_totp.execute(&UserId::new(USER_ID).unwrap(), "654321");
+
+        assert!(matches!(result, Err(ConfirmTotpFailReason::InvalidCode)));
+        assert!(!mock_totp_credential_repository.data[USER_ID].confirmed);
+    }
+
+    #[test]
+    fn execute_given_not_enrolled_user_should_return_not_enrolled() {
+        let stub_totp_authenticator = FakeTotpAuthenticator::new("SECRET", "123456", 58231231);
+        let mut stub_totp_credential_repository = FakeTotpCredentialRepository::new();
+        let confirm_totp = ConfirmTotpUseCase::new(
+            &stub_totp_authenticator,
+            &mut stub_totp_credential_repository,
+            fake_generate_recovery_code,
+            fake_hash_secret,
+            fake_get_timestamp,
+        );
+
+        let result = confirm_totp.execute(&UserId::new(USER_ID).unwrap(), "123456");
+
+        assert!(matches!(result, Err(ConfirmTotpFailReason::NotEnrolled)));
+    }
+
+    #[test]
+    fn execute_given_confirmed_credential_should_return_already_enabled() {
+        let stub_totp_authenticator = FakeTotpAuthenticator::new("SECRET", "123456", 58231231);
+        let mut mock_totp_credential_repository = setup_repository(true);
+        let confirm_totp = ConfirmTotpUseCase::new(
+            &stub_totp_authenticator,
+            &mut mock_totp_credential_repository,
+            fake_generate_recovery_code,
+            fake_hash_secret,
+            fake_get_timestamp,
+        );
+
+        let result = confirm_totp.execute(&UserId::new(USER_ID).unwrap(), "123456");
+
+        assert!(matches!(result, Err(ConfirmTotpFailReason::AlreadyEnabled)));
+        assert_eq!(
+            mock_totp_credential_repository.data[USER_ID].last_used_step,
+            0
+        );
+    }
+}
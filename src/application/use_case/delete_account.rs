@@ -0,0 +1,146 @@
+use crate::application::service::auth::PasswordValidator;
+use crate::domain::{
+    repository::{RefreshTokenFamilyRepository, UserRepository},
+    value_object::UserId,
+};
+
+pub struct DeleteAccountUseCase<'a> {
+    password_validator: &'a dyn PasswordValidator,
+    user_repository: &'a mut dyn UserRepository,
+    refresh_token_family_repository: &'a mut dyn RefreshTokenFamilyRepository,
+}
+
+impl<'a> DeleteAccountUseCase<'a> {
+    pub fn new(
+        password_validator: &'a dyn PasswordValidator,
+        user_repository: &'a mut dyn UserRepository,
+        refresh_token_family_repository: &'a mut dyn RefreshTokenFamilyRepository,
+    ) -> Self {
+        DeleteAccountUseCase {
+            password_validator,
+            user_repository,
+            refresh_token_family_repository,
+        }
+    }
+
+    pub fn execute(
+        self,
+        id: &UserId,
+        current_password: &str,
+    ) -> Result<(), DeleteAccountFailReason> {
+        let user = self
+            .user_repository
+            .get_by_id(id)
+            .map_err(|_| DeleteAccountFailReason::UserNotExist)?;
+        if !self
+            .password_validator
+            .verify(current_password, &user.password)
+        {
+            return Err(DeleteAccountFailReason::IncorrectPassword);
+        }
+
+        self.user_repository
+            .delete(id)
+            .map_err(|_| DeleteAccountFailReason::UserNotExist)?;
+        self.refresh_token_family_repository
+            .revoke_by_subject(id.as_str());
+
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub enum DeleteAccountFailReason {
+    UserNotExist,
+    IncorrectPassword,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::domain::{
+        entity::{RefreshTokenFamily, User},
+        value_object::EmailAddress,
+    };
+    use crate::test_support::{
+        application::service::FakePasswordValidator,
+        domain::repository::{FakeRefreshTokenFamilyRepository, FakeUserRepository},
+    };
+
+    const USER_ID: &str = "0196e2a4-5c1b-4f7e-9a3d-2b8c6d4e1f00";
+
+    fn setup_user_repository() -> FakeUserRepository {
+        let mut repo = FakeUserRepository::new();
+        repo.data.insert(
+            USER_ID.to_string(),
+            User {
+                id: UserId::new(USER_ID).unwrap(),
+                email: EmailAddress::new("example@example.com").unwrap(),
+                email_verified: true,
+                pending_email: None,
+                username: "foo".to_string(),
+                password: "hashed".to_string(),
+                create_at: 1747636936,
+                update_at: 1747636936,
+            },
+        );
+        repo
+    }
+
+    fn setup_refresh_token_family_repository() -> FakeRefreshTokenFamilyRepository {
+        let mut repo = FakeRefreshTokenFamilyRepository::new();
+        repo.data.insert(
+            "family_id".to_string(),
+            RefreshTokenFamily {
+                id: "family_id".to_string(),
+                subject: USER_ID.to_string(),
+                current_token_id: "token_id".to_string(),
+                revoked: false,
+            },
+        );
+        repo
+    }
+
+    #[test]
+    fn execute_given_correct_password_should_delete_user_and_revoke_sessions() {
+        let mock_password_validator = FakePasswordValidator::new(true);
+        let mut mock_user_repository = setup_user_repository();
+        let mut mock_refresh_token_family_repository = setup_refresh_token_family_repository();
+        let delete_account = DeleteAccountUseCase::new(
+            &mock_password_validator,
+            &mut mock_user_repository,
+            &mut mock_refresh_token_family_repository,
+        );
+
+        let result = delete_account.execute(&UserId::new(USER_ID).unwrap(), "password");
+
+        assert!(result.is_ok());
+        assert_eq!(
+            *mock_password_validator.verified_hashes.borrow(),
+            vec!["hashed"]
+        );
+        assert!(mock_user_repository.data.is_empty());
+        assert!(mock_refresh_token_family_repository.data["family_id"].revoked);
+    }
+
+    #[test]
+    fn execute_given_incorrect_password_should_keep_user() {
+        let stub_password_validator = FakePasswordValidator::new(false);
+        let mut mock_user_repository = setup_user_repository();
+        let mut mock_refresh_token_family_repository = setup_refresh_token_family_repository();
+        let delete_account = DeleteAccountUseCase::new(
+            &stub_password_validator,
+            &mut mock_user_repository,
+            &mut mock_refresh_token_family_repository,
+        );
+
+        let result = delete_account.execute(&UserId::new(USER_ID).unwrap(), "wrong password");
+
+        assert!(matches!(
+            result,
+            Err(DeleteAccountFailReason::IncorrectPassword)
+        ));
+        assert!(mock_user_repository.data.contains_key(USER_ID));
+        assert!(!mock_refresh_token_family_repository.data["family_id"].revoked);
+    }
+}
@@ -0,0 +1,174 @@
+use crate::application::service::auth::TotpAuthenticator;
+use crate::domain::{
+    entity::TotpCredential,
+    repository::{TotpCredentialRepository, UserRepository},
+    value_object::UserId,
+};
+
+pub struct EnrollTotpUseCase<'a> {
+    totp_authenticator: &'a dyn TotpAuthenticator,
+    user_repository: &'a dyn UserRepository,
+    totp_credential_repository: &'a mut dyn TotpCredentialRepository,
+}
+
+impl<'a> EnrollTotpUseCase<'a> {
+    pub fn new(
+        totp_authenticator: &'a dyn TotpAuthenticator,
+        user_repository: &'a dyn UserRepository,
+        totp_credential_repository: &'a mut dyn TotpCredentialRepository,
+    ) -> Self {
+        EnrollTotpUseCase {
+            totp_authenticator,
+            user_repository,
+            totp_credential_repository,
+        }
+    }
+
+    pub fn execute(self, id: &UserId) -> Result<TotpEnrollment, EnrollTotpFailReason> {
+        let user = self
+            .user_repository
+            .get_by_id(id)
+            .map_err(|_| EnrollTotpFailReason::UserNotExist)?;
+        if self
+            .totp_credential_repository
+            .get(id)
+            .is_ok_and(|credential| credential.confirmed)
+        {
+            return Err(EnrollTotpFailReason::AlreadyEnabled);
+        }
+
+        let secret = self.totp_authenticator.generate_secret();
+        let provisioning_uri = self
+            .totp_authenticator
+            .provisioning_uri(&secret, user.email.as_str());
+        self.totp_credential_repository.save(TotpCredential {
+            user_id: user.id,
+            secret: secret.clone(),
+            confirmed: false,
+            recovery_codes: vec![],
+            last_used_step: 0,
+        });
+
+        Ok(TotpEnrollment {
+            secret,
+            provisioning_uri,
+        })
+    }
+}
+
+pub struct TotpEnrollment {
+    pub secret: String,
+    pub provisioning_uri: String,
+}
+
+#[derive(Debug)]
+pub enum EnrollTotpFailReason {
+    UserNotExist,
+    AlreadyEnabled,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::domain::{entity::User, value_object::EmailAddress};
+    use crate::test_support::{
+        application::service::FakeTotpAuthenticator,
+        domain::repository::{FakeTotpCredentialRepository, FakeUserRepository},
+    };
+
+    const USER_ID: &str = "0196e2a4-5c1b-4f7e-9a3d-2b8c6d4e1f00";
+
+    fn setup_user_repository() -> FakeUserRepository {
+        let mut repo = FakeUserRepository::new();
+        repo.data.insert(
+            USER_ID.to_string(),
+            User {
+                id: UserId::new(USER_ID).unwrap(),
+                email: EmailAddress::new("example@example.com").unwrap(),
+                email_verified: true,
+                pending_email: None,
+                username: "foo".to_string(),
+                password: "hashed".to_string(),
+                create_at: 1747636936,
+                update_at: 1747636936,
+            },
+        );
+        repo
+    }
+
+    fn create_credential(confirmed: bool) -> TotpCredential {
+        TotpCredential {
+            user_id: UserId::new(USER_ID).unwrap(),
+            secret: "OLDSECRET".to_string(),
+            confirmed,
+            recovery_codes: vec![],
+            last_used_step: 0,
+        }
+    }
+
+    #[test]
+    fn execute_given_user_should_persist_unconfirmed_credential() {
+        let stub_totp_authenticator = FakeTotpAuthenticator::new("NEWSECRET", "123456", 1);
+        let stub_user_repository = setup_user_repository();
+        let mut mock_totp_credential_repository = FakeTotpCredentialRepository::new();
+        mock_totp_credential_repository
+            .data
+            .insert(USER_ID.to_string(), create_credential(false));
+        let enroll_totp = EnrollTotpUseCase::new(
+            &stub_totp_authenticator,
+            &stub_user_repository,
+            &mut mock_totp_credential_repository,
+        );
+
+        let result = enroll_totp.execute(&UserId::new(USER_ID).unwrap());
+
+        assert!(result.is_ok_and(|enrollment| {
+            enrollment.secret == "NEWSECRET"
+                && enrollment.provisioning_uri
+                    == "otpauth://totp/example@example.com?secret=NEWSECRET"
+        }));
+        let credential = &mock_totp_credential_repository.data[USER_ID];
+        assert_eq!(credential.secret, "NEWSECRET");
+        assert!(!credential.confirmed);
+    }
+
+    #[test]
+    fn execute_given_confirmed_credential_should_return_already_enabled() {
+        let stub_totp_authenticator = FakeTotpAuthenticator::new("NEWSECRET", "123456", 1);
+        let stub_user_repository = setup_user_repository();
+        let mut mock_totp_credential_repository = FakeTotpCredentialRepository::new();
+        mock_totp_credential_repository
+            .data
+            .insert(USER_ID.to_string(), create_credential(true));
+        let enroll_totp = EnrollTotpUseCase::new(
+            &stub_totp_authenticator,
+            &stub_user_repository,
+            &mut mock_totp_credential_repository,
+        );
+
+        let result = enroll_totp.execute(&UserId::new(USER_ID).unwrap());
+
+        assert!(matches!(result, Err(EnrollTotpFailReason::AlreadyEnabled)));
+        assert_eq!(
+            mock_totp_credential_repository.data[USER_ID].secret,
+            "OLDSECRET"
+        );
+    }
+
+    #[test]
+    fn execute_given_not_exist_user_should_return_user_not_exist() {
+        let stub_totp_authenticator = FakeTotpAuthenticator::new("NEWSECRET", "123456", 1);
+        let stub_user_repository = FakeUserRepository::new();
+        let mut mock_totp_credential_repository = FakeTotpCredentialRepository::new();
+        let enroll_totp = EnrollTotpUseCase::new(
+            &stub_totp_authenticator,
+            &stub_user_repository,
+            &mut mock_totp_credential_repository,
+        );
+
+        let result = enroll_totp.execute(&UserId::new(USER_ID).unwrap());
+
+        assert!(matches!(result, Err(EnrollTotpFailReason::UserNotExist)));
+        assert!(mock_totp_credential_repository.data.is_empty());
+    }
+}
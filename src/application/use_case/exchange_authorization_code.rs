@@ -0,0 +1,438 @@
+use super::begin_authorization::is_pkce_value;
+use super::client_credentials::ClientAuthenticator;
+use super::signin::{SessionIssuer, SignInResult};
+use crate::domain::repository::{AuthorizationCodeRepository, UserRepository};
+
+pub struct ExchangeAuthorizationCodeUseCase<'a> {
+    session_issuer: SessionIssuer<'a>,
+    client_authenticator: ClientAuthenticator<'a>,
+    user_repository: &'a dyn UserRepository,
+    authorization_code_repository: &'a mut dyn AuthorizationCodeRepository,
+    hash_secret: fn(&str) -> String,
+    get_timestamp: fn() -> u64,
+}
+
+impl<'a> ExchangeAuthorizationCodeUseCase<'a> {
+    pub fn new(
+        session_issuer: SessionIssuer<'a>,
+        client_authenticator: ClientAuthenticator<'a>,
+        user_repository: &'a dyn UserRepository,
+        authorization_code_repository: &'a mut dyn AuthorizationCodeRepository,
+        hash_secret: fn(&str) -> String,
+        get_timestamp: fn() -> u64,
+    ) -> Self {
+        ExchangeAuthorizationCodeUseCase {
+            session_issuer,
+            client_authenticator,
+            user_repository,
+            authorization_code_repository,
+            hash_secret,
+            get_timestamp,
+        }
+    }
+
+    pub fn execute(
+        self,
+        code: &str,
+        client_id: &str,
+        client_secret: Option<&str>,
+        redirect_uri: &str,
+        code_verifier: &str,
+    ) -> Result<SignInResult, ExchangeAuthorizationCodeFailReason> {
+        if (self.client_authenticator.is_confidential(client_id) || client_secret.is_some())
+            && !client_secret.is_some_and(|client_secret| {
+                self.client_authenticator
+                    .authenticate(client_id, client_secret)
+                    .is_some()
+            })
+        {
+            return Err(ExchangeAuthorizationCodeFailReason::InvalidClient);
+        }
+        let now = (self.get_timestamp)();
+        let authorization_code = self
+            .authorization_code_repository
+            .take(&(self.hash_secret)(code))
+            .ok()
+            .filter(|authorization_code| authorization_code.expire_at > now)
+            .ok_or(ExchangeAuthorizationCodeFailReason::InvalidGrant)?;
+        if authorization_code.client_id != client_id
+            || authorization_code.redirect_uri != redirect_uri
+            || !is_pkce_value(code_verifier)
+            || (self.hash_secret)(code_verifier) != authorization_code.code_challenge
+        {
+            return Err(ExchangeAuthorizationCodeFailReason::InvalidGrant);
+        }
+        let user = self
+            .user_repository
+            .get_by_id(&authorization_code.user_id)
+            .map_err(|_| ExchangeAuthorizationCodeFailReason::InvalidGrant)?;
+
+        Ok(self.session_issuer.issue(user))
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ExchangeAuthorizationCodeFailReason {
+    InvalidClient,
+    InvalidGrant,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::domain::{
+        entity::{AuthorizationCode, OAuthClient, User},
+        value_object::{EmailAddress, UserId},
+    };
+    use crate::test_support::{
+        application::service::{FakePasswordHasher, FakePasswordValidator, FakeTokenIssuer},
+        domain::repository::{
+            FakeAuthorizationCodeRepository, FakeOAuthClientRepository,
+            FakeRefreshTokenFamilyRepository, FakeUserRepository,
+        },
+    };
+
+    const USER_ID: &str = "0196e2a4-5c1b-4f7e-9a3d-2b8c6d4e1f00";
+    const CODE_VERIFIER: &str = "dBjftJeZ4CVP-mB92K27uhbUJU1p1r_wW1gFWFOEjXk";
+
+    fn fake_hash_secret(secret: &str) -> String {
+        format!("hashed_{}", secret)
+    }
+
+    fn fake_generate_id() -> String {
+        "generated_id".to_string()
+    }
+
+    fn fake_get_timestamp() -> u64 {
+        1747636936
+    }
+
+    fn setup_oauth_client_repository() -> FakeOAuthClientRepository {
+        let mut repo = FakeOAuthClientRepository::new();
+        repo.data.insert(
+            "spa".to_string(),
+            OAuthClient {
+                id: "spa".to_string(),
+                name: "Single page app".to_string(),
+                redirect_uris: vec!["https://app.example.com/callback".to_string()],
+                secret_hash: None,
+                scopes: vec![],
+            },
+        );
+        repo.data.insert(
+            "web".to_string(),
+            OAuthClient {
+                id: "web".to_string(),
+                name: "Web app".to_string(),
+                redirect_uris: vec!["https://app.example.com/callback".to_string()],
+                secret_hash: Some("hashed_secret".to_string()),
+                scopes: vec![],
+            },
+        );
+        repo
+    }
+
+    fn setup_user_repository() -> FakeUserRepository {
+        let mut repo = FakeUserRepository::new();
+        repo.data.insert(
+            USER_ID.to_string(),
+            User {
+                id: UserId::new(USER_ID).unwrap(),
+                email: EmailAddress::new("example@example.com").unwrap(),
+                email_verified: true,
+                pending_email: None,
+                username: "foo".to_string(),
+                password: "hashed".to_string(),
+                create_at: 1747636936,
+                update_at: 1747636936,
+            },
+        );
+        repo
+    }
+
+    fn setup_authorization_code_repository(expire_at: u64) -> FakeAuthorizationCodeRepository {
+        let mut repo = FakeAuthorizationCodeRepository::new();
+        repo.data.insert(
+            "hashed_authorization_code".to_string(),
+            AuthorizationCode {
+                code_hash: "hashed_authorization_code".to_string(),
+                client_id: "spa".to_string(),
+                user_id: UserId::new(USER_ID).unwrap(),
+                redirect_uri: "https://app.example.com/callback".to_string(),
+                code_challenge: fake_hash_secret(CODE_VERIFIER),
+                expire_at,
+            },
+        );
+        repo
+    }
+
+    #[test]
+    fn execute_given_valid_code_and_verifier_should_consume_code_and_issue_tokens() {
+        let stub_access_token_issuer = FakeTokenIssuer::new("access_token");
+        let stub_refresh_token_issuer = FakeTokenIssuer::new("refresh_token");
+        let stub_password_hasher = FakePasswordHasher::new("hashed");
+        let stub_password_validator = FakePasswordValidator::new(true);
+        let stub_oauth_client_repository = setup_oauth_client_repository();
+        let stub_user_repository = setup_user_repository();
+        let mut mock_authorization_code_repository =
+            setup_authorization_code_repository(1747636996);
+        let mut mock_refresh_token_family_repository = FakeRefreshTokenFamilyRepository::new();
+        let exchange_authorization_code = ExchangeAuthorizationCodeUseCase::new(
+            SessionIssuer::new(
+                &stub_access_token_issuer,
+                &stub_refresh_token_issuer,
+                &mut mock_refresh_token_family_repository,
+                fake_generate_id,
+            ),
+            ClientAuthenticator::new(
+                &stub_password_hasher,
+                &stub_password_validator,
+                &stub_oauth_client_repository,
+            ),
+            &stub_user_repository,
+            &mut mock_authorization_code_repository,
+            fake_hash_secret,
+            fake_get_timestamp,
+        );
+
+        let result = exchange_authorization_code.execute(
+            "authorization_code",
+            "spa",
+            None,
+            "https://app.example.com/callback",
+            CODE_VERIFIER,
+        );
+
+        assert!(result.is_ok_and(|result| {
+            result.access_token == "access_token"
+                && result.refresh_token == "refresh_token"
+                && result.id == USER_ID
+        }));
+        assert!(mock_authorization_code_repository.data.is_empty());
+        assert!(
+            mock_refresh_token_family_repository
+                .data
+                .contains_key("generated_id")
+        );
+    }
+
+    #[test]
+    fn execute_given_mismatched_request_should_burn_code_and_return_invalid_token() {
+        let test_cases = vec![
+            (
+                "other",
+                "https://app.example.com/callback",
+                CODE_VERIFIER,
+                1747636996,
+            ),
+            (
+                "spa",
+                "https://app.example.com/other",
+                CODE_VERIFIER,
+                1747636996,
+            ),
+            (
+                "spa",
+                "https://app.example.com/callback",
+                "Wrong-verifier-with-enough-characters-000000",
+                1747636996,
+            ),
+            ("spa", "https://app.example.com/callback", "", 1747636996),
+            (
+                "spa",
+                "https://app.example.com/callback",
+                CODE_VERIFIER,
+                1747636936,
+            ),
+        ];
+
+        for (client_id, redirect_uri, code_verifier, expire_at) in test_cases {
+            let stub_access_token_issuer = FakeTokenIssuer::new("access_token");
+            let stub_refresh_token_issuer = FakeTokenIssuer::new("refresh_token");
+            let stub_password_hasher = FakePasswordHasher::new("hashed");
+            let stub_password_validator = FakePasswordValidator::new(true);
+            let stub_oauth_client_repository = setup_oauth_client_repository();
+            let stub_user_repository = setup_user_repository();
+            let mut mock_authorization_code_repository =
+                setup_authorization_code_repository(expire_at);
+            let mut mock_refresh_token_family_repository = FakeRefreshTokenFamilyRepository::new();
+            let exchange_authorization_code = ExchangeAuthorizationCodeUseCase::new(
+                SessionIssuer::new(
+                    &stub_access_token_issuer,
+                    &stub_refresh_token_issuer,
+                    &mut mock_refresh_token_family_repository,
+                    fake_generate_id,
+                ),
+                ClientAuthenticator::new(
+                    &stub_password_hasher,
+                    &stub_password_validator,
+                    &stub_oauth_client_repository,
+                ),
+                &stub_user_repository,
+                &mut mock_authorization_code_repository,
+                fake_hash_secret,
+                fake_get_timestamp,
+            );
+
+            let result = exchange_authorization_code.execute(
+                "authorization_code",
+                client_id,
+                None,
+                redirect_uri,
+                code_verifier,
+            );
+
+            assert!(result.is_err());
+            assert!(mock_authorization_code_repository.data.is_empty());
+            assert!(mock_refresh_token_family_repository.data.is_empty());
+        }
+    }
+
+    #[test]
+    fn execute_given_unknown_code_should_return_invalid_token() {
+        let stub_access_token_issuer = FakeTokenIssuer::new("access_token");
+        let stub_refresh_token_issuer = FakeTokenIssuer::new("refresh_token");
+        let stub_password_hasher = FakePasswordHasher::new("hashed");
+        let stub_password_validator = FakePasswordValidator::new(true);
+        let stub_oauth_client_repository = setup_oauth_client_repository();
+        let stub_user_repository = setup_user_repository();
+        let mut stub_authorization_code_repository =
+            setup_authorization_code_repository(1747636996);
+        let mut mock_refresh_token_family_repository = FakeRefreshTokenFamilyRepository::new();
+        let exchange_authorization_code = ExchangeAuthorizationCodeUseCase::new(
+            SessionIssuer::new(
+                &stub_access_token_issuer,
+                &stub_refresh_token_issuer,
+                &mut mock_refresh_token_family_repository,
+                fake_generate_id,
+            ),
+            ClientAuthenticator::new(
+                &stub_password_hasher,
+                &stub_password_validator,
+                &stub_oauth_client_repository,
+            ),
+            &stub_user_repository,
+            &mut stub_authorization_code_repository,
+            fake_hash_secret,
+            fake_get_timestamp,
+        );
+
+        let result = exchange_authorization_code.execute(
+            "unknown_code",
+            "spa",
+            None,
+            "https://app.example.com/callback",
+            CODE_VERIFIER,
+        );
+
+        assert!(result.is_err());
+        assert!(mock_refresh_token_family_repository.data.is_empty());
+    }
+
+    #[test]
+    fn execute_given_confidential_client_with_secret_should_issue_tokens() {
+        let stub_access_token_issuer = FakeTokenIssuer::new("access_token");
+        let stub_refresh_token_issuer = FakeTokenIssuer::new("refresh_token");
+        let stub_password_hasher = FakePasswordHasher::new("hashed");
+        let stub_password_validator = FakePasswordValidator::new(true);
+        let stub_oauth_client_repository = setup_oauth_client_repository();
+        let stub_user_repository = setup_user_repository();
+        let mut mock_authorization_code_repository =
+            setup_authorization_code_repository(1747636996);
+        mock_authorization_code_repository
+            .data
+            .get_mut("hashed_authorization_code")
+            .unwrap()
+            .client_id = "web".to_string();
+        let mut mock_refresh_token_family_repository = FakeRefreshTokenFamilyRepository::new();
+        let exchange_authorization_code = ExchangeAuthorizationCodeUseCase::new(
+            SessionIssuer::new(
+                &stub_access_token_issuer,
+                &stub_refresh_token_issuer,
+                &mut mock_refresh_token_family_repository,
+                fake_generate_id,
+            ),
+            ClientAuthenticator::new(
+                &stub_password_hasher,
+                &stub_password_validator,
+                &stub_oauth_client_repository,
+            ),
+            &stub_user_repository,
+            &mut mock_authorization_code_repository,
+            fake_hash_secret,
+            fake_get_timestamp,
+        );
+
+        let result = exchange_authorization_code.execute(
+            "authorization_code",
+            "web",
+            Some("secret"),
+            "https://app.example.com/callback",
+            CODE_VERIFIER,
+        );
+
+        assert!(result.is_ok_and(|result| result.id == USER_ID));
+        assert!(mock_authorization_code_repository.data.is_empty());
+    }
+
+    #[test]
+    fn execute_given_unauthenticated_client_should_keep_code_and_return_invalid_client() {
+        let test_cases = vec![
+            ("web", None, true),
+            ("web", Some("wrong"), false),
+            ("spa", Some("unexpected"), false),
+        ];
+
+        for (client_id, client_secret, is_valid) in test_cases {
+            let stub_access_token_issuer = FakeTokenIssuer::new("access_token");
+            let stub_refresh_token_issuer = FakeTokenIssuer::new("refresh_token");
+            let stub_password_hasher = FakePasswordHasher::new("hashed");
+            let stub_password_validator = FakePasswordValidator::new(is_valid);
+            let stub_oauth_client_repository = setup_oauth_client_repository();
+            let stub_user_repository = setup_user_repository();
+            let mut mock_authorization_code_repository =
+                setup_authorization_code_repository(1747636996);
+            mock_authorization_code_repository
+                .data
+                .get_mut("hashed_authorization_code")
+                .unwrap()
+                .client_id = client_id.to_string();
+            let mut mock_refresh_token_family_repository = FakeRefreshTokenFamilyRepository::new();
+            let exchange_authorization_code = ExchangeAuthorizationCodeUseCase::new(
+                SessionIssuer::new(
+                    &stub_access_token_issuer,
+                    &stub_refresh_token_issuer,
+                    &mut mock_refresh_token_family_repository,
+                    fake_generate_id,
+                ),
+                ClientAuthenticator::new(
+                    &stub_password_hasher,
+                    &stub_password_validator,
+                    &stub_oauth_client_repository,
+                ),
+                &stub_user_repository,
+                &mut mock_authorization_code_repository,
+                fake_hash_secret,
+                fake_get_timestamp,
+            );
+
+            let result = exchange_authorization_code.execute(
+                "authorization_code",
+                client_id,
+                client_secret,
+                "https://app.example.com/callback",
+                CODE_VERIFIER,
+            );
+
+            assert!(
+                result.is_err_and(|err| err == ExchangeAuthorizationCodeFailReason::InvalidClient)
+            );
+            assert!(
+                mock_authorization_code_repository
+                    .data
+                    .contains_key("hashed_authorization_code")
+            );
+            assert!(mock_refresh_token_family_repository.data.is_empty());
+        }
+    }
+}
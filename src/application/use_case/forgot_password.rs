@@ -0,0 +1,176 @@
+use crate::application::service::mail::{Mail, MailSender};
+use crate::domain::{
+    entity::PasswordResetToken,
+    error,
+    repository::{PasswordResetTokenRepository, UserRepository},
+    value_object::EmailAddress,
+};
+
+pub struct ForgotPasswordUseCase<'a> {
+    mail_sender: &'a dyn MailSender,
+    user_repository: &'a dyn UserRepository,
+    password_reset_token_repository: &'a mut dyn PasswordResetTokenRepository,
+    password_reset_policy: &'a PasswordResetPolicy,
+    generate_secret: fn() -> String,
+    hash_secret: fn(&str) -> String,
+    get_timestamp: fn() -> u64,
+}
+
+impl<'a> ForgotPasswordUseCase<'a> {
+    pub fn new(
+        mail_sender: &'a dyn MailSender,
+        user_repository: &'a dyn UserRepository,
+        password_reset_token_repository: &'a mut dyn PasswordResetTokenRepository,
+        password_reset_policy: &'a PasswordResetPolicy,
+        generate_secret: fn() -> String,
+        hash_secret: fn(&str) -> String,
+        get_timestamp: fn() -> u64,
+    ) -> Self {
+        ForgotPasswordUseCase {
+            mail_sender,
+            user_repository,
+            password_reset_token_repository,
+            password_reset_policy,
+            generate_secret,
+            hash_secret,
+            get_timestamp,
+        }
+    }
+
+    pub fn execute(self, email: EmailAddress) -> Result<(), error::MailDeliveryFailed> {
+        let user = match self.user_repository.get(email) {
+            Ok(user) => user,
+            Err(_) => return Ok(()),
+        };
+        let token = (self.generate_secret)();
+        self.password_reset_token_repository
+            .create(PasswordResetToken {
+                token_hash: (self.hash_secret)(&token),
+                user_id: user.id.clone(),
+                expire_at: (self.get_timestamp)() + self.password_reset_policy.token_valid_seconds,
+            })
+            .expect("generated reset token should be unique");
+
+        self.mail_sender.send(&Mail {
+            to: user.email.as_str().to_string(),
+            subject: "Reset your password".to_string(),
+            body: format!(
+                "Hi {},\n\nOpen the link below to choose a new password:\n\n{}?token={}\n\nIf you did not ask to reset your password, you can ignore this email.\n",
+                user.username, self.password_reset_policy.reset_url, token
+            ),
+        })
+    }
+}
+
+pub struct PasswordResetPolicy {
+    pub reset_url: String,
+    pub token_valid_seconds: u64,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::domain::{entity::User, value_object::UserId};
+    use crate::test_support::{
+        application::service::FakeMailSender,
+        domain::repository::{FakePasswordResetTokenRepository, FakeUserRepository},
+    };
+
+    const USER_ID: &str = "0196e2a4-5c1b-4f7e-9a3d-2b8c6d4e1f00";
+
+    fn fake_generate_secret() -> String {
+        "reset_token".to_string()
+    }
+
+    fn fake_hash_secret(secret: &str) -> String {
+        format!("hashed_{}", secret)
+    }
+
+    fn fake_get_timestamp() -> u64 {
+        1747636936
+    }
+
+    fn password_reset_policy() -> PasswordResetPolicy {
+        PasswordResetPolicy {
+            reset_url: "https://example.com/password/reset".to_string(),
+            token_valid_seconds: 900,
+        }
+    }
+
+    fn setup_repository() -> FakeUserRepository {
+        let mut repo = FakeUserRepository::new();
+        repo.data.insert(
+            USER_ID.to_string(),
+            User {
+                id: UserId::new(USER_ID).unwrap(),
+                email: EmailAddress::new("example@example.com").unwrap(),
+                email_verified: true,
+                pending_email: None,
+                username: "foo".to_string(),
+                password: "hashed".to_string(),
+                create_at: 1747636936,
+                update_at: 1747636936,
+            },
+        );
+        repo
+    }
+
+    #[test]
+    fn execute_given_registered_email_should_store_hashed_token_and_mail_link() {
+        let stub_user_repository = setup_repository();
+        let mut mock_password_reset_token_repository = FakePasswordResetTokenRepository::new();
+        let mock_mail_sender = FakeMailSender::new(true);
+        let stub_password_reset_policy = password_reset_policy();
+        let forgot_password = ForgotPasswordUseCase::new(
+            &mock_mail_sender,
+            &stub_user_repository,
+            &mut mock_password_reset_token_repository,
+            &stub_password_reset_policy,
+            fake_generate_secret,
+            fake_hash_secret,
+            fake_get_timestamp,
+        );
+
+        let result = forgot_password.execute(EmailAddress::new("example@example.com").unwrap());
+
+        assert!(result.is_ok());
+        let token = &mock_password_reset_token_repository.data["hashed_reset_token"];
+        assert_eq!(token.user_id.as_str(), USER_ID);
+        assert_eq!(token.expire_at, 1747637836);
+        assert!(
+            !mock_password_reset_token_repository
+                .data
+                .contains_key("reset_token")
+        );
+        let sent = mock_mail_sender.sent.borrow();
+        assert_eq!(sent[0].to, "example@example.com");
+        assert!(
+            sent[0]
+                .body
+                .contains("https://example.com/password/reset?token=reset_token")
+        );
+    }
+
+    #[test]
+    fn execute_given_unknown_email_should_do_nothing() {
+        let stub_user_repository = setup_repository();
+        let mut mock_password_reset_token_repository = FakePasswordResetTokenRepository::new();
+        let mock_mail_sender = FakeMailSender::new(true);
+        let stub_password_reset_policy = password_reset_policy();
+        let forgot_password = ForgotPasswordUseCase::new(
+            &mock_mail_sender,
+            &stub_user_repository,
+            &mut mock_password_reset_token_repository,
+            &stub_password_reset_policy,
+            fake_generate_secret,
+            fake_hash_secret,
+            fake_get_timestamp,
+        );
+
+        let result = forgot_password.execute(EmailAddress::new("unknown@example.com").unwrap());
+
+        assert!(result.is_ok());
+        assert!(mock_password_reset_token_repository.data.is_empty());
+        assert!(mock_mail_sender.sent.borrow().is_empty());
+    }
+}
@@ -0,0 +1,230 @@
+use super::client_credentials::authenticate_client;
+use crate::application::service::auth::{
+    PasswordHasher, PasswordValidator, TokenClaims, TokenVerifier,
+};
+use crate::domain::repository::OAuthClientRepository;
+
+pub struct IntrospectUseCase<'a> {
+    password_hasher: &'a dyn PasswordHasher,
+    password_validator: &'a dyn PasswordValidator,
+    oauth_client_repository: &'a dyn OAuthClientRepository,
+    access_token_verifier: &'a dyn TokenVerifier,
+    refresh_token_verifier: &'a dyn TokenVerifier,
+}
+
+impl<'a> IntrospectUseCase<'a> {
+    pub fn new(
+        password_hasher: &'a dyn PasswordHasher,
+        password_validator: &'a dyn PasswordValidator,
+        oauth_client_repository: &'a dyn OAuthClientRepository,
+        access_token_verifier: &'a dyn TokenVerifier,
+        refresh_token_verifier: &'a dyn TokenVerifier,
+    ) -> Self {
+        IntrospectUseCase {
+            password_hasher,
+            password_validator,
+            oauth_client_repository,
+            access_token_verifier,
+            refresh_token_verifier,
+        }
+    }
+
+    pub fn execute(
+        self,
+        client_id: &str,
+        client_secret: &str,
+        token: &str,
+        token_type_hint: Option<TokenTypeHint>,
+    ) -> Result<IntrospectResult, IntrospectFailReason> {
+        if authenticate_client(
+            self.password_hasher,
+            self.password_validator,
+            self.oauth_client_repository,
+            client_id,
+            client_secret,
+        )
+        .is_none()
+        {
+            return Err(IntrospectFailReason::InvalidClient);
+        }
+        let verifiers = match token_type_hint {
+            Some(TokenTypeHint::RefreshToken) => {
+                [self.refresh_token_verifier, self.access_token_verifier]
+            }
+            _ => [self.access_token_verifier, self.refresh_token_verifier],
+        };
+
+        Ok(verifiers
+            .iter()
+            .find_map(|verifier| verifier.verify(token).ok())
+            .map_or(IntrospectResult::Inactive, IntrospectResult::Active))
+    }
+}
+
+pub enum TokenTypeHint {
+    AccessToken,
+    RefreshToken,
+}
+
+pub enum IntrospectResult {
+    Active(TokenClaims),
+    Inactive,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum IntrospectFailReason {
+    InvalidClient,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::domain::entity::OAuthClient;
+    use crate::test_support::{
+        application::service::{FakePasswordHasher, FakePasswordValidator, FakeTokenVerifier},
+        domain::repository::FakeOAuthClientRepository,
+    };
+
+    fn setup_oauth_client_repository() -> FakeOAuthClientRepository {
+        let mut repo = FakeOAuthClientRepository::new();
+        repo.data.insert(
+            "resource-server".to_string(),
+            OAuthClient {
+                id: "resource-server".to_string(),
+                name: "Resource server".to_string(),
+                redirect_uris: vec![],
+                secret_hash: Some("hashed_secret".to_string()),
+                scopes: vec![],
+            },
+        );
+        repo
+    }
+
+    fn claims(sub: &str) -> TokenClaims {
+        TokenClaims {
+            sub: sub.to_string(),
+            iss: "example".to_string(),
+            aud: "example".to_string(),
+            iat: 1747636936,
+            exp: 1747640536,
+            jti: None,
+            fid: None,
+            email: None,
+            scope: None,
+        }
+    }
+
+    #[test]
+    fn execute_given_valid_access_token_should_return_active() {
+        let stub_access_token_verifier = FakeTokenVerifier::new(Some(claims("foo")));
+        let stub_refresh_token_verifier = FakeTokenVerifier::new(None);
+        let stub_password_hasher = FakePasswordHasher::new("hashed");
+        let stub_password_validator = FakePasswordValidator::new(true);
+        let stub_oauth_client_repository = setup_oauth_client_repository();
+        let introspect = IntrospectUseCase::new(
+            &stub_password_hasher,
+            &stub_password_validator,
+            &stub_oauth_client_repository,
+            &stub_access_token_verifier,
+            &stub_refresh_token_verifier,
+        );
+
+        let result = introspect.execute("resource-server", "secret", "access_token", None);
+
+        assert!(matches!(result, Ok(IntrospectResult::Active(c)) if c.sub == "foo"));
+    }
+
+    #[test]
+    fn execute_given_refresh_token_with_wrong_hint_should_still_return_active() {
+        let stub_access_token_verifier = FakeTokenVerifier::new(None);
+        let stub_refresh_token_verifier = FakeTokenVerifier::new(Some(claims("foo")));
+        let stub_password_hasher = FakePasswordHasher::new("hashed");
+        let stub_password_validator = FakePasswordValidator::new(true);
+        let stub_oauth_client_repository = setup_oauth_client_repository();
+        let introspect = IntrospectUseCase::new(
+            &stub_password_hasher,
+            &stub_password_validator,
+            &stub_oauth_client_repository,
+            &stub_access_token_verifier,
+            &stub_refresh_token_verifier,
+        );
+
+        let result = introspect.execute(
+            "resource-server",
+            "secret",
+            "refresh_token",
+            Some(TokenTypeHint::AccessToken),
+        );
+
+        assert!(matches!(result, Ok(IntrospectResult::Active(c)) if c.sub == "foo"));
+    }
+
+    #[test]
+    fn execute_given_refresh_token_hint_should_try_refresh_token_verifier_first() {
+        let stub_access_token_verifier = FakeTokenVerifier::new(Some(claims("access")));
+        let stub_refresh_token_verifier = FakeTokenVerifier::new(Some(claims("refresh")));
+        let stub_password_hasher = FakePasswordHasher::new("hashed");
+        let stub_password_validator = FakePasswordValidator::new(true);
+        let stub_oauth_client_repository = setup_oauth_client_repository();
+        let introspect = IntrospectUseCase::new(
+            &stub_password_hasher,
+            &stub_password_validator,
+            &stub_oauth_client_repository,
+            &stub_access_token_verifier,
+            &stub_refresh_token_verifier,
+        );
+
+        let result = introspect.execute(
+            "resource-server",
+            "secret",
+            "token",
+            Some(TokenTypeHint::RefreshToken),
+        );
+
+        assert!(matches!(result, Ok(IntrospectResult::Active(c)) if c.sub == "refresh"));
+    }
+
+    #[test]
+    fn execute_given_invalid_token_should_return_inactive() {
+        let stub_access_token_verifier = FakeTokenVerifier::new(None);
+        let stub_refresh_token_verifier = FakeTokenVerifier::new(None);
+        let stub_password_hasher = FakePasswordHasher::new("hashed");
+        let stub_password_validator = FakePasswordValidator::new(true);
+        let stub_oauth_client_repository = setup_oauth_client_repository();
+        let introspect = IntrospectUseCase::new(
+            &stub_password_hasher,
+            &stub_password_validator,
+            &stub_oauth_client_repository,
+            &stub_access_token_verifier,
+            &stub_refresh_token_verifier,
+        );
+
+        let result = introspect.execute("resource-server", "secret", "invalid_token", None);
+
+        assert!(matches!(result, Ok(IntrospectResult::Inactive)));
+    }
+
+    #[test]
+    fn execute_given_unauthenticated_client_should_return_invalid_client() {
+        let test_cases = vec![("resource-server", false), ("not_exist", true)];
+
+        for (client_id, is_valid) in test_cases {
+            let stub_access_token_verifier = FakeTokenVerifier::new(Some(claims("foo")));
+            let stub_refresh_token_verifier = FakeTokenVerifier::new(Some(claims("foo")));
+            let stub_password_hasher = FakePasswordHasher::new("hashed");
+            let stub_password_validator = FakePasswordValidator::new(is_valid);
+            let stub_oauth_client_repository = setup_oauth_client_repository();
+            let introspect = IntrospectUseCase::new(
+                &stub_password_hasher,
+                &stub_password_validator,
+                &stub_oauth_client_repository,
+                &stub_access_token_verifier,
+                &stub_refresh_token_verifier,
+            );
+
+            let result = introspect.execute(client_id, "secret", "access_token", None);
+
+            assert!(matches!(result, Err(IntrospectFailReason::InvalidClient)));
+        }
+    }
+}
@@ -0,0 +1,116 @@
+use crate::domain::{entity::User, repository::UserRepository};
+
+const MAX_PAGE_SIZE: usize = 100;
+
+pub struct ListUsersUseCase<'a> {
+    user_repository: &'a dyn UserRepository,
+}
+
+impl<'a> ListUsersUseCase<'a> {
+    pub fn new(user_repository: &'a dyn UserRepository) -> Self {
+        ListUsersUseCase { user_repository }
+    }
+
+    pub fn execute(self, query: ListUsersQuery) -> Vec<User> {
+        match query.username {
+            Some(username) => self
+                .user_repository
+                .get_by_username(&username)
+                .into_iter()
+                .collect(),
+            None => self
+                .user_repository
+                .list(query.offset, query.limit.min(MAX_PAGE_SIZE)),
+        }
+    }
+}
+
+pub struct ListUsersQuery {
+    pub offset: usize,
+    pub limit: usize,
+    pub username: Option<String>,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::domain::value_object::{EmailAddress, UserId};
+    use crate::test_support::domain::repository::FakeUserRepository;
+
+    fn setup_repository(count: u64) -> FakeUserRepository {
+        let mut repo = FakeUserRepository::new();
+        for i in 0..count {
+            let id = format!("00000000-0000-4000-8000-{:012}", i);
+            repo.data.insert(
+                id.clone(),
+                User {
+                    id: UserId::new(&id).unwrap(),
+                    email: EmailAddress::new(&format!("user{}@example.com", i)).unwrap(),
+                    email_verified: false,
+                    pending_email: None,
+                    username: format!("user{}", i),
+                    password: "hashed".to_string(),
+                    create_at: 1747636936 + i,
+                    update_at: 1747636936 + i,
+                },
+            );
+        }
+        repo
+    }
+
+    #[test]
+    fn execute_given_offset_and_limit_should_return_page() {
+        let test_cases = vec![
+            (1, 2, vec!["user1", "user2"]),
+            (3, 10, vec!["user3", "user4"]),
+            (5, 10, vec![]),
+        ];
+
+        for (offset, limit, expected) in test_cases {
+            let stub_user_repository = setup_repository(5);
+            let list_users = ListUsersUseCase::new(&stub_user_repository);
+
+            let users = list_users.execute(ListUsersQuery {
+                offset,
+                limit,
+                username: None,
+            });
+
+            let usernames: Vec<String> = users.into_iter().map(|u| u.username).collect();
+            assert_eq!(usernames, expected);
+        }
+    }
+
+    #[test]
+    fn execute_given_limit_above_max_should_cap_page_size() {
+        let stub_user_repository = setup_repository(120);
+        let list_users = ListUsersUseCase::new(&stub_user_repository);
+
+        let users = list_users.execute(ListUsersQuery {
+            offset: 0,
+            limit: 1000,
+            username: None,
+        });
+
+        assert_eq!(users.len(), MAX_PAGE_SIZE);
+    }
+
+    #[test]
+    fn execute_given_username_should_return_matching_user_only() {
+        let test_cases = vec![("user2", vec!["user2"]), ("missing", vec![])];
+
+        for (username, expected) in test_cases {
+            let stub_user_repository = setup_repository(5);
+            let list_users = ListUsersUseCase::new(&stub_user_repository);
+
+            let users = list_users.execute(ListUsersQuery {
+                offset: 0,
+                limit: 20,
+                username: Some(username.to_string()),
+            });
+
+            let usernames: Vec<String> = users.into_iter().map(|u| u.username).collect();
+            assert_eq!(usernames, expected);
+        }
+    }
+}
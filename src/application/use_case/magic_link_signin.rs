@@ -0,0 +1,323 @@
+use super::signin::{SessionIssuer, SignInResult};
+use crate::application::service::auth::{TokenIssuer, TokenSubject, TokenVerifier};
+use crate::domain::{
+    repository::{RevokedTokenRepository, TotpCredentialRepository, UserRepository},
+    value_object::UserId,
+};
+
+pub struct MagicLinkSignInUseCase<'a> {
+    magic_link_token_verifier: &'a dyn TokenVerifier,
+    session_issuer: SessionIssuer<'a>,
+    mfa_token_issuer: &'a dyn TokenIssuer,
+    user_repository: &'a mut dyn UserRepository,
+    revoked_token_repository: &'a mut dyn RevokedTokenRepository,
+    totp_credential_repository: &'a dyn TotpCredentialRepository,
+    get_timestamp: fn() -> u64,
+}
+
+impl<'a> MagicLinkSignInUseCase<'a> {
+    pub fn new(
+        magic_link_token_verifier: &'a dyn TokenVerifier,
+        session_issuer: SessionIssuer<'a>,
+        mfa_token_issuer: &'a dyn TokenIssuer,
+        user_repository: &'a mut dyn UserRepository,
+        revoked_token_repository: &'a mut dyn RevokedTokenRepository,
+        totp_credential_repository: &'a dyn TotpCredentialRepository,
+        get_timestamp: fn() -> u64,
+    ) -> Self {
+        MagicLinkSignInUseCase {
+            magic_link_token_verifier,
+            session_issuer,
+            mfa_token_issuer,
+            user_repository,
+            revoked_token_repository,
+            totp_credential_repository,
+            get_timestamp,
+        }
+    }
+
+    pub fn execute(self, token: &str) -> Result<SignInResult, MagicLinkSignInFailReason> {
+        let claims = self
+            .magic_link_token_verifier
+            .verify(token)
+            .map_err(|_| MagicLinkSignInFailReason::InvalidToken)?;
+        let (Some(jti), Some(email)) = (claims.jti, claims.email) else {
+            return Err(MagicLinkSignInFailReason::InvalidToken);
+        };
+        let id = UserId::new(&claims.sub).map_err(|_| MagicLinkSignInFailReason::InvalidToken)?;
+        let mut user = self
+            .user_repository
+            .get_by_id(&id)
+            .map_err(|_| MagicLinkSignInFailReason::InvalidToken)?;
+        if user.email.as_str() != email {
+            return Err(MagicLinkSignInFailReason::InvalidToken);
+        }
+
+        if !self
+            .revoked_token_repository
+            .revoke_if_absent(&jti, claims.exp)
+        {
+            return Err(MagicLinkSignInFailReason::InvalidToken);
+        }
+        if !user.email_verified {
+            user.email_verified = true;
+            user.update_at = (self.get_timestamp)();
+            self.user_repository
+                .update(user.clone())
+                .map_err(|_| MagicLinkSignInFailReason::InvalidToken)?;
+        }
+        if self
+            .totp_credential_repository
+            .get(&user.id)
+            .is_ok_and(|credential| credential.confirmed)
+        {
+            return Err(MagicLinkSignInFailReason::MfaRequired {
+                mfa_token: self.mfa_token_issuer.issue(&TokenSubject {
+                    sub: user.id.as_str().to_string(),
+                    jti: Some(self.session_issuer.generate_id()),
+                    ..Default::default()
+                }),
+            });
+        }
+
+        Ok(self.session_issuer.issue(user))
+    }
+}
+
+#[derive(Debug)]
+pub enum MagicLinkSignInFailReason {
+    InvalidToken,
+    MfaRequired { mfa_token: String },
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::application::service::auth::TokenClaims;
+    use crate::domain::{
+        entity::{TotpCredential, User},
+        value_object::EmailAddress,
+    };
+    use crate::test_support::{
+        application::service::{FakeTokenIssuer, FakeTokenVerifier},
+        domain::repository::{
+            FakeRefreshTokenFamilyRepository, FakeRevokedTokenRepository,
+            FakeTotpCredentialRepository, FakeUserRepository,
+        },
+    };
+
+    const USER_ID: &str = "0196e2a4-5c1b-4f7e-9a3d-2b8c6d4e1f00";
+
+    fn fake_generate_id() -> String {
+        "generated_id".to_string()
+    }
+
+    fn fake_get_timestamp() -> u64 {
+        1747637000
+    }
+
+    fn claims(email: &str) -> TokenClaims {
+        TokenClaims {
+            sub: USER_ID.to_string(),
+            iss: "example".to_string(),
+            aud: "example:magic-link".to_string(),
+            iat: 1747636936,
+            exp: 1747637836,
+            jti: Some("token_id".to_string()),
+            fid: None,
+            email: Some(email.to_string()),
+            scope: None,
+        }
+    }
+
+    fn setup_user_repository(email_verified: bool) -> FakeUserRepository {
+        let mut repo = FakeUserRepository::new();
+        repo.data.insert(
+            USER_ID.to_string(),
+            User {
+                id: UserId::new(USER_ID).unwrap(),
+                email: EmailAddress::new("example@example.com").unwrap(),
+                email_verified,
+                pending_email: None,
+                username: "foo".to_string(),
+                password: "hashed".to_string(),
+                create_at: 1747636936,
+                update_at: 1747636936,
+            },
+        );
+        repo
+    }
+
+    #[test]
+    fn execute_given_valid_token_should_consume_token_and_return_signin_result() {
+        let stub_token_verifier = FakeTokenVerifier::new(Some(claims("example@example.com")));
+        let stub_access_token_issuer = FakeTokenIssuer::new("access_token");
+        let stub_refresh_token_issuer = FakeTokenIssuer::new("refresh_token");
+        let stub_mfa_token_issuer = FakeTokenIssuer::new("mfa_token");
+        let mut mock_user_repository = setup_user_repository(false);
+        let mut mock_refresh_token_family_repository = FakeRefreshTokenFamilyRepository::new();
+        let mut mock_revoked_token_repository = FakeRevokedTokenRepository::new();
+        let stub_totp_credential_repository = FakeTotpCredentialRepository::new();
+        let magic_link_signin = MagicLinkSignInUseCase::new(
+            &stub_token_verifier,
+            SessionIssuer::new(
+                &stub_access_token_issuer,
+                &stub_refresh_token_issuer,
+                &mut mock_refresh_token_family_repository,
+                fake_generate_id,
+            ),
+            &stub_mfa_token_issuer,
+            &mut mock_user_repository,
+            &mut mock_revoked_token_repository,
+            &stub_totp_credential_repository,
+            fake_get_timestamp,
+        );
+
+        let result = magic_link_signin.execute("magic_link_token");
+
+        assert!(result.is_ok_and(|result| {
+            result.access_token == "access_token"
+                && result.refresh_token == "refresh_token"
+                && result.id == USER_ID
+                && result.email == "example@example.com"
+        }));
+        assert_eq!(mock_revoked_token_repository.data["token_id"], 1747637836);
+        let user = &mock_user_repository.data[USER_ID];
+        assert!(user.email_verified);
+        assert_eq!(user.update_at, 1747637000);
+        assert!(
+            mock_refresh_token_family_repository
+                .data
+                .contains_key("generated_id")
+        );
+    }
+
+    #[test]
+    fn execute_given_token_consumed_twice_should_reject_second_consume() {
+        let stub_token_verifier = FakeTokenVerifier::new(Some(claims("example@example.com")));
+        let stub_access_token_issuer = FakeTokenIssuer::new("access_token");
+        let stub_refresh_token_issuer = FakeTokenIssuer::new("refresh_token");
+        let stub_mfa_token_issuer = FakeTokenIssuer::new("mfa_token");
+        let mut stub_user_repository = setup_user_repository(true);
+        let mut mock_refresh_token_family_repository = FakeRefreshTokenFamilyRepository::new();
+        let mut stub_revoked_token_repository = FakeRevokedTokenRepository::new();
+        let stub_totp_credential_repository = FakeTotpCredentialRepository::new();
+        let mut results = vec![];
+        for _ in 0..2 {
+            let magic_link_signin = MagicLinkSignInUseCase::new(
+                &stub_token_verifier,
+                SessionIssuer::new(
+                    &stub_access_token_issuer,
+                    &stub_refresh_token_issuer,
+                    &mut mock_refresh_token_family_repository,
+                    fake_generate_id,
+                ),
+                &stub_mfa_token_issuer,
+                &mut stub_user_repository,
+                &mut stub_revoked_token_repository,
+                &stub_totp_credential_repository,
+                fake_get_timestamp,
+            );
+            results.push(magic_link_signin.execute("magic_link_token"));
+        }
+
+        assert!(results[0].is_ok());
+        assert!(matches!(
+            results[1],
+            Err(MagicLinkSignInFailReason::InvalidToken)
+        ));
+        assert_eq!(mock_refresh_token_family_repository.data.len(), 1);
+    }
+
+    #[test]
+    fn execute_given_confirmed_totp_should_consume_token_and_require_mfa() {
+        let stub_token_verifier = FakeTokenVerifier::new(Some(claims("example@example.com")));
+        let stub_access_token_issuer = FakeTokenIssuer::new("access_token");
+        let stub_refresh_token_issuer = FakeTokenIssuer::new("refresh_token");
+        let stub_mfa_token_issuer = FakeTokenIssuer::new("mfa_token");
+        let mut stub_user_repository = setup_user_repository(true);
+        let mut mock_refresh_token_family_repository = FakeRefreshTokenFamilyRepository::new();
+        let mut mock_revoked_token_repository = FakeRevokedTokenRepository::new();
+        let mut stub_totp_credential_repository = FakeTotpCredentialRepository::new();
+        stub_totp_credential_repository.data.insert(
+            USER_ID.to_string(),
+            TotpCredential {
+                user_id: UserId::new(USER_ID).unwrap(),
+                secret: "secret".to_string(),
+                confirmed: true,
+                recovery_codes: vec![],
+                last_used_step: 0,
+            },
+        );
+        let magic_link_signin = MagicLinkSignInUseCase::new(
+            &stub_token_verifier,
+            SessionIssuer::new(
+                &stub_access_token_issuer,
+                &stub_refresh_token_issuer,
+                &mut mock_refresh_token_family_repository,
+                fake_generate_id,
+            ),
+            &stub_mfa_token_issuer,
+            &mut stub_user_repository,
+            &mut mock_revoked_token_repository,
+            &stub_totp_credential_repository,
+            fake_get_timestamp,
+        );
+
+        let result = magic_link_signin.execute("magic_link_token");
+
+        assert!(matches!(
+            result,
+            Err(MagicLinkSignInFailReason::MfaRequired { mfa_token }) if mfa_token == "mfa_token"
+        ));
+        assert!(mock_revoked_token_repository.data.contains_key("token_id"));
+        assert!(mock_refresh_token_family_repository.data.is_empty());
+    }
+
+    #[test]
+    fn execute_given_invalid_unbound_or_stale_token_should_return_invalid_token() {
+        let unbound_claims = TokenClaims {
+            jti: None,
+            ..claims("example@example.com")
+        };
+        let test_cases = vec![
+            None,
+            Some(unbound_claims),
+            Some(claims("previous@example.com")),
+        ];
+
+        for to_return in test_cases {
+            let stub_token_verifier = FakeTokenVerifier::new(to_return);
+            let stub_access_token_issuer = FakeTokenIssuer::new("access_token");
+            let stub_refresh_token_issuer = FakeTokenIssuer::new("refresh_token");
+            let stub_mfa_token_issuer = FakeTokenIssuer::new("mfa_token");
+            let mut stub_user_repository = setup_user_repository(false);
+            let mut mock_refresh_token_family_repository = FakeRefreshTokenFamilyRepository::new();
+            let mut mock_revoked_token_repository = FakeRevokedTokenRepository::new();
+            let stub_totp_credential_repository = FakeTotpCredentialRepository::new();
+            let magic_link_signin = MagicLinkSignInUseCase::new(
+                &stub_token_verifier,
+                SessionIssuer::new(
+                    &stub_access_token_issuer,
+                    &stub_refresh_token_issuer,
+                    &mut mock_refresh_token_family_repository,
+                    fake_generate_id,
+                ),
+                &stub_mfa_token_issuer,
+                &mut stub_user_repository,
+                &mut mock_revoked_token_repository,
+                &stub_totp_credential_repository,
+                fake_get_timestamp,
+            );
+
+            let result = magic_link_signin.execute("magic_link_token");
+
+            assert!(matches!(
+                result,
+                Err(MagicLinkSignInFailReason::InvalidToken)
+            ));
+            assert!(mock_revoked_token_repository.data.is_empty());
+            assert!(mock_refresh_token_family_repository.data.is_empty());
+        }
+    }
+}
@@ -1,5 +1,64 @@
+mod authorize;
+mod begin_authorization;
+mod begin_passkey_registration;
+mod begin_passkey_signin;
+mod change_email;
+mod change_password;
+mod client_credentials;
+mod confirm_totp;
+mod delete_account;
+mod enroll_totp;
+mod exchange_authorization_code;
+mod forgot_password;
+mod introspect;
+mod list_users;
+mod magic_link_signin;
+mod passkey_signin;
+mod refresh;
+mod register_passkey;
+mod reset_password;
+mod revoke;
+mod send_email_verification;
+mod send_magic_link;
 mod signin;
+mod signin_mfa;
+mod signout;
 mod signup;
+mod verify_email;
 
-pub use signin::{FailReason, SignInResult, SignInUseCase};
-pub use signup::{CreateUserDTO, SignUpUseCase};
+pub use authorize::{AuthorizationCodeIssuer, AuthorizeFailReason, AuthorizeUseCase};
+pub use begin_authorization::{
+    AuthorizationRequest, AuthorizationRequestError, BeginAuthorizationUseCase,
+};
+pub use begin_passkey_registration::BeginPasskeyRegistrationUseCase;
+pub use begin_passkey_signin::BeginPasskeySignInUseCase;
+pub use change_email::{ChangeEmailFailReason, ChangeEmailUseCase};
+pub use change_password::{ChangePasswordFailReason, ChangePasswordUseCase};
+pub use client_credentials::{
+    ClientAuthenticator, ClientCredentialsFailReason, ClientCredentialsUseCase,
+};
+pub use confirm_totp::{ConfirmTotpFailReason, ConfirmTotpUseCase};
+pub use delete_account::{DeleteAccountFailReason, DeleteAccountUseCase};
+pub use enroll_totp::{EnrollTotpFailReason, EnrollTotpUseCase};
+pub use exchange_authorization_code::{
+    ExchangeAuthorizationCodeFailReason, ExchangeAuthorizationCodeUseCase,
+};
+pub use forgot_password::{ForgotPasswordUseCase, PasswordResetPolicy};
+pub use introspect::{IntrospectFailReason, IntrospectResult, IntrospectUseCase, TokenTypeHint};
+pub use list_users::{ListUsersQuery, ListUsersUseCase};
+pub use magic_link_signin::{MagicLinkSignInFailReason, MagicLinkSignInUseCase};
+pub use passkey_signin::{PasskeySignInFailReason, PasskeySignInUseCase};
+pub use refresh::RefreshUseCase;
+pub use register_passkey::{RegisterPasskeyFailReason, RegisterPasskeyUseCase};
+pub use reset_password::{ResetPasswordFailReason, ResetPasswordUseCase};
+pub use revoke::RevokeUseCase;
+pub use send_email_verification::SendEmailVerificationUseCase;
+pub use send_magic_link::SendMagicLinkUseCase;
+pub use signin::{
+    FailReason, LoginThrottle, LoginThrottlePolicy, PasswordAuthenticator, SessionIssuer,
+    SignInResult, SignInUseCase,
+};
+pub use signin_mfa::{SecondFactor, SignInMfaFailReason, SignInMfaUseCase};
+pub use signout::SignOutUseCase;
+pub use signup::{CreateUserDTO, PasswordRules, SignUpFailReason, SignUpUseCase};
+pub use verify_email::VerifyEmailUseCase;
@@ -0,0 +1,368 @@
+use super::signin::{SessionIssuer, SignInResult};
+use crate::application::service::auth::PasskeyVerifier;
+use crate::domain::repository::{
+    PasskeyChallengeRepository, PasskeyCredentialRepository, UserRepository,
+};
+
+pub struct PasskeySignInUseCase<'a> {
+    passkey_verifier: &'a dyn PasskeyVerifier,
+    session_issuer: SessionIssuer<'a>,
+    user_repository: &'a dyn UserRepository,
+    passkey_credential_repository: &'a mut dyn PasskeyCredentialRepository,
+    passkey_challenge_repository: &'a mut dyn PasskeyChallengeRepository,
+    require_verified_email: bool,
+}
+
+impl<'a> PasskeySignInUseCase<'a> {
+    pub fn new(
+        passkey_verifier: &'a dyn PasskeyVerifier,
+        session_issuer: SessionIssuer<'a>,
+        user_repository: &'a dyn UserRepository,
+        passkey_credential_repository: &'a mut dyn PasskeyCredentialRepository,
+        passkey_challenge_repository: &'a mut dyn PasskeyChallengeRepository,
+        require_verified_email: bool,
+    ) -> Self {
+        PasskeySignInUseCase {
+            passkey_verifier,
+            session_issuer,
+            user_repository,
+            passkey_credential_repository,
+            passkey_challenge_repository,
+            require_verified_email,
+        }
+    }
+
+    pub fn execute(
+        self,
+        challenge: &str,
+        credential_id: &str,
+        client_data_json: &[u8],
+        authenticator_data: &[u8],
+        signature: &[u8],
+    ) -> Result<SignInResult, PasskeySignInFailReason> {
+        self.passkey_challenge_repository
+            .take(challenge)
+            .ok()
+            .filter(|stored| stored.user_id.is_none())
+            .ok_or(PasskeySignInFailReason::InvalidChallenge)?;
+
+        let credential = self
+            .passkey_credential_repository
+            .get(credential_id)
+            .map_err(|_| PasskeySignInFailReason::InvalidCredential)?;
+        let sign_count = self
+            .passkey_verifier
+            .verify_assertion(
+                challenge,
+                &credential.public_key,
+                client_data_json,
+                authenticator_data,
+                signature,
+            )
+            .map_err(|_| PasskeySignInFailReason::InvalidCredential)?;
+        if (sign_count != 0 || credential.sign_count != 0) && sign_count <= credential.sign_count {
+            return Err(PasskeySignInFailReason::InvalidCredential);
+        }
+        let user = self
+            .user_repository
+            .get_by_id(&credential.user_id)
+            .map_err(|_| PasskeySignInFailReason::InvalidCredential)?;
+        self.passkey_credential_repository
+            .update_sign_count(&credential.id, credential.sign_count, sign_count)
+            .map_err(|_| PasskeySignInFailReason::InvalidCredential)?;
+        if self.require_verified_email && !user.email_verified {
+            return Err(PasskeySignInFailReason::EmailNotVerified);
+        }
+
+        Ok(self.session_issuer.issue(user))
+    }
+}
+
+#[derive(Debug)]
+pub enum PasskeySignInFailReason {
+    InvalidChallenge,
+    InvalidCredential,
+    EmailNotVerified,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::domain::{
+        entity::{PasskeyChallenge, PasskeyCredential, User},
+        value_object::{EmailAddress, UserId},
+    };
+    use crate::test_support::{
+        application::service::{FakePasskeyVerifier, FakeTokenIssuer},
+        domain::repository::{
+            FakePasskeyChallengeRepository, FakePasskeyCredentialRepository,
+            FakeRefreshTokenFamilyRepository, FakeUserRepository,
+        },
+    };
+
+    const USER_ID: &str = "0196e2a4-5c1b-4f7e-9a3d-2b8c6d4e1f00";
+
+    fn fake_generate_id() -> String {
+        "generated_id".to_string()
+    }
+
+    fn setup_user_repository(email_verified: bool) -> FakeUserRepository {
+        let mut repo = FakeUserRepository::new();
+        repo.data.insert(
+            USER_ID.to_string(),
+            User {
+                id: UserId::new(USER_ID).unwrap(),
+                email: EmailAddress::new("example@example.com").unwrap(),
+                email_verified,
+                pending_email: None,
+                username: "foo".to_string(),
+                password: "hashed".to_string(),
+                create_at: 1747636936,
+                update_at: 1747636936,
+            },
+        );
+        repo
+    }
+
+    fn setup_credential_repository(sign_count: u32) -> FakePasskeyCredentialRepository {
+        let mut repo = FakePasskeyCredentialRepository::new();
+        repo.data.insert(
+            "credential_id".to_string(),
+            PasskeyCredential {
+                id: "credential_id".to_string(),
+                user_id: UserId::new(USER_ID).unwrap(),
+                public_key: "public_key".to_string(),
+                sign_count,
+                create_at: 1747636936,
+            },
+        );
+        repo
+    }
+
+    fn setup_challenge_repository(user_id: Option<&str>) -> FakePasskeyChallengeRepository {
+        let mut repo = FakePasskeyChallengeRepository::new();
+        repo.data.insert(
+            "challenge".to_string(),
+            PasskeyChallenge {
+                challenge: "challenge".to_string(),
+                user_id: user_id.map(|id| UserId::new(id).unwrap()),
+                expire_at: 1747637236,
+            },
+        );
+        repo
+    }
+
+    #[test]
+    fn execute_given_valid_assertion_should_return_signin_result() {
+        let stub_passkey_verifier = FakePasskeyVerifier::new(true, 8);
+        let stub_access_token_issuer = FakeTokenIssuer::new("access_token");
+        let stub_refresh_token_issuer = FakeTokenIssuer::new("refresh_token");
+        let stub_user_repository = setup_user_repository(true);
+        let mut mock_passkey_credential_repository = setup_credential_repository(7);
+        let mut mock_passkey_challenge_repository = setup_challenge_repository(None);
+        let mut mock_refresh_token_family_repository = FakeRefreshTokenFamilyRepository::new();
+        let passkey_signin = PasskeySignInUseCase::new(
+            &stub_passkey_verifier,
+            SessionIssuer::new(
+                &stub_access_token_issuer,
+                &stub_refresh_token_issuer,
+                &mut mock_refresh_token_family_repository,
+                fake_generate_id,
+            ),
+            &stub_user_repository,
+            &mut mock_passkey_credential_repository,
+            &mut mock_passkey_challenge_repository,
+            false,
+        );
+
+        let result = passkey_signin.execute(
+            "challenge",
+            "credential_id",
+            b"client_data_json",
+            b"authenticator_data",
+            b"signature",
+        );
+
+        assert!(result.is_ok_and(|result| {
+            result.access_token == "access_token"
+                && result.refresh_token == "refresh_token"
+                && result.id == USER_ID
+                && result.username == "foo"
+        }));
+        assert_eq!(
+            mock_passkey_credential_repository.data["credential_id"].sign_count,
+            8
+        );
+        assert!(mock_passkey_challenge_repository.data.is_empty());
+        assert!(
+            mock_refresh_token_family_repository
+                .data
+                .contains_key("generated_id")
+        );
+    }
+
+    #[test]
+    fn execute_given_authenticator_without_counter_should_accept_zero_sign_count() {
+        let stub_passkey_verifier = FakePasskeyVerifier::new(true, 0);
+        let stub_access_token_issuer = FakeTokenIssuer::new("access_token");
+        let stub_refresh_token_issuer = FakeTokenIssuer::new("refresh_token");
+        let stub_user_repository = setup_user_repository(true);
+        let mut stub_passkey_credential_repository = setup_credential_repository(0);
+        let mut stub_passkey_challenge_repository = setup_challenge_repository(None);
+        let mut stub_refresh_token_family_repository = FakeRefreshTokenFamilyRepository::new();
+        let passkey_signin = PasskeySignInUseCase::new(
+            &stub_passkey_verifier,
+            SessionIssuer::new(
+                &stub_access_token_issuer,
+                &stub_refresh_token_issuer,
+                &mut stub_refresh_token_family_repository,
+                fake_generate_id,
+            ),
+            &stub_user_repository,
+            &mut stub_passkey_credential_repository,
+            &mut stub_passkey_challenge_repository,
+            false,
+        );
+
+        let result = passkey_signin.execute(
+            "challenge",
+            "credential_id",
+            b"client_data_json",
+            b"authenticator_data",
+            b"signature",
+        );
+
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn execute_given_invalid_or_cloned_credential_should_return_invalid_credential() {
+        let test_cases = vec![
+            (FakePasskeyVerifier::new(false, 8), "credential_id"),
+            (FakePasskeyVerifier::new(true, 7), "credential_id"),
+            (FakePasskeyVerifier::new(true, 0), "credential_id"),
+            (FakePasskeyVerifier::new(true, 8), "unknown_credential"),
+        ];
+
+        for (stub_passkey_verifier, credential_id) in test_cases {
+            let stub_access_token_issuer = FakeTokenIssuer::new("access_token");
+            let stub_refresh_token_issuer = FakeTokenIssuer::new("refresh_token");
+            let stub_user_repository = setup_user_repository(true);
+            let mut mock_passkey_credential_repository = setup_credential_repository(7);
+            let mut mock_passkey_challenge_repository = setup_challenge_repository(None);
+            let mut mock_refresh_token_family_repository = FakeRefreshTokenFamilyRepository::new();
+            let passkey_signin = PasskeySignInUseCase::new(
+                &stub_passkey_verifier,
+                SessionIssuer::new(
+                    &stub_access_token_issuer,
+                    &stub_refresh_token_issuer,
+                    &mut mock_refresh_token_family_repository,
+                    fake_generate_id,
+                ),
+                &stub_user_repository,
+                &mut mock_passkey_credential_repository,
+                &mut mock_passkey_challenge_repository,
+                false,
+            );
+
+            let result = passkey_signin.execute(
+                "challenge",
+                credential_id,
+                b"client_data_json",
+                b"authenticator_data",
+                b"signature",
+            );
+
+            assert!(matches!(
+                result,
+                Err(PasskeySignInFailReason::InvalidCredential)
+            ));
+            assert_eq!(
+                mock_passkey_credential_repository.data["credential_id"].sign_count,
+                7
+            );
+            assert!(mock_passkey_challenge_repository.data.is_empty());
+            assert!(mock_refresh_token_family_repository.data.is_empty());
+        }
+    }
+
+    #[test]
+    fn execute_given_registration_or_unknown_challenge_should_return_invalid_challenge() {
+        let test_cases = vec![("challenge", Some(USER_ID)), ("unknown_challenge", None)];
+
+        for (challenge, user_id) in test_cases {
+            let stub_passkey_verifier = FakePasskeyVerifier::new(true, 8);
+            let stub_access_token_issuer = FakeTokenIssuer::new("access_token");
+            let stub_refresh_token_issuer = FakeTokenIssuer::new("refresh_token");
+            let stub_user_repository = setup_user_repository(true);
+            let mut stub_passkey_credential_repository = setup_credential_repository(7);
+            let mut stub_passkey_challenge_repository = setup_challenge_repository(user_id);
+            let mut mock_refresh_token_family_repository = FakeRefreshTokenFamilyRepository::new();
+            let passkey_signin = PasskeySignInUseCase::new(
+                &stub_passkey_verifier,
+                SessionIssuer::new(
+                    &stub_access_token_issuer,
+                    &stub_refresh_token_issuer,
+                    &mut mock_refresh_token_family_repository,
+                    fake_generate_id,
+                ),
+                &stub_user_repository,
+                &mut stub_passkey_credential_repository,
+                &mut stub_passkey_challenge_repository,
+                false,
+            );
+
+            let result = passkey_signin.execute(
+                challenge,
+                "credential_id",
+                b"client_data_json",
+                b"authenticator_data",
+                b"signature",
+            );
+
+            assert!(matches!(
+                result,
+                Err(PasskeySignInFailReason::InvalidChallenge)
+            ));
+            assert!(mock_refresh_token_family_repository.data.is_empty());
+        }
+    }
+
+    #[test]
+    fn execute_given_unverified_email_should_require_verification_when_configured() {
+        let stub_passkey_verifier = FakePasskeyVerifier::new(true, 8);
+        let stub_access_token_issuer = FakeTokenIssuer::new("access_token");
+        let stub_refresh_token_issuer = FakeTokenIssuer::new("refresh_token");
+        let stub_user_repository = setup_user_repository(false);
+        let mut stub_passkey_credential_repository = setup_credential_repository(7);
+        let mut stub_passkey_challenge_repository = setup_challenge_repository(None);
+        let mut mock_refresh_token_family_repository = FakeRefreshTokenFamilyRepository::new();
+        let passkey_signin = PasskeySignInUseCase::new(
+            &stub_passkey_verifier,
+            SessionIssuer::new(
+                &stub_access_token_issuer,
+                &stub_refresh_token_issuer,
+                &mut mock_refresh_token_family_repository,
+                fake_generate_id,
+            ),
+            &stub_user_repository,
+            &mut stub_passkey_credential_repository,
+            &mut stub_passkey_challenge_repository,
+            true,
+        );
+
+        let result = passkey_signin.execute(
+            "challenge",
+            "credential_id",
+            b"client_data_json",
+            b"authenticator_data",
+            b"signature",
+        );
+
+        assert!(matches!(
+            result,
+            Err(PasskeySignInFailReason::EmailNotVerified)
+        ));
+        assert!(mock_refresh_token_family_repository.data.is_empty());
+    }
+}
@@ -0,0 +1,244 @@
+use crate::application::service::auth::{TokenIssuer, TokenSubject, TokenVerifier};
+use crate::domain::{error, repository::RefreshTokenFamilyRepository};
+
+pub struct RefreshUseCase<'a> {
+    refresh_token_verifier: &'a dyn TokenVerifier,
+    access_token_issuer: &'a dyn TokenIssuer,
+    refresh_token_issuer: &'a dyn TokenIssuer,
+    refresh_token_family_repository: &'a mut dyn RefreshTokenFamilyRepository,
+    generate_id: fn() -> String,
+}
+
+impl<'a> RefreshUseCase<'a> {
+    pub fn new(
+        refresh_token_verifier: &'a dyn TokenVerifier,
+        access_token_issuer: &'a dyn TokenIssuer,
+        refresh_token_issuer: &'a dyn TokenIssuer,
+        refresh_token_family_repository: &'a mut dyn RefreshTokenFamilyRepository,
+        generate_id: fn() -> String,
+    ) -> Self {
+        RefreshUseCase {
+            refresh_token_verifier,
+            access_token_issuer,
+            refresh_token_issuer,
+            refresh_token_family_repository,
+            generate_id,
+        }
+    }
+
+    pub fn execute(self, refresh_token: &str) -> Result<RefreshResult, error::InvalidToken> {
+        let claims = self.refresh_token_verifier.verify(refresh_token)?;
+        let (Some(token_id), Some(family_id)) = (claims.jti, claims.fid) else {
+            return Err(error::InvalidToken {});
+        };
+        let family = self
+            .refresh_token_family_repository
+            .get(&family_id)
+            .map_err(|_| error::InvalidToken {})?;
+        if family.revoked {
+            return Err(error::InvalidToken {});
+        }
+
+        let next_token_id = (self.generate_id)();
+        self.refresh_token_family_repository
+            .rotate(&family.id, &token_id, &next_token_id)
+            .map_err(|_| error::InvalidToken {})?;
+        let refresh_token = self.refresh_token_issuer.issue(&TokenSubject {
+            sub: family.subject,
+            jti: Some(next_token_id),
+            fid: Some(family.id),
+            email: None,
+            scope: None,
+        });
+
+        Ok(RefreshResult {
+            access_token: self.access_token_issuer.issue(&TokenSubject {
+                sub: claims.sub,
+                jti: Some((self.generate_id)()),
+                fid: None,
+                email: None,
+                scope: None,
+            }),
+            refresh_token,
+        })
+    }
+}
+
+pub struct RefreshResult {
+    pub access_token: String,
+    pub refresh_token: String,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::application::service::auth::TokenClaims;
+    use crate::domain::entity::RefreshTokenFamily;
+    use crate::test_support::{
+        application::service::{FakeTokenIssuer, FakeTokenVerifier},
+        domain::repository::FakeRefreshTokenFamilyRepository,
+    };
+
+    fn fake_generate_id() -> String {
+        "next_token_id".to_string()
+    }
+
+    fn claims(token_id: &str) -> TokenClaims {
+        TokenClaims {
+            sub: "foo".to_string(),
+            iss: "example".to_string(),
+            aud: "example".to_string(),
+            iat: 1747636936,
+            exp: 1747640536,
+            jti: Some(token_id.to_string()),
+            fid: Some("family_id".to_string()),
+            email: None,
+            scope: None,
+        }
+    }
+
+    fn setup_repository(revoked: bool) -> FakeRefreshTokenFamilyRepository {
+        let mut repo = FakeRefreshTokenFamilyRepository::new();
+        repo.data.insert(
+            "family_id".to_string(),
+            RefreshTokenFamily {
+                id: "family_id".to_string(),
+                subject: "foo".to_string(),
+                current_token_id: "token_id".to_string(),
+                revoked,
+            },
+        );
+        repo
+    }
+
+    #[test]
+    fn execute_given_valid_refresh_token_should_return_new_tokens() {
+        let stub_refresh_token_verifier = FakeTokenVerifier::new(Some(claims("token_id")));
+        let stub_access_token_issuer = FakeTokenIssuer::new("access_token");
+        let stub_refresh_token_issuer = FakeTokenIssuer::new("refresh_token");
+        let mut stub_refresh_token_family_repository = setup_repository(false);
+        let refresh = RefreshUseCase::new(
+            &stub_refresh_token_verifier,
+            &stub_access_token_issuer,
+            &stub_refresh_token_issuer,
+            &mut stub_refresh_token_family_repository,
+            fake_generate_id,
+        );
+
+        let result = refresh.execute("old_refresh_token");
+
+        assert!(
+            result.is_ok_and(
+                |r| r.access_token == "access_token" && r.refresh_token == "refresh_token"
+            )
+        );
+    }
+
+    #[test]
+    fn execute_given_valid_refresh_token_should_rotate_family() {
+        let stub_refresh_token_verifier = FakeTokenVerifier::new(Some(claims("token_id")));
+        let stub_access_token_issuer = FakeTokenIssuer::new("access_token");
+        let stub_refresh_token_issuer = FakeTokenIssuer::new("refresh_token");
+        let mut mock_refresh_token_family_repository = setup_repository(false);
+        let refresh = RefreshUseCase::new(
+            &stub_refresh_token_verifier,
+            &stub_access_token_issuer,
+            &stub_refresh_token_issuer,
+            &mut mock_refresh_token_family_repository,
+            fake_generate_id,
+        );
+
+        let _ = refresh.execute("old_refresh_token");
+
+        assert!(
+            mock_refresh_token_family_repository.data["family_id"].current_token_id
+                == "next_token_id"
+        );
+    }
+
+    #[test]
+    fn execute_given_reused_refresh_token_should_revoke_family() {
+        let stub_refresh_token_verifier = FakeTokenVerifier::new(Some(claims("used_token_id")));
+        let stub_access_token_issuer = FakeTokenIssuer::new("access_token");
+        let stub_refresh_token_issuer = FakeTokenIssuer::new("refresh_token");
+        let mut mock_refresh_token_family_repository = setup_repository(false);
+        let refresh = RefreshUseCase::new(
+            &stub_refresh_token_verifier,
+            &stub_access_token_issuer,
+            &stub_refresh_token_issuer,
+            &mut mock_refresh_token_family_repository,
+            fake_generate_id,
+        );
+
+        let result = refresh.execute("used_refresh_token");
+
+        assert!(result.is_err_and(|err| matches!(err, error::InvalidToken {})));
+        assert!(mock_refresh_token_family_repository.data["family_id"].revoked);
+    }
+
+    #[test]
+    fn execute_given_stale_refresh_token_after_rotation_should_lose_and_revoke_family() {
+        let stub_refresh_token_verifier = FakeTokenVerifier::new(Some(claims("token_id")));
+        let stub_access_token_issuer = FakeTokenIssuer::new("access_token");
+        let stub_refresh_token_issuer = FakeTokenIssuer::new("refresh_token");
+        let mut mock_refresh_token_family_repository = setup_repository(false);
+        let first_refresh = RefreshUseCase::new(
+            &stub_refresh_token_verifier,
+            &stub_access_token_issuer,
+            &stub_refresh_token_issuer,
+            &mut mock_refresh_token_family_repository,
+            fake_generate_id,
+        );
+        assert!(first_refresh.execute("old_refresh_token").is_ok());
+        let second_refresh = RefreshUseCase::new(
+            &stub_refresh_token_verifier,
+            &stub_access_token_issuer,
+            &stub_refresh_token_issuer,
+            &mut mock_refresh_token_family_repository,
+            fake_generate_id,
+        );
+
+        let result = second_refresh.execute("old_refresh_token");
+
+        assert!(result.is_err_and(|err| matches!(err, error::InvalidToken {})));
+        assert!(mock_refresh_token_family_repository.data["family_id"].revoked);
+    }
+
+    #[test]
+    fn execute_given_refresh_token_of_revoked_family_should_return_invalid_token() {
+        let stub_refresh_token_verifier = FakeTokenVerifier::new(Some(claims("token_id")));
+        let stub_access_token_issuer = FakeTokenIssuer::new("access_token");
+        let stub_refresh_token_issuer = FakeTokenIssuer::new("refresh_token");
+        let mut stub_refresh_token_family_repository = setup_repository(true);
+        let refresh = RefreshUseCase::new(
+            &stub_refresh_token_verifier,
+            &stub_access_token_issuer,
+            &stub_refresh_token_issuer,
+            &mut stub_refresh_token_family_repository,
+            fake_generate_id,
+        );
+
+        let result = refresh.execute("old_refresh_token");
+
+        assert!(result.is_err_and(|err| matches!(err, error::InvalidToken {})));
+    }
+
+    #[test]
+    fn execute_given_invalid_refresh_token_should_return_invalid_token() {
+        let stub_refresh_token_verifier = FakeTokenVerifier::new(None);
+        let stub_access_token_issuer = FakeTokenIssuer::new("access_token");
+        let stub_refresh_token_issuer = FakeTokenIssuer::new("refresh_token");
+        let mut stub_refresh_token_family_repository = setup_repository(false);
+        let refresh = RefreshUseCase::new(
+            &stub_refresh_token_verifier,
+            &stub_access_token_issuer,
+            &stub_refresh_token_issuer,
+            &mut stub_refresh_token_family_repository,
+            fake_generate_id,
+        );
+
+        let result = refresh.execute("invalid_refresh_token");
+
+        assert!(result.is_err_and(|err| matches!(err, error::InvalidToken {})));
+    }
+}
@@ -0,0 +1,182 @@
+use crate::application::service::auth::PasskeyVerifier;
+use crate::domain::{
+    entity::PasskeyCredential,
+    error,
+    repository::{PasskeyChallengeRepository, PasskeyCredentialRepository},
+    value_object::UserId,
+};
+
+pub struct RegisterPasskeyUseCase<'a> {
+    passkey_verifier: &'a dyn PasskeyVerifier,
+    passkey_credential_repository: &'a mut dyn PasskeyCredentialRepository,
+    passkey_challenge_repository: &'a mut dyn PasskeyChallengeRepository,
+    get_timestamp: fn() -> u64,
+}
+
+impl<'a> RegisterPasskeyUseCase<'a> {
+    pub fn new(
+        passkey_verifier: &'a dyn PasskeyVerifier,
+        passkey_credential_repository: &'a mut dyn PasskeyCredentialRepository,
+        passkey_challenge_repository: &'a mut dyn PasskeyChallengeRepository,
+        get_timestamp: fn() -> u64,
+    ) -> Self {
+        RegisterPasskeyUseCase {
+            passkey_verifier,
+            passkey_credential_repository,
+            passkey_challenge_repository,
+            get_timestamp,
+        }
+    }
+
+    pub fn execute(
+        self,
+        id: &UserId,
+        challenge: &str,
+        client_data_json: &[u8],
+        attestation_object: &[u8],
+    ) -> Result<String, RegisterPasskeyFailReason> {
+        self.passkey_challenge_repository
+            .take(challenge)
+            .ok()
+            .filter(|stored| stored.user_id.as_ref().map(UserId::as_str) == Some(id.as_str()))
+            .ok_or(RegisterPasskeyFailReason::InvalidChallenge)?;
+
+        let attested = self
+            .passkey_verifier
+            .verify_registration(challenge, client_data_json, attestation_object)
+            .map_err(RegisterPasskeyFailReason::InvalidResponse)?;
+        self.passkey_credential_repository
+            .create(PasskeyCredential {
+                id: attested.id.clone(),
+                user_id: id.clone(),
+                public_key: attested.public_key,
+                sign_count: attested.sign_count,
+                create_at: (self.get_timestamp)(),
+            })
+            .map_err(|_| RegisterPasskeyFailReason::CredentialConflict)?;
+
+        Ok(attested.id)
+    }
+}
+
+#[derive(Debug)]
+pub enum RegisterPasskeyFailReason {
+    InvalidChallenge,
+    InvalidResponse(error::InvalidPasskeyResponse),
+    CredentialConflict,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::domain::entity::PasskeyChallenge;
+    use crate::test_support::{
+        application::service::FakePasskeyVerifier,
+        domain::repository::{FakePasskeyChallengeRepository, FakePasskeyCredentialRepository},
+    };
+
+    const USER_ID: &str = "0196e2a4-5c1b-4f7e-9a3d-2b8c6d4e1f00";
+    const OTHER_USER_ID: &str = "0196e2a4-5c1b-4f7e-9a3d-2b8c6d4e1f01";
+
+    fn fake_get_timestamp() -> u64 {
+        1747636936
+    }
+
+    fn setup_challenge_repository(user_id: Option<&str>) -> FakePasskeyChallengeRepository {
+        let mut repo = FakePasskeyChallengeRepository::new();
+        repo.data.insert(
+            "challenge".to_string(),
+            PasskeyChallenge {
+                challenge: "challenge".to_string(),
+                user_id: user_id.map(|id| UserId::new(id).unwrap()),
+                expire_at: 1747637236,
+            },
+        );
+        repo
+    }
+
+    #[test]
+    fn execute_given_valid_attestation_should_persist_credential() {
+        let stub_passkey_verifier = FakePasskeyVerifier::new(true, 0);
+        let mut mock_passkey_credential_repository = FakePasskeyCredentialRepository::new();
+        let mut mock_passkey_challenge_repository = setup_challenge_repository(Some(USER_ID));
+        let register_passkey = RegisterPasskeyUseCase::new(
+            &stub_passkey_verifier,
+            &mut mock_passkey_credential_repository,
+            &mut mock_passkey_challenge_repository,
+            fake_get_timestamp,
+        );
+
+        let result = register_passkey.execute(
+            &UserId::new(USER_ID).unwrap(),
+            "challenge",
+            b"client_data_json",
+            b"attestation_object",
+        );
+
+        assert_eq!(result.ok().as_deref(), Some("credential_id"));
+        let credential = &mock_passkey_credential_repository.data["credential_id"];
+        assert_eq!(credential.user_id.as_str(), USER_ID);
+        assert_eq!(credential.public_key, "public_key");
+        assert_eq!(credential.create_at, 1747636936);
+        assert!(mock_passkey_challenge_repository.data.is_empty());
+    }
+
+    #[test]
+    fn execute_given_challenge_of_other_ceremony_should_return_invalid_challenge() {
+        let test_cases = vec![Some(OTHER_USER_ID), None];
+
+        for user_id in test_cases {
+            let stub_passkey_verifier = FakePasskeyVerifier::new(true, 0);
+            let mut mock_passkey_credential_repository = FakePasskeyCredentialRepository::new();
+            let mut mock_passkey_challenge_repository = setup_challenge_repository(user_id);
+            let register_passkey = RegisterPasskeyUseCase::new(
+                &stub_passkey_verifier,
+                &mut mock_passkey_credential_repository,
+                &mut mock_passkey_challenge_repository,
+                fake_get_timestamp,
+            );
+
+            let result = register_passkey.execute(
+                &UserId::new(USER_ID).unwrap(),
+                "challenge",
+                b"client_data_json",
+                b"attestation_object",
+            );
+
+            assert!(matches!(
+                result,
+                Err(RegisterPasskeyFailReason::InvalidChallenge)
+            ));
+            assert!(mock_passkey_credential_repository.data.is_empty());
+            assert!(mock_passkey_challenge_repository.data.is_empty());
+        }
+    }
+
+    #[test]
+    fn execute_given_invalid_attestation_should_consume_challenge() {
+        let stub_passkey_verifier = FakePasskeyVerifier::new(false, 0);
+        let mut mock_passkey_credential_repository = FakePasskeyCredentialRepository::new();
+        let mut mock_passkey_challenge_repository = setup_challenge_repository(Some(USER_ID));
+        let register_passkey = RegisterPasskeyUseCase::new(
+            &stub_passkey_verifier,
+            &mut mock_passkey_credential_repository,
+            &mut mock_passkey_challenge_repository,
+            fake_get_timestamp,
+        );
+
+        let result = register_passkey.execute(
+            &UserId::new(USER_ID).unwrap(),
+            "challenge",
+            b"client_data_json",
+            b"attestation_object",
+        );
+
+        assert!(matches!(
+            result,
+            Err(RegisterPasskeyFailReason::InvalidResponse(_))
+        ));
+        assert!(mock_passkey_credential_repository.data.is_empty());
+        assert!(mock_passkey_challenge_repository.data.is_empty());
+    }
+}
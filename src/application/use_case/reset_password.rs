@@ -0,0 +1,289 @@
+use super::signup::PasswordRules;
+use crate::application::service::auth::PasswordHasher;
+use crate::domain::{
+    error,
+    repository::{PasswordResetTokenRepository, RefreshTokenFamilyRepository, UserRepository},
+};
+
+pub struct ResetPasswordUseCase<'a> {
+    password_hasher: &'a dyn PasswordHasher,
+    password_rules: PasswordRules<'a>,
+    user_repository: &'a mut dyn UserRepository,
+    password_reset_token_repository: &'a mut dyn PasswordResetTokenRepository,
+    refresh_token_family_repository: &'a mut dyn RefreshTokenFamilyRepository,
+    hash_secret: fn(&str) -> String,
+    get_timestamp: fn() -> u64,
+}
+
+impl<'a> ResetPasswordUseCase<'a> {
+    pub fn new(
+        password_hasher: &'a dyn PasswordHasher,
+        password_rules: PasswordRules<'a>,
+        user_repository: &'a mut dyn UserRepository,
+        password_reset_token_repository: &'a mut dyn PasswordResetTokenRepository,
+        refresh_token_family_repository: &'a mut dyn RefreshTokenFamilyRepository,
+        hash_secret: fn(&str) -> String,
+        get_timestamp: fn() -> u64,
+    ) -> Self {
+        ResetPasswordUseCase {
+            password_hasher,
+            password_rules,
+            user_repository,
+            password_reset_token_repository,
+            refresh_token_family_repository,
+            hash_secret,
+            get_timestamp,
+        }
+    }
+
+    pub fn execute(self, token: &str, new_password: &str) -> Result<(), ResetPasswordFailReason> {
+        let now = (self.get_timestamp)();
+        let token_hash = (self.hash_secret)(token);
+        let reset_token = self
+            .password_reset_token_repository
+            .get(&token_hash)
+            .ok()
+            .filter(|reset_token| reset_token.expire_at > now)
+            .ok_or(ResetPasswordFailReason::InvalidToken)?;
+        let mut user = self
+            .user_repository
+            .get_by_id(&reset_token.user_id)
+            .map_err(|_| ResetPasswordFailReason::InvalidToken)?;
+        let password = self
+            .password_rules
+            .check(new_password, &user.email, &user.username)
+            .map_err(ResetPasswordFailReason::InvalidPassword)?;
+
+        self.password_reset_token_repository
+            .take(&token_hash)
+            .ok()
+            .filter(|reset_token| reset_token.expire_at > now)
+            .ok_or(ResetPasswordFailReason::InvalidToken)?;
+        user.password = self.password_hasher.hash(password.as_str());
+        user.update_at = now;
+        self.user_repository
+            .update(user)
+            .map_err(|_| ResetPasswordFailReason::InvalidToken)?;
+        self.refresh_token_family_repository
+            .revoke_by_subject(reset_token.user_id.as_str());
+
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub enum ResetPasswordFailReason {
+    InvalidToken,
+    InvalidPassword(error::ValidationError),
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::domain::{
+        entity::{PasswordResetToken, RefreshTokenFamily, User},
+        value_object::{EmailAddress, PasswordPolicy, UserId},
+    };
+    use crate::test_support::{
+        application::service::{FakeBreachedPasswordChecker, FakePasswordHasher},
+        domain::repository::{
+            FakePasswordResetTokenRepository, FakeRefreshTokenFamilyRepository, FakeUserRepository,
+        },
+    };
+
+    const USER_ID: &str = "0196e2a4-5c1b-4f7e-9a3d-2b8c6d4e1f00";
+
+    fn fake_hash_secret(secret: &str) -> String {
+        format!("hashed_{}", secret)
+    }
+
+    fn fake_get_timestamp() -> u64 {
+        1747640536
+    }
+
+    fn password_policy() -> PasswordPolicy {
+        PasswordPolicy {
+            min_length: 8,
+            max_length: 64,
+            require_lowercase: false,
+            require_uppercase: false,
+            require_digit: false,
+            require_symbol: false,
+            forbid_common: true,
+        }
+    }
+
+    fn setup_user_repository() -> FakeUserRepository {
+        let mut repo = FakeUserRepository::new();
+        repo.data.insert(
+            USER_ID.to_string(),
+            User {
+                id: UserId::new(USER_ID).unwrap(),
+                email: EmailAddress::new("example@example.com").unwrap(),
+                email_verified: true,
+                pending_email: None,
+                username: "foo".to_string(),
+                password: "old_hashed".to_string(),
+                create_at: 1747636936,
+                update_at: 1747636936,
+            },
+        );
+        repo
+    }
+
+    fn setup_token_repository(expire_at: u64) -> FakePasswordResetTokenRepository {
+        let mut repo = FakePasswordResetTokenRepository::new();
+        repo.data.insert(
+            "hashed_reset_token".to_string(),
+            PasswordResetToken {
+                token_hash: "hashed_reset_token".to_string(),
+                user_id: UserId::new(USER_ID).unwrap(),
+                expire_at,
+            },
+        );
+        repo
+    }
+
+    fn setup_family_repository() -> FakeRefreshTokenFamilyRepository {
+        let mut repo = FakeRefreshTokenFamilyRepository::new();
+        repo.data.insert(
+            "family_id".to_string(),
+            RefreshTokenFamily {
+                id: "family_id".to_string(),
+                subject: USER_ID.to_string(),
+                current_token_id: "token_id".to_string(),
+                revoked: false,
+            },
+        );
+        repo
+    }
+
+    #[test]
+    fn execute_given_valid_token_should_rehash_password_and_revoke_sessions() {
+        let stub_password_hasher = FakePasswordHasher::new("new_hashed");
+        let stub_password_policy = password_policy();
+        let stub_breached_password_checker = FakeBreachedPasswordChecker::new(false);
+        let mut mock_user_repository = setup_user_repository();
+        let mut mock_password_reset_token_repository = setup_token_repository(1747641436);
+        let mut mock_refresh_token_family_repository = setup_family_repository();
+        let reset_password = ResetPasswordUseCase::new(
+            &stub_password_hasher,
+            PasswordRules::new(&stub_password_policy, &stub_breached_password_checker),
+            &mut mock_user_repository,
+            &mut mock_password_reset_token_repository,
+            &mut mock_refresh_token_family_repository,
+            fake_hash_secret,
+            fake_get_timestamp,
+        );
+
+        let result = reset_password.execute("reset_token", "correct horse");
+
+        assert!(result.is_ok());
+        let user = &mock_user_repository.data[USER_ID];
+        assert_eq!(user.password, "new_hashed");
+        assert_eq!(user.update_at, 1747640536);
+        assert!(mock_password_reset_token_repository.data.is_empty());
+        assert!(mock_refresh_token_family_repository.data["family_id"].revoked);
+    }
+
+    #[test]
+    fn execute_given_used_token_should_return_invalid_token() {
+        let stub_password_hasher = FakePasswordHasher::new("new_hashed");
+        let stub_password_policy = password_policy();
+        let stub_breached_password_checker = FakeBreachedPasswordChecker::new(false);
+        let mut mock_user_repository = setup_user_repository();
+        let mut stub_password_reset_token_repository = setup_token_repository(1747641436);
+        let mut stub_refresh_token_family_repository = setup_family_repository();
+        ResetPasswordUseCase::new(
+            &stub_password_hasher,
+            PasswordRules::new(&stub_password_policy, &stub_breached_password_checker),
+            &mut mock_user_repository,
+            &mut stub_password_reset_token_repository,
+            &mut stub_refresh_token_family_repository,
+            fake_hash_secret,
+            fake_get_timestamp,
+        )
+        .execute("reset_token", "correct horse")
+        .expect("should be ok");
+        let stub_password_hasher = FakePasswordHasher::new("another_hashed");
+        let reset_password = ResetPasswordUseCase::new(
+            &stub_password_hasher,
+            PasswordRules::new(&stub_password_policy, &stub_breached_password_checker),
+            &mut mock_user_repository,
+            &mut stub_password_reset_token_repository,
+            &mut stub_refresh_token_family_repository,
+            fake_hash_secret,
+            fake_get_timestamp,
+        );
+
+        let result = reset_password.execute("reset_token", "battery staple");
+
+        assert!(matches!(result, Err(ResetPasswordFailReason::InvalidToken)));
+        assert_eq!(mock_user_repository.data[USER_ID].password, "new_hashed");
+    }
+
+    #[test]
+    fn execute_given_unknown_or_expired_token_should_return_invalid_token() {
+        let test_cases = vec![("unknown_token", 1747641436), ("reset_token", 1747640536)];
+
+        for (token, expire_at) in test_cases {
+            let stub_password_hasher = FakePasswordHasher::new("new_hashed");
+            let stub_password_policy = password_policy();
+            let stub_breached_password_checker = FakeBreachedPasswordChecker::new(false);
+            let mut mock_user_repository = setup_user_repository();
+            let mut stub_password_reset_token_repository = setup_token_repository(expire_at);
+            let mut mock_refresh_token_family_repository = setup_family_repository();
+            let reset_password = ResetPasswordUseCase::new(
+                &stub_password_hasher,
+                PasswordRules::new(&stub_password_policy, &stub_breached_password_checker),
+                &mut mock_user_repository,
+                &mut stub_password_reset_token_repository,
+                &mut mock_refresh_token_family_repository,
+                fake_hash_secret,
+                fake_get_timestamp,
+            );
+
+            let result = reset_password.execute(token, "correct horse");
+
+            assert!(matches!(result, Err(ResetPasswordFailReason::InvalidToken)));
+            assert_eq!(mock_user_repository.data[USER_ID].password, "old_hashed");
+            assert!(!mock_refresh_token_family_repository.data["family_id"].revoked);
+        }
+    }
+
+    #[test]
+    fn execute_given_password_violating_policy_should_keep_token_for_retry() {
+        let test_cases = vec![("short", false), ("correct horse", true)];
+
+        for (password, is_breached) in test_cases {
+            let stub_password_hasher = FakePasswordHasher::new("new_hashed");
+            let stub_password_policy = password_policy();
+            let stub_breached_password_checker = FakeBreachedPasswordChecker::new(is_breached);
+            let mut mock_user_repository = setup_user_repository();
+            let mut mock_password_reset_token_repository = setup_token_repository(1747641436);
+            let mut stub_refresh_token_family_repository = setup_family_repository();
+            let reset_password = ResetPasswordUseCase::new(
+                &stub_password_hasher,
+                PasswordRules::new(&stub_password_policy, &stub_breached_password_checker),
+                &mut mock_user_repository,
+                &mut mock_password_reset_token_repository,
+                &mut stub_refresh_token_family_repository,
+                fake_hash_secret,
+                fake_get_timestamp,
+            );
+
+            let result = reset_password.execute("reset_token", password);
+
+            assert!(matches!(
+                result,
+                Err(ResetPasswordFailReason::InvalidPassword(_))
+            ));
+            assert_eq!(mock_user_repository.data[USER_ID].password, "old_hashed");
+            assert!(
+                mock_password_reset_token_repository
+                    .data
+                    .contains_key("hashed_reset_token")
+            );
+        }
+    }
+}
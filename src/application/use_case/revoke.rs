@@ -0,0 +1,156 @@
+use super::introspect::TokenTypeHint;
+use crate::application::service::auth::{TokenClaims, TokenVerifier};
+use crate::domain::repository::{RefreshTokenFamilyRepository, RevokedTokenRepository};
+
+pub struct RevokeUseCase<'a> {
+    access_token_verifier: &'a dyn TokenVerifier,
+    refresh_token_verifier: &'a dyn TokenVerifier,
+    revoked_token_repository: &'a mut dyn RevokedTokenRepository,
+    refresh_token_family_repository: &'a mut dyn RefreshTokenFamilyRepository,
+}
+
+impl<'a> RevokeUseCase<'a> {
+    pub fn new(
+        access_token_verifier: &'a dyn TokenVerifier,
+        refresh_token_verifier: &'a dyn TokenVerifier,
+        revoked_token_repository: &'a mut dyn RevokedTokenRepository,
+        refresh_token_family_repository: &'a mut dyn RefreshTokenFamilyRepository,
+    ) -> Self {
+        RevokeUseCase {
+            access_token_verifier,
+            refresh_token_verifier,
+            revoked_token_repository,
+            refresh_token_family_repository,
+        }
+    }
+
+    pub fn execute(self, token: &str, token_type_hint: Option<TokenTypeHint>) {
+        let verifiers = match token_type_hint {
+            Some(TokenTypeHint::RefreshToken) => {
+                [self.refresh_token_verifier, self.access_token_verifier]
+            }
+            _ => [self.access_token_verifier, self.refresh_token_verifier],
+        };
+
+        if let Some(claims) = verifiers
+            .iter()
+            .find_map(|verifier| verifier.verify(token).ok())
+        {
+            revoke(
+                claims,
+                self.revoked_token_repository,
+                self.refresh_token_family_repository,
+            );
+        }
+    }
+}
+
+pub(super) fn revoke(
+    claims: TokenClaims,
+    revoked_token_repository: &mut dyn RevokedTokenRepository,
+    refresh_token_family_repository: &mut dyn RefreshTokenFamilyRepository,
+) {
+    if let Some(jti) = claims.jti {
+        revoked_token_repository.revoke(&jti, claims.exp);
+    }
+    if let Some(mut family) = claims
+        .fid
+        .and_then(|fid| refresh_token_family_repository.get(&fid).ok())
+    {
+        family.revoked = true;
+        let _ = refresh_token_family_repository.update(family);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::domain::entity::RefreshTokenFamily;
+    use crate::test_support::{
+        application::service::FakeTokenVerifier,
+        domain::repository::{FakeRefreshTokenFamilyRepository, FakeRevokedTokenRepository},
+    };
+
+    fn claims(fid: Option<&str>) -> TokenClaims {
+        TokenClaims {
+            sub: "foo".to_string(),
+            iss: "example".to_string(),
+            aud: "example".to_string(),
+            iat: 1747636936,
+            exp: 1747640536,
+            jti: Some("token_id".to_string()),
+            fid: fid.map(String::from),
+            email: None,
+            scope: None,
+        }
+    }
+
+    fn setup_family_repository() -> FakeRefreshTokenFamilyRepository {
+        let mut repo = FakeRefreshTokenFamilyRepository::new();
+        repo.data.insert(
+            "family_id".to_string(),
+            RefreshTokenFamily {
+                id: "family_id".to_string(),
+                subject: "foo".to_string(),
+                current_token_id: "token_id".to_string(),
+                revoked: false,
+            },
+        );
+        repo
+    }
+
+    #[test]
+    fn execute_given_access_token_should_revoke_token_id() {
+        let stub_access_token_verifier = FakeTokenVerifier::new(Some(claims(None)));
+        let stub_refresh_token_verifier = FakeTokenVerifier::new(None);
+        let mut mock_revoked_token_repository = FakeRevokedTokenRepository::new();
+        let mut stub_refresh_token_family_repository = setup_family_repository();
+        let revoke = RevokeUseCase::new(
+            &stub_access_token_verifier,
+            &stub_refresh_token_verifier,
+            &mut mock_revoked_token_repository,
+            &mut stub_refresh_token_family_repository,
+        );
+
+        revoke.execute("access_token", None);
+
+        assert_eq!(mock_revoked_token_repository.data["token_id"], 1747640536);
+    }
+
+    #[test]
+    fn execute_given_refresh_token_should_revoke_family() {
+        let stub_access_token_verifier = FakeTokenVerifier::new(None);
+        let stub_refresh_token_verifier = FakeTokenVerifier::new(Some(claims(Some("family_id"))));
+        let mut stub_revoked_token_repository = FakeRevokedTokenRepository::new();
+        let mut mock_refresh_token_family_repository = setup_family_repository();
+        let revoke = RevokeUseCase::new(
+            &stub_access_token_verifier,
+            &stub_refresh_token_verifier,
+            &mut stub_revoked_token_repository,
+            &mut mock_refresh_token_family_repository,
+        );
+
+        revoke.execute("refresh_token", Some(TokenTypeHint::RefreshToken));
+
+        assert!(mock_refresh_token_family_repository.data["family_id"].revoked);
+    }
+
+    #[test]
+    fn execute_given_invalid_token_should_revoke_nothing() {
+        let stub_access_token_verifier = FakeTokenVerifier::new(None);
+        let stub_refresh_token_verifier = FakeTokenVerifier::new(None);
+        let mut mock_revoked_token_repository = FakeRevokedTokenRepository::new();
+        let mut mock_refresh_token_family_repository = setup_family_repository();
+        let revoke = RevokeUseCase::new(
+            &stub_access_token_verifier,
+            &stub_refresh_token_verifier,
+            &mut mock_revoked_token_repository,
+            &mut mock_refresh_token_family_repository,
+        );
+
+        revoke.execute("invalid_token", None);
+
+        assert!(mock_revoked_token_repository.data.is_empty());
+        assert!(!mock_refresh_token_family_repository.data["family_id"].revoked);
+    }
+}
@@ -0,0 +1,230 @@
+use crate::application::service::{
+    auth::{TokenIssuer, TokenSubject},
+    mail::{Mail, MailSender},
+};
+use crate::domain::{
+    entity::User,
+    error,
+    repository::UserRepository,
+    value_object::{EmailAddress, UserId},
+};
+
+pub struct SendEmailVerificationUseCase<'a> {
+    verification_token_issuer: &'a dyn TokenIssuer,
+    mail_sender: &'a dyn MailSender,
+    user_repository: &'a dyn UserRepository,
+    verification_url: &'a str,
+    generate_id: fn() -> String,
+}
+
+impl<'a> SendEmailVerificationUseCase<'a> {
+    pub fn new(
+        verification_token_issuer: &'a dyn TokenIssuer,
+        mail_sender: &'a dyn MailSender,
+        user_repository: &'a dyn UserRepository,
+        verification_url: &'a str,
+        generate_id: fn() -> String,
+    ) -> Self {
+        SendEmailVerificationUseCase {
+            verification_token_issuer,
+            mail_sender,
+            user_repository,
+            verification_url,
+            generate_id,
+        }
+    }
+
+    pub fn execute(&self, email: EmailAddress) -> Result<(), error::MailDeliveryFailed> {
+        match self.user_repository.get(email) {
+            Ok(user) if !user.email_verified => self.send(&user, &user.email),
+            _ => Ok(()),
+        }
+    }
+
+    pub fn execute_for_pending_email(&self, id: &UserId) -> Result<(), error::MailDeliveryFailed> {
+        match self.user_repository.get_by_id(id) {
+            Ok(user) => match &user.pending_email {
+                Some(pending_email) => self.send(&user, pending_email),
+                None => Ok(()),
+            },
+            Err(_) => Ok(()),
+        }
+    }
+
+    fn send(&self, user: &User, email: &EmailAddress) -> Result<(), error::MailDeliveryFailed> {
+        let token = self.verification_token_issuer.issue(&TokenSubject {
+            sub: user.id.as_str().to_string(),
+            jti: Some((self.generate_id)()),
+            fid: None,
+            email: Some(email.as_str().to_string()),
+            scope: None,
+        });
+
+        self.mail_sender.send(&Mail {
+            to: email.as_str().to_string(),
+            subject: "Verify your email address".to_string(),
+            body: format!(
+                "Hi {},\n\nOpen the link below to verify your email address:\n\n{}?token={}\n",
+                user.username, self.verification_url, token
+            ),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_support::{
+        application::service::{FakeMailSender, FakeTokenIssuer},
+        domain::repository::FakeUserRepository,
+    };
+
+    const USER_ID: &str = "0196e2a4-5c1b-4f7e-9a3d-2b8c6d4e1f00";
+
+    fn fake_generate_id() -> String {
+        "token_id".to_string()
+    }
+
+    fn setup_repository(email_verified: bool) -> FakeUserRepository {
+        let mut repo = FakeUserRepository::new();
+        repo.data.insert(
+            USER_ID.to_string(),
+            User {
+                id: UserId::new(USER_ID).unwrap(),
+                email: EmailAddress::new("example@example.com").unwrap(),
+                email_verified,
+                pending_email: None,
+                username: "foo".to_string(),
+                password: "hashed".to_string(),
+                create_at: 1747636936,
+                update_at: 1747636936,
+            },
+        );
+        repo
+    }
+
+    #[test]
+    fn execute_given_unverified_user_should_mail_verification_link() {
+        let stub_user_repository = setup_repository(false);
+        let mock_token_issuer = FakeTokenIssuer::new("verification_token");
+        let mock_mail_sender = FakeMailSender::new(true);
+        let send_email_verification = SendEmailVerificationUseCase::new(
+            &mock_token_issuer,
+            &mock_mail_sender,
+            &stub_user_repository,
+            "https://example.com/verify-email",
+            fake_generate_id,
+        );
+
+        let result =
+            send_email_verification.execute(EmailAddress::new("example@example.com").unwrap());
+
+        assert!(result.is_ok());
+        let sent = mock_mail_sender.sent.borrow();
+        assert_eq!(sent.len(), 1);
+        assert_eq!(sent[0].to, "example@example.com");
+        assert!(
+            sent[0]
+                .body
+                .contains("https://example.com/verify-email?token=verification_token")
+        );
+        let subjects = mock_token_issuer.issued_subjects.borrow();
+        assert_eq!(subjects[0].sub, USER_ID);
+        assert_eq!(subjects[0].jti.as_deref(), Some("token_id"));
+        assert_eq!(subjects[0].email.as_deref(), Some("example@example.com"));
+    }
+
+    #[test]
+    fn execute_given_verified_or_unknown_user_should_send_nothing() {
+        let test_cases = vec![
+            (setup_repository(true), "example@example.com"),
+            (setup_repository(false), "unknown@example.com"),
+        ];
+
+        for (stub_user_repository, email) in test_cases {
+            let stub_token_issuer = FakeTokenIssuer::new("verification_token");
+            let mock_mail_sender = FakeMailSender::new(true);
+            let send_email_verification = SendEmailVerificationUseCase::new(
+                &stub_token_issuer,
+                &mock_mail_sender,
+                &stub_user_repository,
+                "https://example.com/verify-email",
+                fake_generate_id,
+            );
+
+            let result = send_email_verification.execute(EmailAddress::new(email).unwrap());
+
+            assert!(result.is_ok());
+            assert!(mock_mail_sender.sent.borrow().is_empty());
+        }
+    }
+
+    #[test]
+    fn execute_for_pending_email_given_pending_email_should_mail_link_to_pending_address() {
+        let mut stub_user_repository = setup_repository(true);
+        stub_user_repository
+            .data
+            .get_mut(USER_ID)
+            .unwrap()
+            .pending_email = Some(EmailAddress::new("changed@example.com").unwrap());
+        let mock_token_issuer = FakeTokenIssuer::new("verification_token");
+        let mock_mail_sender = FakeMailSender::new(true);
+        let send_email_verification = SendEmailVerificationUseCase::new(
+            &mock_token_issuer,
+            &mock_mail_sender,
+            &stub_user_repository,
+            "https://example.com/verify-email",
+            fake_generate_id,
+        );
+
+        let result =
+            send_email_verification.execute_for_pending_email(&UserId::new(USER_ID).unwrap());
+
+        assert!(result.is_ok());
+        let sent = mock_mail_sender.sent.borrow();
+        assert_eq!(sent.len(), 1);
+        assert_eq!(sent[0].to, "changed@example.com");
+        let subjects = mock_token_issuer.issued_subjects.borrow();
+        assert_eq!(subjects[0].sub, USER_ID);
+        assert_eq!(subjects[0].email.as_deref(), Some("changed@example.com"));
+    }
+
+    #[test]
+    fn execute_for_pending_email_given_no_pending_email_should_send_nothing() {
+        let stub_user_repository = setup_repository(false);
+        let stub_token_issuer = FakeTokenIssuer::new("verification_token");
+        let mock_mail_sender = FakeMailSender::new(true);
+        let send_email_verification = SendEmailVerificationUseCase::new(
+            &stub_token_issuer,
+            &mock_mail_sender,
+            &stub_user_repository,
+            "https://example.com/verify-email",
+            fake_generate_id,
+        );
+
+        let result =
+            send_email_verification.execute_for_pending_email(&UserId::new(USER_ID).unwrap());
+
+        assert!(result.is_ok());
+        assert!(mock_mail_sender.sent.borrow().is_empty());
+    }
+
+    #[test]
+    fn execute_given_mail_failure_should_return_mail_delivery_failed() {
+        let stub_user_repository = setup_repository(false);
+        let stub_token_issuer = FakeTokenIssuer::new("verification_token");
+        let stub_mail_sender = FakeMailSender::new(false);
+        let send_email_verification = SendEmailVerificationUseCase::new(
+            &stub_token_issuer,
+            &stub_mail_sender,
+            &stub_user_repository,
+            "https://example.com/verify-email",
+            fake_generate_id,
+        );
+
+        let result =
+            send_email_verification.execute(EmailAddress::new("example@example.com").unwrap());
+
+        assert!(result.is_err());
+    }
+}
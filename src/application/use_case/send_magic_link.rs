@@ -0,0 +1,155 @@
+use crate::application::service::{
+    auth::{TokenIssuer, TokenSubject},
+    mail::{Mail, MailSender},
+};
+use crate::domain::{error, repository::UserRepository, value_object::EmailAddress};
+
+pub struct SendMagicLinkUseCase<'a> {
+    magic_link_token_issuer: &'a dyn TokenIssuer,
+    mail_sender: &'a dyn MailSender,
+    user_repository: &'a dyn UserRepository,
+    magic_link_url: &'a str,
+    generate_id: fn() -> String,
+}
+
+impl<'a> SendMagicLinkUseCase<'a> {
+    pub fn new(
+        magic_link_token_issuer: &'a dyn TokenIssuer,
+        mail_sender: &'a dyn MailSender,
+        user_repository: &'a dyn UserRepository,
+        magic_link_url: &'a str,
+        generate_id: fn() -> String,
+    ) -> Self {
+        SendMagicLinkUseCase {
+            magic_link_token_issuer,
+            mail_sender,
+            user_repository,
+            magic_link_url,
+            generate_id,
+        }
+    }
+
+    pub fn execute(self, email: EmailAddress) -> Result<(), error::MailDeliveryFailed> {
+        let user = match self.user_repository.get(email) {
+            Ok(user) => user,
+            Err(_) => return Ok(()),
+        };
+        let token = self.magic_link_token_issuer.issue(&TokenSubject {
+            sub: user.id.as_str().to_string(),
+            jti: Some((self.generate_id)()),
+            fid: None,
+            email: Some(user.email.as_str().to_string()),
+            scope: None,
+        });
+
+        self.mail_sender.send(&Mail {
+            to: user.email.as_str().to_string(),
+            subject: "Your sign-in link".to_string(),
+            body: format!(
+                "Hi {},\n\nOpen the link below to sign in. It can be used once and expires shortly:\n\n{}?token={}\n\nIf you did not ask to sign in, you can ignore this email.\n",
+                user.username, self.magic_link_url, token
+            ),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::domain::{entity::User, value_object::UserId};
+    use crate::test_support::{
+        application::service::{FakeMailSender, FakeTokenIssuer},
+        domain::repository::FakeUserRepository,
+    };
+
+    const USER_ID: &str = "0196e2a4-5c1b-4f7e-9a3d-2b8c6d4e1f00";
+
+    fn fake_generate_id() -> String {
+        "token_id".to_string()
+    }
+
+    fn setup_repository() -> FakeUserRepository {
+        let mut repo = FakeUserRepository::new();
+        repo.data.insert(
+            USER_ID.to_string(),
+            User {
+                id: UserId::new(USER_ID).unwrap(),
+                email: EmailAddress::new("example@example.com").unwrap(),
+                email_verified: true,
+                pending_email: None,
+                username: "foo".to_string(),
+                password: "hashed".to_string(),
+                create_at: 1747636936,
+                update_at: 1747636936,
+            },
+        );
+        repo
+    }
+
+    #[test]
+    fn execute_given_registered_email_should_mail_signin_link() {
+        let stub_user_repository = setup_repository();
+        let mock_token_issuer = FakeTokenIssuer::new("magic_link_token");
+        let mock_mail_sender = FakeMailSender::new(true);
+        let send_magic_link = SendMagicLinkUseCase::new(
+            &mock_token_issuer,
+            &mock_mail_sender,
+            &stub_user_repository,
+            "https://example.com/signin/magic-link/callback",
+            fake_generate_id,
+        );
+
+        let result = send_magic_link.execute(EmailAddress::new("example@example.com").unwrap());
+
+        assert!(result.is_ok());
+        let sent = mock_mail_sender.sent.borrow();
+        assert_eq!(sent.len(), 1);
+        assert_eq!(sent[0].to, "example@example.com");
+        assert!(
+            sent[0]
+                .body
+                .contains("https://example.com/signin/magic-link/callback?token=magic_link_token")
+        );
+        let subjects = mock_token_issuer.issued_subjects.borrow();
+        assert_eq!(subjects[0].sub, USER_ID);
+        assert_eq!(subjects[0].jti.as_deref(), Some("token_id"));
+        assert_eq!(subjects[0].email.as_deref(), Some("example@example.com"));
+    }
+
+    #[test]
+    fn execute_given_unknown_email_should_send_nothing() {
+        let stub_user_repository = setup_repository();
+        let stub_token_issuer = FakeTokenIssuer::new("magic_link_token");
+        let mock_mail_sender = FakeMailSender::new(true);
+        let send_magic_link = SendMagicLinkUseCase::new(
+            &stub_token_issuer,
+            &mock_mail_sender,
+            &stub_user_repository,
+            "https://example.com/signin/magic-link/callback",
+            fake_generate_id,
+        );
+
+        let result = send_magic_link.execute(EmailAddress::new("unknown@example.com").unwrap());
+
+        assert!(result.is_ok());
+        assert!(mock_mail_sender.sent.borrow().is_empty());
+    }
+
+    #[test]
+    fn execute_given_mail_failure_should_return_mail_delivery_failed() {
+        let stub_user_repository = setup_repository();
+        let stub_token_issuer = FakeTokenIssuer::new("magic_link_token");
+        let stub_mail_sender = FakeMailSender::new(false);
+        let send_magic_link = SendMagicLinkUseCase::new(
+            &stub_token_issuer,
+            &stub_mail_sender,
+            &stub_user_repository,
+            "https://example.com/signin/magic-link/callback",
+            fake_generate_id,
+        );
+
+        let result = send_magic_link.execute(EmailAddress::new("example@example.com").unwrap());
+
+        assert!(result.is_err());
+    }
+}
@@ -1,49 +1,268 @@
-use crate::application::service::{auth::PasswordValidator, auth::TokenIssuer};
-use crate::domain::{repository::UserRepository, value_object::EmailAddress};
+use crate::application::service::auth::{
+    PasswordHasher, PasswordValidator, TokenIssuer, TokenSubject,
+};
+use crate::domain::{
+    entity::{RefreshTokenFamily, User},
+    repository::{
+        LoginAttemptRepository, RefreshTokenFamilyRepository, TotpCredentialRepository,
+        UserRepository,
+    },
+    value_object::{EmailAddress, LockoutRule},
+};
 
 pub struct SignInUseCase<'a> {
-    password_validator: &'a dyn PasswordValidator,
-    access_token_issuer: &'a dyn TokenIssuer,
-    refresh_token_issuer: &'a dyn TokenIssuer,
-    user_repository: &'a dyn UserRepository,
+    password_authenticator: PasswordAuthenticator<'a>,
+    session_issuer: SessionIssuer<'a>,
+    mfa_token_issuer: &'a dyn TokenIssuer,
+    login_throttle: LoginThrottle<'a>,
+    totp_credential_repository: &'a dyn TotpCredentialRepository,
+    require_verified_email: bool,
+    get_timestamp: fn() -> u64,
 }
 
 impl<'a> SignInUseCase<'a> {
     pub fn new(
-        password_validator: &'a dyn PasswordValidator,
-        access_token_issuer: &'a dyn TokenIssuer,
-        refresh_token_issuer: &'a dyn TokenIssuer,
-        user_repository: &'a dyn UserRepository,
+        password_authenticator: PasswordAuthenticator<'a>,
+        session_issuer: SessionIssuer<'a>,
+        mfa_token_issuer: &'a dyn TokenIssuer,
+        login_throttle: LoginThrottle<'a>,
+        totp_credential_repository: &'a dyn TotpCredentialRepository,
+        require_verified_email: bool,
+        get_timestamp: fn() -> u64,
     ) -> Self {
         SignInUseCase {
+            password_authenticator,
+            session_issuer,
+            mfa_token_issuer,
+            login_throttle,
+            totp_credential_repository,
+            require_verified_email,
+            get_timestamp,
+        }
+    }
+
+    pub fn execute(
+        mut self,
+        email: EmailAddress,
+        password: &str,
+        client_ip: &str,
+    ) -> Result<SignInResult, FailReason> {
+        let now = (self.get_timestamp)();
+        let email_key = format!("email:{}", email.as_str());
+        let ip_key = format!("ip:{}", client_ip);
+        if let Some(blocked_until) = self
+            .login_throttle
+            .locked_until(&[&email_key, &ip_key], now)
+        {
+            return Err(FailReason::AccountLocked {
+                retry_after: blocked_until - now,
+            });
+        }
+
+        let user = match self.password_authenticator.authenticate(email, password) {
+            Ok(user) => user,
+            Err(reason) => {
+                let policy = self.login_throttle.policy();
+                self.login_throttle
+                    .record_failure(&email_key, policy.max_failures, now);
+                self.login_throttle
+                    .record_failure(&ip_key, policy.ip_max_failures, now);
+                return Err(reason);
+            }
+        };
+        self.login_throttle.reset(&email_key);
+        if self.require_verified_email && !user.email_verified {
+            return Err(FailReason::EmailNotVerified);
+        }
+        if self
+            .totp_credential_repository
+            .get(&user.id)
+            .is_ok_and(|credential| credential.confirmed)
+        {
+            return Err(FailReason::MfaRequired {
+                mfa_token: self.mfa_token_issuer.issue(&TokenSubject {
+                    sub: user.id.as_str().to_string(),
+                    jti: Some(self.session_issuer.generate_id()),
+                    ..Default::default()
+                }),
+            });
+        }
+
+        Ok(self.session_issuer.issue(user))
+    }
+}
+
+pub struct PasswordAuthenticator<'a> {
+    password_hasher: &'a dyn PasswordHasher,
+    password_validator: &'a dyn PasswordValidator,
+    user_repository: &'a mut dyn UserRepository,
+}
+
+impl<'a> PasswordAuthenticator<'a> {
+    pub fn new(
+        password_hasher: &'a dyn PasswordHasher,
+        password_validator: &'a dyn PasswordValidator,
+        user_repository: &'a mut dyn UserRepository,
+    ) -> Self {
+        PasswordAuthenticator {
+            password_hasher,
             password_validator,
-            access_token_issuer,
-            refresh_token_issuer,
             user_repository,
         }
     }
 
-    pub fn execute(self, email: EmailAddress, password: &str) -> Result<SignInResult, FailReason> {
-        let user = match self.user_repository.get(email) {
+    pub(super) fn authenticate(
+        &mut self,
+        email: EmailAddress,
+        password: &str,
+    ) -> Result<User, FailReason> {
+        let mut user = match self.user_repository.get(email) {
             Ok(u) => u,
-            Err(_) => return Err(FailReason::UserNotExist),
+            Err(_) => {
+                self.password_validator
+                    .verify(password, self.password_hasher.dummy_hash());
+                return Err(FailReason::UserNotExist);
+            }
         };
         if !self.password_validator.verify(password, &user.password) {
             return Err(FailReason::InvalidPassowrd);
         }
+        if self.password_hasher.needs_rehash(&user.password) {
+            let password_hash = self.password_hasher.hash(password);
+            if self.user_repository.update_password_if_unchanged(
+                &user.id,
+                &user.password,
+                &password_hash,
+            ) {
+                user.password = password_hash;
+            } else {
+                log::warn!(
+                    "failed to store rehashed password for user {}",
+                    user.id.as_str()
+                );
+            }
+        }
+
+        Ok(user)
+    }
+}
+
+pub struct SessionIssuer<'a> {
+    access_token_issuer: &'a dyn TokenIssuer,
+    refresh_token_issuer: &'a dyn TokenIssuer,
+    refresh_token_family_repository: &'a mut dyn RefreshTokenFamilyRepository,
+    generate_id: fn() -> String,
+}
+
+impl<'a> SessionIssuer<'a> {
+    pub fn new(
+        access_token_issuer: &'a dyn TokenIssuer,
+        refresh_token_issuer: &'a dyn TokenIssuer,
+        refresh_token_family_repository: &'a mut dyn RefreshTokenFamilyRepository,
+        generate_id: fn() -> String,
+    ) -> Self {
+        SessionIssuer {
+            access_token_issuer,
+            refresh_token_issuer,
+            refresh_token_family_repository,
+            generate_id,
+        }
+    }
 
-        Ok(SignInResult {
-            access_token: self.access_token_issuer.issue(&user.username),
-            refresh_token: self.refresh_token_issuer.issue(&user.username),
+    pub(super) fn generate_id(&self) -> String {
+        (self.generate_id)()
+    }
+
+    pub(super) fn issue(self, user: User) -> SignInResult {
+        let family = RefreshTokenFamily {
+            id: self.generate_id(),
+            subject: user.id.as_str().to_string(),
+            current_token_id: self.generate_id(),
+            revoked: false,
+        };
+        let refresh_token = self.refresh_token_issuer.issue(&TokenSubject {
+            sub: family.subject.clone(),
+            jti: Some(family.current_token_id.clone()),
+            fid: Some(family.id.clone()),
+            email: None,
+            scope: None,
+        });
+        self.refresh_token_family_repository
+            .create(family)
+            .expect("generated family id should be unique");
+
+        SignInResult {
+            access_token: self.access_token_issuer.issue(&TokenSubject {
+                sub: user.id.as_str().to_string(),
+                jti: Some(self.generate_id()),
+                fid: None,
+                email: None,
+                scope: None,
+            }),
+            refresh_token,
+            id: user.id.as_str().to_string(),
             username: user.username,
             email: user.email.as_str().to_string(),
-        })
+        }
     }
 }
 
+pub struct LoginThrottle<'a> {
+    login_attempt_repository: &'a mut dyn LoginAttemptRepository,
+    policy: &'a LoginThrottlePolicy,
+}
+
+impl<'a> LoginThrottle<'a> {
+    pub fn new(
+        login_attempt_repository: &'a mut dyn LoginAttemptRepository,
+        policy: &'a LoginThrottlePolicy,
+    ) -> Self {
+        LoginThrottle {
+            login_attempt_repository,
+            policy,
+        }
+    }
+
+    pub(super) fn policy(&self) -> &'a LoginThrottlePolicy {
+        self.policy
+    }
+
+    pub(super) fn locked_until(&self, keys: &[&str], now: u64) -> Option<u64> {
+        keys.iter()
+            .filter_map(|key| self.login_attempt_repository.locked_until(key, now))
+            .max()
+    }
+
+    pub(super) fn record_failure(&mut self, key: &str, max_failures: u32, now: u64) {
+        self.login_attempt_repository.record_failure(
+            key,
+            &LockoutRule {
+                max_failures,
+                backoff_base_seconds: self.policy.backoff_base_seconds,
+                backoff_max_seconds: self.policy.backoff_max_seconds,
+                lockout_seconds: self.policy.lockout_seconds,
+            },
+            now,
+        );
+    }
+
+    pub(super) fn reset(&mut self, key: &str) {
+        self.login_attempt_repository.delete(key);
+    }
+}
+
+pub struct LoginThrottlePolicy {
+    pub max_failures: u32,
+    pub ip_max_failures: u32,
+    pub backoff_base_seconds: u64,
+    pub backoff_max_seconds: u64,
+    pub lockout_seconds: u64,
+}
+
 pub struct SignInResult {
     pub access_token: String,
     pub refresh_token: String,
+    pub id: String,
     pub username: String,
     pub email: String,
 }
@@ -51,23 +270,53 @@ pub struct SignInResult {
 pub enum FailReason {
     UserNotExist,
     InvalidPassowrd,
+    AccountLocked { retry_after: u64 },
+    EmailNotVerified,
+    MfaRequired { mfa_token: String },
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::domain::entity::User;
+    use crate::domain::{
+        entity::{LoginAttempt, TotpCredential},
+        value_object::UserId,
+    };
     use crate::test_support::{
-        application::service::{FakePasswordValidator, FakeTokenIssuer},
-        domain::repository::FakeUserRepository,
+        application::service::{FakePasswordHasher, FakePasswordValidator, FakeTokenIssuer},
+        domain::repository::{
+            FakeLoginAttemptRepository, FakeRefreshTokenFamilyRepository,
+            FakeTotpCredentialRepository, FakeUserRepository,
+        },
     };
 
+    fn fake_generate_id() -> String {
+        "generated_id".to_string()
+    }
+
+    fn fake_get_timestamp() -> u64 {
+        1747636936
+    }
+
+    fn login_throttle_policy() -> LoginThrottlePolicy {
+        LoginThrottlePolicy {
+            max_failures: 3,
+            ip_max_failures: 10,
+            backoff_base_seconds: 1,
+            backoff_max_seconds: 60,
+            lockout_seconds: 900,
+        }
+    }
+
     fn setup_repository() -> FakeUserRepository {
         let mut repo = FakeUserRepository::new();
         repo.data.insert(
-            "example@example.com".to_string(),
+            "0196e2a4-5c1b-4f7e-9a3d-2b8c6d4e1f00".to_string(),
             User {
+                id: UserId::new("0196e2a4-5c1b-4f7e-9a3d-2b8c6d4e1f00").unwrap(),
                 email: EmailAddress::new("example@example.com").unwrap(),
+                email_verified: false,
+                pending_email: None,
                 username: "foo".to_string(),
                 password: "bar".to_string(),
                 create_at: 1747636936,
@@ -81,42 +330,89 @@ mod test {
     fn execute_given_valid_user_data_should_return_signin_result() {
         let stub_access_token_issuer = FakeTokenIssuer::new("access_token");
         let stub_refresh_token_issuer = FakeTokenIssuer::new("refresh_token");
+        let stub_mfa_token_issuer = FakeTokenIssuer::new("mfa_token");
+        let stub_password_hasher = FakePasswordHasher::new("rehashed");
         let stub_password_validator = FakePasswordValidator::new(true);
         let mut mock_user_repository = setup_repository();
+        let mut mock_refresh_token_family_repository = FakeRefreshTokenFamilyRepository::new();
+        let mut stub_login_attempt_repository = FakeLoginAttemptRepository::new();
+        let stub_totp_credential_repository = FakeTotpCredentialRepository::new();
+        let stub_login_throttle_policy = login_throttle_policy();
         let sign_in = SignInUseCase::new(
-            &stub_password_validator,
-            &stub_access_token_issuer,
-            &stub_refresh_token_issuer,
-            &mut mock_user_repository,
+            PasswordAuthenticator::new(
+                &stub_password_hasher,
+                &stub_password_validator,
+                &mut mock_user_repository,
+            ),
+            SessionIssuer::new(
+                &stub_access_token_issuer,
+                &stub_refresh_token_issuer,
+                &mut mock_refresh_token_family_repository,
+                fake_generate_id,
+            ),
+            &stub_mfa_token_issuer,
+            LoginThrottle::new(
+                &mut stub_login_attempt_repository,
+                &stub_login_throttle_policy,
+            ),
+            &stub_totp_credential_repository,
+            false,
+            fake_get_timestamp,
         );
 
         let result = sign_in.execute(
             EmailAddress::new("example@example.com").unwrap(),
             "password",
+            "127.0.0.1",
         );
 
-        assert!(result.is_ok_and(|r| r.email == "example@example.com"
-            && r.username == "foo"
-            && r.access_token == "access_token"
-            && r.refresh_token == "refresh_token"));
+        assert!(
+            result.is_ok_and(|r| r.id == "0196e2a4-5c1b-4f7e-9a3d-2b8c6d4e1f00"
+                && r.email == "example@example.com"
+                && r.username == "foo"
+                && r.access_token == "access_token"
+                && r.refresh_token == "refresh_token")
+        );
     }
 
     #[test]
     fn execute_given_not_exist_user_should_return_entity_not_exist() {
         let stub_access_token_issuer = FakeTokenIssuer::new("access_token");
         let stub_refresh_token_issuer = FakeTokenIssuer::new("refresh_token");
+        let stub_mfa_token_issuer = FakeTokenIssuer::new("mfa_token");
+        let stub_password_hasher = FakePasswordHasher::new("rehashed");
         let stub_password_validator = FakePasswordValidator::new(true);
         let mut mock_user_repository = setup_repository();
+        let mut mock_refresh_token_family_repository = FakeRefreshTokenFamilyRepository::new();
+        let mut stub_login_attempt_repository = FakeLoginAttemptRepository::new();
+        let stub_totp_credential_repository = FakeTotpCredentialRepository::new();
+        let stub_login_throttle_policy = login_throttle_policy();
         let sign_in = SignInUseCase::new(
-            &stub_password_validator,
-            &stub_access_token_issuer,
-            &stub_refresh_token_issuer,
-            &mut mock_user_repository,
+            PasswordAuthenticator::new(
+                &stub_password_hasher,
+                &stub_password_validator,
+                &mut mock_user_repository,
+            ),
+            SessionIssuer::new(
+                &stub_access_token_issuer,
+                &stub_refresh_token_issuer,
+                &mut mock_refresh_token_family_repository,
+                fake_generate_id,
+            ),
+            &stub_mfa_token_issuer,
+            LoginThrottle::new(
+                &mut stub_login_attempt_repository,
+                &stub_login_throttle_policy,
+            ),
+            &stub_totp_credential_repository,
+            false,
+            fake_get_timestamp,
         );
 
         let result = sign_in.execute(
             EmailAddress::new("not_exist@example.com").unwrap(),
             "password",
+            "127.0.0.1",
         );
 
         assert!(result.is_err_and(|err| matches!(err, FailReason::UserNotExist)));
@@ -126,20 +422,591 @@ mod test {
     fn execute_given_invalid_password_should_return_entity_not_exist() {
         let stub_access_token_issuer = FakeTokenIssuer::new("access_token");
         let stub_refresh_token_issuer = FakeTokenIssuer::new("refresh_token");
+        let stub_mfa_token_issuer = FakeTokenIssuer::new("mfa_token");
+        let stub_password_hasher = FakePasswordHasher::new("rehashed");
         let mock_password_validator = FakePasswordValidator::new(false);
         let mut stub_user_repository = setup_repository();
+        let mut mock_refresh_token_family_repository = FakeRefreshTokenFamilyRepository::new();
+        let mut stub_login_attempt_repository = FakeLoginAttemptRepository::new();
+        let stub_totp_credential_repository = FakeTotpCredentialRepository::new();
+        let stub_login_throttle_policy = login_throttle_policy();
         let sign_in = SignInUseCase::new(
-            &mock_password_validator,
-            &stub_access_token_issuer,
-            &stub_refresh_token_issuer,
-            &mut stub_user_repository,
+            PasswordAuthenticator::new(
+                &stub_password_hasher,
+                &mock_password_validator,
+                &mut stub_user_repository,
+            ),
+            SessionIssuer::new(
+                &stub_access_token_issuer,
+                &stub_refresh_token_issuer,
+                &mut mock_refresh_token_family_repository,
+                fake_generate_id,
+            ),
+            &stub_mfa_token_issuer,
+            LoginThrottle::new(
+                &mut stub_login_attempt_repository,
+                &stub_login_throttle_policy,
+            ),
+            &stub_totp_credential_repository,
+            false,
+            fake_get_timestamp,
         );
 
         let result = sign_in.execute(
             EmailAddress::new("example@example.com").unwrap(),
             "password",
+            "127.0.0.1",
         );
 
         assert!(result.is_err_and(|err| matches!(err, FailReason::InvalidPassowrd)));
     }
+
+    #[test]
+    fn execute_given_valid_user_data_should_start_refresh_token_family() {
+        let stub_access_token_issuer = FakeTokenIssuer::new("access_token");
+        let stub_refresh_token_issuer = FakeTokenIssuer::new("refresh_token");
+        let stub_mfa_token_issuer = FakeTokenIssuer::new("mfa_token");
+        let stub_password_hasher = FakePasswordHasher::new("rehashed");
+        let stub_password_validator = FakePasswordValidator::new(true);
+        let mut stub_user_repository = setup_repository();
+        let mut mock_refresh_token_family_repository = FakeRefreshTokenFamilyRepository::new();
+        let mut stub_login_attempt_repository = FakeLoginAttemptRepository::new();
+        let stub_totp_credential_repository = FakeTotpCredentialRepository::new();
+        let stub_login_throttle_policy = login_throttle_policy();
+        let sign_in = SignInUseCase::new(
+            PasswordAuthenticator::new(
+                &stub_password_hasher,
+                &stub_password_validator,
+                &mut stub_user_repository,
+            ),
+            SessionIssuer::new(
+                &stub_access_token_issuer,
+                &stub_refresh_token_issuer,
+                &mut mock_refresh_token_family_repository,
+                fake_generate_id,
+            ),
+            &stub_mfa_token_issuer,
+            LoginThrottle::new(
+                &mut stub_login_attempt_repository,
+                &stub_login_throttle_policy,
+            ),
+            &stub_totp_credential_repository,
+            false,
+            fake_get_timestamp,
+        );
+
+        let _ = sign_in.execute(
+            EmailAddress::new("example@example.com").unwrap(),
+            "password",
+            "127.0.0.1",
+        );
+
+        assert!(
+            mock_refresh_token_family_repository
+                .data
+                .get("generated_id")
+                .is_some_and(|f| f.subject == "0196e2a4-5c1b-4f7e-9a3d-2b8c6d4e1f00" && !f.revoked)
+        );
+    }
+
+    #[test]
+    fn execute_given_outdated_password_hash_should_persist_rehashed_password() {
+        let stub_access_token_issuer = FakeTokenIssuer::new("access_token");
+        let stub_refresh_token_issuer = FakeTokenIssuer::new("refresh_token");
+        let stub_mfa_token_issuer = FakeTokenIssuer::new("mfa_token");
+        let stub_password_hasher = FakePasswordHasher::outdated("rehashed");
+        let stub_password_validator = FakePasswordValidator::new(true);
+        let mut mock_user_repository = setup_repository();
+        let mut mock_refresh_token_family_repository = FakeRefreshTokenFamilyRepository::new();
+        let mut stub_login_attempt_repository = FakeLoginAttemptRepository::new();
+        let stub_totp_credential_repository = FakeTotpCredentialRepository::new();
+        let stub_login_throttle_policy = login_throttle_policy();
+        let sign_in = SignInUseCase::new(
+            PasswordAuthenticator::new(
+                &stub_password_hasher,
+                &stub_password_validator,
+                &mut mock_user_repository,
+            ),
+            SessionIssuer::new(
+                &stub_access_token_issuer,
+                &stub_refresh_token_issuer,
+                &mut mock_refresh_token_family_repository,
+                fake_generate_id,
+            ),
+            &stub_mfa_token_issuer,
+            LoginThrottle::new(
+                &mut stub_login_attempt_repository,
+                &stub_login_throttle_policy,
+            ),
+            &stub_totp_credential_repository,
+            false,
+            fake_get_timestamp,
+        );
+
+        let result = sign_in.execute(
+            EmailAddress::new("example@example.com").unwrap(),
+            "password",
+            "127.0.0.1",
+        );
+
+        assert!(result.is_ok());
+        assert!(
+            mock_user_repository
+                .data
+                .get("0196e2a4-5c1b-4f7e-9a3d-2b8c6d4e1f00")
+                .is_some_and(|u| u.password == "rehashed")
+        );
+    }
+
+    #[test]
+    fn execute_given_current_password_hash_should_keep_password() {
+        let stub_access_token_issuer = FakeTokenIssuer::new("access_token");
+        let stub_refresh_token_issuer = FakeTokenIssuer::new("refresh_token");
+        let stub_mfa_token_issuer = FakeTokenIssuer::new("mfa_token");
+        let stub_password_hasher = FakePasswordHasher::new("rehashed");
+        let stub_password_validator = FakePasswordValidator::new(true);
+        let mut mock_user_repository = setup_repository();
+        let mut mock_refresh_token_family_repository = FakeRefreshTokenFamilyRepository::new();
+        let mut stub_login_attempt_repository = FakeLoginAttemptRepository::new();
+        let stub_totp_credential_repository = FakeTotpCredentialRepository::new();
+        let stub_login_throttle_policy = login_throttle_policy();
+        let sign_in = SignInUseCase::new(
+            PasswordAuthenticator::new(
+                &stub_password_hasher,
+                &stub_password_validator,
+                &mut mock_user_repository,
+            ),
+            SessionIssuer::new(
+                &stub_access_token_issuer,
+                &stub_refresh_token_issuer,
+                &mut mock_refresh_token_family_repository,
+                fake_generate_id,
+            ),
+            &stub_mfa_token_issuer,
+            LoginThrottle::new(
+                &mut stub_login_attempt_repository,
+                &stub_login_throttle_policy,
+            ),
+            &stub_totp_credential_repository,
+            false,
+            fake_get_timestamp,
+        );
+
+        let _ = sign_in.execute(
+            EmailAddress::new("example@example.com").unwrap(),
+            "password",
+            "127.0.0.1",
+        );
+
+        assert!(
+            mock_user_repository
+                .data
+                .get("0196e2a4-5c1b-4f7e-9a3d-2b8c6d4e1f00")
+                .is_some_and(|u| u.password == "bar")
+        );
+    }
+
+    #[test]
+    fn execute_given_any_failure_should_invoke_password_validator() {
+        let test_cases = vec![
+            ("not_exist@example.com", true, "dummy_hash"),
+            ("example@example.com", false, "bar"),
+        ];
+
+        for (email, is_valid, verified_hash) in test_cases {
+            let stub_access_token_issuer = FakeTokenIssuer::new("access_token");
+            let stub_refresh_token_issuer = FakeTokenIssuer::new("refresh_token");
+            let stub_mfa_token_issuer = FakeTokenIssuer::new("mfa_token");
+            let stub_password_hasher = FakePasswordHasher::new("rehashed");
+            let mock_password_validator = FakePasswordValidator::new(is_valid);
+            let mut stub_user_repository = setup_repository();
+            let mut stub_refresh_token_family_repository = FakeRefreshTokenFamilyRepository::new();
+            let mut stub_login_attempt_repository = FakeLoginAttemptRepository::new();
+            let stub_totp_credential_repository = FakeTotpCredentialRepository::new();
+            let stub_login_throttle_policy = login_throttle_policy();
+            let sign_in = SignInUseCase::new(
+                PasswordAuthenticator::new(
+                    &stub_password_hasher,
+                    &mock_password_validator,
+                    &mut stub_user_repository,
+                ),
+                SessionIssuer::new(
+                    &stub_access_token_issuer,
+                    &stub_refresh_token_issuer,
+                    &mut stub_refresh_token_family_repository,
+                    fake_generate_id,
+                ),
+                &stub_mfa_token_issuer,
+                LoginThrottle::new(
+                    &mut stub_login_attempt_repository,
+                    &stub_login_throttle_policy,
+                ),
+                &stub_totp_credential_repository,
+                false,
+                fake_get_timestamp,
+            );
+
+            let result =
+                sign_in.execute(EmailAddress::new(email).unwrap(), "password", "127.0.0.1");
+
+            assert!(result.is_err());
+            assert_eq!(
+                *mock_password_validator.verified_hashes.borrow(),
+                vec![verified_hash]
+            );
+        }
+    }
+
+    fn create_attempt(key: &str, failures: u32, blocked_until: u64) -> LoginAttempt {
+        LoginAttempt {
+            key: key.to_string(),
+            failures,
+            blocked_until,
+            expire_at: blocked_until + 900,
+        }
+    }
+
+    #[test]
+    fn execute_given_invalid_password_should_back_off_email_and_ip() {
+        let stub_access_token_issuer = FakeTokenIssuer::new("access_token");
+        let stub_refresh_token_issuer = FakeTokenIssuer::new("refresh_token");
+        let stub_mfa_token_issuer = FakeTokenIssuer::new("mfa_token");
+        let stub_password_hasher = FakePasswordHasher::new("rehashed");
+        let stub_password_validator = FakePasswordValidator::new(false);
+        let mut stub_user_repository = setup_repository();
+        let mut stub_refresh_token_family_repository = FakeRefreshTokenFamilyRepository::new();
+        let mut mock_login_attempt_repository = FakeLoginAttemptRepository::new();
+        let stub_totp_credential_repository = FakeTotpCredentialRepository::new();
+        mock_login_attempt_repository.data.insert(
+            "email:example@example.com".to_string(),
+            create_attempt("email:example@example.com", 1, 1747636930),
+        );
+        let stub_login_throttle_policy = login_throttle_policy();
+        let sign_in = SignInUseCase::new(
+            PasswordAuthenticator::new(
+                &stub_password_hasher,
+                &stub_password_validator,
+                &mut stub_user_repository,
+            ),
+            SessionIssuer::new(
+                &stub_access_token_issuer,
+                &stub_refresh_token_issuer,
+                &mut stub_refresh_token_family_repository,
+                fake_generate_id,
+            ),
+            &stub_mfa_token_issuer,
+            LoginThrottle::new(
+                &mut mock_login_attempt_repository,
+                &stub_login_throttle_policy,
+            ),
+            &stub_totp_credential_repository,
+            false,
+            fake_get_timestamp,
+        );
+
+        let _ = sign_in.execute(
+            EmailAddress::new("example@example.com").unwrap(),
+            "password",
+            "127.0.0.1",
+        );
+
+        assert!(
+            mock_login_attempt_repository
+                .data
+                .get("email:example@example.com")
+                .is_some_and(|a| a.failures == 2 && a.blocked_until == 1747636938)
+        );
+        assert!(
+            mock_login_attempt_repository
+                .data
+                .get("ip:127.0.0.1")
+                .is_some_and(|a| a.failures == 1 && a.blocked_until == 1747636937)
+        );
+    }
+
+    #[test]
+    fn execute_given_max_failures_reached_should_lock_account() {
+        let stub_access_token_issuer = FakeTokenIssuer::new("access_token");
+        let stub_refresh_token_issuer = FakeTokenIssuer::new("refresh_token");
+        let stub_mfa_token_issuer = FakeTokenIssuer::new("mfa_token");
+        let stub_password_hasher = FakePasswordHasher::new("rehashed");
+        let stub_password_validator = FakePasswordValidator::new(false);
+        let mut stub_user_repository = setup_repository();
+        let mut stub_refresh_token_family_repository = FakeRefreshTokenFamilyRepository::new();
+        let mut mock_login_attempt_repository = FakeLoginAttemptRepository::new();
+        let stub_totp_credential_repository = FakeTotpCredentialRepository::new();
+        mock_login_attempt_repository.data.insert(
+            "email:not_exist@example.com".to_string(),
+            create_attempt("email:not_exist@example.com", 2, 1747636930),
+        );
+        let stub_login_throttle_policy = login_throttle_policy();
+        let sign_in = SignInUseCase::new(
+            PasswordAuthenticator::new(
+                &stub_password_hasher,
+                &stub_password_validator,
+                &mut stub_user_repository,
+            ),
+            SessionIssuer::new(
+                &stub_access_token_issuer,
+                &stub_refresh_token_issuer,
+                &mut stub_refresh_token_family_repository,
+                fake_generate_id,
+            ),
+            &stub_mfa_token_issuer,
+            LoginThrottle::new(
+                &mut mock_login_attempt_repository,
+                &stub_login_throttle_policy,
+            ),
+            &stub_totp_credential_repository,
+            false,
+            fake_get_timestamp,
+        );
+
+        let result = sign_in.execute(
+            EmailAddress::new("not_exist@example.com").unwrap(),
+            "password",
+            "127.0.0.1",
+        );
+
+        assert!(result.is_err_and(|err| matches!(err, FailReason::UserNotExist)));
+        assert!(
+            mock_login_attempt_repository
+                .data
+                .get("email:not_exist@example.com")
+                .is_some_and(|a| a.failures == 3 && a.blocked_until == 1747637836)
+        );
+    }
+
+    #[test]
+    fn execute_given_blocked_email_or_ip_should_return_account_locked_without_verifying() {
+        let test_cases = vec!["email:example@example.com", "ip:127.0.0.1"];
+
+        for blocked_key in test_cases {
+            let stub_access_token_issuer = FakeTokenIssuer::new("access_token");
+            let stub_refresh_token_issuer = FakeTokenIssuer::new("refresh_token");
+            let stub_mfa_token_issuer = FakeTokenIssuer::new("mfa_token");
+            let stub_password_hasher = FakePasswordHasher::new("rehashed");
+            let mock_password_validator = FakePasswordValidator::new(true);
+            let mut stub_user_repository = setup_repository();
+            let mut stub_refresh_token_family_repository = FakeRefreshTokenFamilyRepository::new();
+            let mut stub_login_attempt_repository = FakeLoginAttemptRepository::new();
+            let stub_totp_credential_repository = FakeTotpCredentialRepository::new();
+            stub_login_attempt_repository.data.insert(
+                blocked_key.to_string(),
+                create_attempt(blocked_key, 3, 1747637000),
+            );
+            let stub_login_throttle_policy = login_throttle_policy();
+            let sign_in = SignInUseCase::new(
+                PasswordAuthenticator::new(
+                    &stub_password_hasher,
+                    &mock_password_validator,
+                    &mut stub_user_repository,
+                ),
+                SessionIssuer::new(
+                    &stub_access_token_issuer,
+                    &stub_refresh_token_issuer,
+                    &mut stub_refresh_token_family_repository,
+                    fake_generate_id,
+                ),
+                &stub_mfa_token_issuer,
+                LoginThrottle::new(
+                    &mut stub_login_attempt_repository,
+                    &stub_login_throttle_policy,
+                ),
+                &stub_totp_credential_repository,
+                false,
+                fake_get_timestamp,
+            );
+
+            let result = sign_in.execute(
+                EmailAddress::new("example@example.com").unwrap(),
+                "password",
+                "127.0.0.1",
+            );
+
+            assert!(result.is_err_and(
+                |err| matches!(err, FailReason::AccountLocked { retry_after } if retry_after == 64)
+            ));
+            assert!(mock_password_validator.verified_hashes.borrow().is_empty());
+        }
+    }
+
+    #[test]
+    fn execute_given_valid_user_data_should_clear_email_failures() {
+        let stub_access_token_issuer = FakeTokenIssuer::new("access_token");
+        let stub_refresh_token_issuer = FakeTokenIssuer::new("refresh_token");
+        let stub_mfa_token_issuer = FakeTokenIssuer::new("mfa_token");
+        let stub_password_hasher = FakePasswordHasher::new("rehashed");
+        let stub_password_validator = FakePasswordValidator::new(true);
+        let mut stub_user_repository = setup_repository();
+        let mut stub_refresh_token_family_repository = FakeRefreshTokenFamilyRepository::new();
+        let mut mock_login_attempt_repository = FakeLoginAttemptRepository::new();
+        let stub_totp_credential_repository = FakeTotpCredentialRepository::new();
+        mock_login_attempt_repository.data.insert(
+            "email:example@example.com".to_string(),
+            create_attempt("email:example@example.com", 2, 1747636930),
+        );
+        mock_login_attempt_repository.data.insert(
+            "ip:127.0.0.1".to_string(),
+            create_attempt("ip:127.0.0.1", 2, 1747636930),
+        );
+        let stub_login_throttle_policy = login_throttle_policy();
+        let sign_in = SignInUseCase::new(
+            PasswordAuthenticator::new(
+                &stub_password_hasher,
+                &stub_password_validator,
+                &mut stub_user_repository,
+            ),
+            SessionIssuer::new(
+                &stub_access_token_issuer,
+                &stub_refresh_token_issuer,
+                &mut stub_refresh_token_family_repository,
+                fake_generate_id,
+            ),
+            &stub_mfa_token_issuer,
+            LoginThrottle::new(
+                &mut mock_login_attempt_repository,
+                &stub_login_throttle_policy,
+            ),
+            &stub_totp_credential_repository,
+            false,
+            fake_get_timestamp,
+        );
+
+        let result = sign_in.execute(
+            EmailAddress::new("example@example.com").unwrap(),
+            "password",
+            "127.0.0.1",
+        );
+
+        assert!(result.is_ok());
+        assert!(
+            !mock_login_attempt_repository
+                .data
+                .contains_key("email:example@example.com")
+        );
+        assert!(
+            mock_login_attempt_repository
+                .data
+                .contains_key("ip:127.0.0.1")
+        );
+    }
+
+    #[test]
+    fn execute_given_unverified_email_should_require_verification_when_configured() {
+        let test_cases = vec![(true, false), (false, true)];
+
+        for (require_verified_email, is_ok) in test_cases {
+            let stub_access_token_issuer = FakeTokenIssuer::new("access_token");
+            let stub_refresh_token_issuer = FakeTokenIssuer::new("refresh_token");
+            let stub_mfa_token_issuer = FakeTokenIssuer::new("mfa_token");
+            let stub_password_hasher = FakePasswordHasher::new("rehashed");
+            let stub_password_validator = FakePasswordValidator::new(true);
+            let mut stub_user_repository = setup_repository();
+            let mut mock_refresh_token_family_repository = FakeRefreshTokenFamilyRepository::new();
+            let mut stub_login_attempt_repository = FakeLoginAttemptRepository::new();
+            let stub_totp_credential_repository = FakeTotpCredentialRepository::new();
+            let stub_login_throttle_policy = login_throttle_policy();
+            let sign_in = SignInUseCase::new(
+                PasswordAuthenticator::new(
+                    &stub_password_hasher,
+                    &stub_password_validator,
+                    &mut stub_user_repository,
+                ),
+                SessionIssuer::new(
+                    &stub_access_token_issuer,
+                    &stub_refresh_token_issuer,
+                    &mut mock_refresh_token_family_repository,
+                    fake_generate_id,
+                ),
+                &stub_mfa_token_issuer,
+                LoginThrottle::new(
+                    &mut stub_login_attempt_repository,
+                    &stub_login_throttle_policy,
+                ),
+                &stub_totp_credential_repository,
+                require_verified_email,
+                fake_get_timestamp,
+            );
+
+            let result = sign_in.execute(
+                EmailAddress::new("example@example.com").unwrap(),
+                "password",
+                "127.0.0.1",
+            );
+
+            assert_eq!(result.is_ok(), is_ok);
+            assert_eq!(mock_refresh_token_family_repository.data.is_empty(), !is_ok);
+        }
+    }
+
+    #[test]
+    fn execute_given_confirmed_totp_credential_should_return_mfa_required() {
+        let test_cases = vec![(true, false), (false, true)];
+
+        for (confirmed, is_ok) in test_cases {
+            let stub_access_token_issuer = FakeTokenIssuer::new("access_token");
+            let stub_refresh_token_issuer = FakeTokenIssuer::new("refresh_token");
+            let mock_mfa_token_issuer = FakeTokenIssuer::new("mfa_token");
+            let stub_password_hasher = FakePasswordHasher::new("rehashed");
+            let stub_password_validator = FakePasswordValidator::new(true);
+            let mut stub_user_repository = setup_repository();
+            let mut mock_refresh_token_family_repository = FakeRefreshTokenFamilyRepository::new();
+            let mut stub_login_attempt_repository = FakeLoginAttemptRepository::new();
+            let mut stub_totp_credential_repository = FakeTotpCredentialRepository::new();
+            stub_totp_credential_repository.data.insert(
+                "0196e2a4-5c1b-4f7e-9a3d-2b8c6d4e1f00".to_string(),
+                TotpCredential {
+                    user_id: UserId::new("0196e2a4-5c1b-4f7e-9a3d-2b8c6d4e1f00").unwrap(),
+                    secret: "secret".to_string(),
+                    confirmed,
+                    recovery_codes: vec![],
+                    last_used_step: 0,
+                },
+            );
+            let stub_login_throttle_policy = login_throttle_policy();
+            let sign_in = SignInUseCase::new(
+                PasswordAuthenticator::new(
+                    &stub_password_hasher,
+                    &stub_password_validator,
+                    &mut stub_user_repository,
+                ),
+                SessionIssuer::new(
+                    &stub_access_token_issuer,
+                    &stub_refresh_token_issuer,
+                    &mut mock_refresh_token_family_repository,
+                    fake_generate_id,
+                ),
+                &mock_mfa_token_issuer,
+                LoginThrottle::new(
+                    &mut stub_login_attempt_repository,
+                    &stub_login_throttle_policy,
+                ),
+                &stub_totp_credential_repository,
+                false,
+                fake_get_timestamp,
+            );
+
+            let result = sign_in.execute(
+                EmailAddress::new("example@example.com").unwrap(),
+                "password",
+                "127.0.0.1",
+            );
+
+            assert_eq!(result.is_ok(), is_ok);
+            if !is_ok {
+                assert!(matches!(
+                    result,
+                    Err(FailReason::MfaRequired { mfa_token }) if mfa_token == "mfa_token"
+                ));
+                let issued_subjects = mock_mfa_token_issuer.issued_subjects.borrow();
+                assert_eq!(
+                    issued_subjects[0].sub,
+                    "0196e2a4-5c1b-4f7e-9a3d-2b8c6d4e1f00"
+                );
+                assert_eq!(issued_subjects[0].jti.as_deref(), Some("generated_id"));
+            }
+            assert_eq!(mock_refresh_token_family_repository.data.is_empty(), !is_ok);
+        }
+    }
 }
@@ -0,0 +1,528 @@
+use super::signin::{LoginThrottle, SessionIssuer, SignInResult};
+use crate::application::service::auth::{TokenVerifier, TotpAuthenticator};
+use crate::domain::{
+    entity::TotpCredential,
+    repository::{RevokedTokenRepository, TotpCredentialRepository, UserRepository},
+    value_object::UserId,
+};
+
+pub struct SignInMfaUseCase<'a> {
+    mfa_token_verifier: &'a dyn TokenVerifier,
+    second_factor: SecondFactor<'a>,
+    session_issuer: SessionIssuer<'a>,
+    user_repository: &'a dyn UserRepository,
+    revoked_token_repository: &'a mut dyn RevokedTokenRepository,
+    login_throttle: LoginThrottle<'a>,
+    get_timestamp: fn() -> u64,
+}
+
+impl<'a> SignInMfaUseCase<'a> {
+    pub fn new(
+        mfa_token_verifier: &'a dyn TokenVerifier,
+        second_factor: SecondFactor<'a>,
+        session_issuer: SessionIssuer<'a>,
+        user_repository: &'a dyn UserRepository,
+        revoked_token_repository: &'a mut dyn RevokedTokenRepository,
+        login_throttle: LoginThrottle<'a>,
+        get_timestamp: fn() -> u64,
+    ) -> Self {
+        SignInMfaUseCase {
+            mfa_token_verifier,
+            second_factor,
+            session_issuer,
+            user_repository,
+            revoked_token_repository,
+            login_throttle,
+            get_timestamp,
+        }
+    }
+
+    pub fn execute(
+        mut self,
+        mfa_token: &str,
+        code: &str,
+    ) -> Result<SignInResult, SignInMfaFailReason> {
+        let now = (self.get_timestamp)();
+        let claims = self
+            .mfa_token_verifier
+            .verify(mfa_token)
+            .map_err(|_| SignInMfaFailReason::InvalidToken)?;
+        let jti = claims.jti.ok_or(SignInMfaFailReason::InvalidToken)?;
+        let user_id = UserId::new(&claims.sub).map_err(|_| SignInMfaFailReason::InvalidToken)?;
+        let mfa_key = format!("mfa:{}", user_id.as_str());
+        if let Some(blocked_until) = self.login_throttle.locked_until(&[&mfa_key], now) {
+            return Err(SignInMfaFailReason::AccountLocked {
+                retry_after: blocked_until - now,
+            });
+        }
+        let credential = self
+            .second_factor
+            .get_credential(&user_id)
+            .ok_or(SignInMfaFailReason::InvalidToken)?;
+        let user = self
+            .user_repository
+            .get_by_id(&user_id)
+            .map_err(|_| SignInMfaFailReason::InvalidToken)?;
+
+        if !self.second_factor.consume(credential, code, now) {
+            let max_failures = self.login_throttle.policy().max_failures;
+            self.login_throttle
+                .record_failure(&mfa_key, max_failures, now);
+            return Err(SignInMfaFailReason::InvalidCode);
+        }
+        self.login_throttle.reset(&mfa_key);
+        if !self
+            .revoked_token_repository
+            .revoke_if_absent(&jti, claims.exp)
+        {
+            return Err(SignInMfaFailReason::InvalidToken);
+        }
+
+        Ok(self.session_issuer.issue(user))
+    }
+}
+
+pub struct SecondFactor<'a> {
+    totp_authenticator: &'a dyn TotpAuthenticator,
+    totp_credential_repository: &'a mut dyn TotpCredentialRepository,
+    hash_secret: fn(&str) -> String,
+}
+
+impl<'a> SecondFactor<'a> {
+    pub fn new(
+        totp_authenticator: &'a dyn TotpAuthenticator,
+        totp_credential_repository: &'a mut dyn TotpCredentialRepository,
+        hash_secret: fn(&str) -> String,
+    ) -> Self {
+        SecondFactor {
+            totp_authenticator,
+            totp_credential_repository,
+            hash_secret,
+        }
+    }
+
+    pub(super) fn get_credential(&self, user_id: &UserId) -> Option<TotpCredential> {
+        self.totp_credential_repository
+            .get(user_id)
+            .ok()
+            .filter(|credential| credential.confirmed)
+    }
+
+    pub(super) fn consume(&mut self, credential: TotpCredential, code: &str, now: u64) -> bool {
+        if self
+            .totp_authenticator
+            .verify(&credential.secret, code, now)
+            .is_some_and(|step| {
+                self.totp_credential_repository
+                    .advance_step(&credential.user_id, step)
+            })
+        {
+            return true;
+        }
+        let recovery_code_hash = (self.hash_secret)(&code.trim().to_lowercase());
+
+        self.totp_credential_repository
+            .consume_recovery_code(&credential.user_id, &recovery_code_hash)
+    }
+}
+
+#[derive(Debug)]
+pub enum SignInMfaFailReason {
+    InvalidToken,
+    InvalidCode,
+    AccountLocked { retry_after: u64 },
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::application::{service::auth::TokenClaims, use_case::LoginThrottlePolicy};
+    use crate::domain::{
+        entity::{LoginAttempt, TotpCredential, User},
+        value_object::EmailAddress,
+    };
+    use crate::test_support::{
+        application::service::{FakeTokenIssuer, FakeTokenVerifier, FakeTotpAuthenticator},
+        domain::repository::{
+            FakeLoginAttemptRepository, FakeRefreshTokenFamilyRepository,
+            FakeRevokedTokenRepository, FakeTotpCredentialRepository, FakeUserRepository,
+        },
+    };
+
+    const USER_ID: &str = "0196e2a4-5c1b-4f7e-9a3d-2b8c6d4e1f00";
+
+    fn fake_hash_secret(secret: &str) -> String {
+        format!("hashed_{}", secret)
+    }
+
+    fn fake_generate_id() -> String {
+        "generated_id".to_string()
+    }
+
+    fn fake_get_timestamp() -> u64 {
+        1747636936
+    }
+
+    fn login_throttle_policy() -> LoginThrottlePolicy {
+        LoginThrottlePolicy {
+            max_failures: 3,
+            ip_max_failures: 10,
+            backoff_base_seconds: 1,
+            backoff_max_seconds: 60,
+            lockout_seconds: 900,
+        }
+    }
+
+    fn mfa_token_claims() -> TokenClaims {
+        TokenClaims {
+            sub: USER_ID.to_string(),
+            iss: "app".to_string(),
+            aud: "app:mfa".to_string(),
+            iat: 1747636900,
+            exp: 1747637200,
+            jti: Some("mfa_token_id".to_string()),
+            fid: None,
+            email: None,
+            scope: None,
+        }
+    }
+
+    fn setup_user_repository() -> FakeUserRepository {
+        let mut repo = FakeUserRepository::new();
+        repo.data.insert(
+            USER_ID.to_string(),
+            User {
+                id: UserId::new(USER_ID).unwrap(),
+                email: EmailAddress::new("example@example.com").unwrap(),
+                email_verified: true,
+                pending_email: None,
+                username: "foo".to_string(),
+                password: "hashed".to_string(),
+                create_at: 1747636936,
+                update_at: 1747636936,
+            },
+        );
+        repo
+    }
+
+    fn setup_totp_credential_repository(last_used_step: u64) -> FakeTotpCredentialRepository {
+        let mut repo = FakeTotpCredentialRepository::new();
+        repo.data.insert(
+            USER_ID.to_string(),
+            TotpCredential {
+                user_id: UserId::new(USER_ID).unwrap(),
+                secret: "SECRET".to_string(),
+                confirmed: true,
+                recovery_codes: vec![
+                    "hashed_abcde-fghij".to_string(),
+                    "hashed_klmno-pqrst".to_string(),
+                ],
+                last_used_step,
+            },
+        );
+        repo
+    }
+
+    #[test]
+    fn execute_given_valid_totp_code_should_return_signin_result() {
+        let stub_mfa_token_verifier = FakeTokenVerifier::new(Some(mfa_token_claims()));
+        let stub_totp_authenticator = FakeTotpAuthenticator::new("SECRET", "123456", 58254564);
+        let stub_access_token_issuer = FakeTokenIssuer::new("access_token");
+        let stub_refresh_token_issuer = FakeTokenIssuer::new("refresh_token");
+        let stub_user_repository = setup_user_repository();
+        let mut mock_refresh_token_family_repository = FakeRefreshTokenFamilyRepository::new();
+        let mut mock_revoked_token_repository = FakeRevokedTokenRepository::new();
+        let mut mock_login_attempt_repository = FakeLoginAttemptRepository::new();
+        mock_login_attempt_repository.data.insert(
+            format!("mfa:{}", USER_ID),
+            LoginAttempt {
+                key: format!("mfa:{}", USER_ID),
+                failures: 1,
+                blocked_until: 1747636900,
+                expire_at: 1747637836,
+            },
+        );
+        let mut mock_totp_credential_repository = setup_totp_credential_repository(58254563);
+        let stub_login_throttle_policy = login_throttle_policy();
+        let sign_in_mfa = SignInMfaUseCase::new(
+            &stub_mfa_token_verifier,
+            SecondFactor::new(
+                &stub_totp_authenticator,
+                &mut mock_totp_credential_repository,
+                fake_hash_secret,
+            ),
+            SessionIssuer::new(
+                &stub_access_token_issuer,
+                &stub_refresh_token_issuer,
+                &mut mock_refresh_token_family_repository,
+                fake_generate_id,
+            ),
+            &stub_user_repository,
+            &mut mock_revoked_token_repository,
+            LoginThrottle::new(
+                &mut mock_login_attempt_repository,
+                &stub_login_throttle_policy,
+            ),
+            fake_get_timestamp,
+        );
+
+        let result = sign_in_mfa.execute("mfa_token", "123456");
+
+        assert!(result.is_ok_and(|result| {
+            result.access_token == "access_token"
+                && result.refresh_token == "refresh_token"
+                && result.id == USER_ID
+        }));
+        assert_eq!(
+            mock_totp_credential_repository.data[USER_ID].last_used_step,
+            58254564
+        );
+        assert_eq!(
+            mock_revoked_token_repository.data["mfa_token_id"],
+            1747637200
+        );
+        assert!(mock_login_attempt_repository.data.is_empty());
+        assert!(
+            mock_refresh_token_family_repository
+                .data
+                .contains_key("generated_id")
+        );
+    }
+
+    #[test]
+    fn execute_given_consumed_mfa_token_should_return_invalid_token() {
+        let stub_mfa_token_verifier = FakeTokenVerifier::new(Some(mfa_token_claims()));
+        let stub_totp_authenticator = FakeTotpAuthenticator::new("SECRET", "123456", 58254564);
+        let stub_access_token_issuer = FakeTokenIssuer::new("access_token");
+        let stub_refresh_token_issuer = FakeTokenIssuer::new("refresh_token");
+        let stub_user_repository = setup_user_repository();
+        let mut mock_refresh_token_family_repository = FakeRefreshTokenFamilyRepository::new();
+        let mut stub_revoked_token_repository = FakeRevokedTokenRepository::new();
+        stub_revoked_token_repository
+            .data
+            .insert("mfa_token_id".to_string(), 1747637200);
+        let mut stub_login_attempt_repository = FakeLoginAttemptRepository::new();
+        let mut stub_totp_credential_repository = setup_totp_credential_repository(58254563);
+        let stub_login_throttle_policy = login_throttle_policy();
+        let sign_in_mfa = SignInMfaUseCase::new(
+            &stub_mfa_token_verifier,
+            SecondFactor::new(
+                &stub_totp_authenticator,
+                &mut stub_totp_credential_repository,
+                fake_hash_secret,
+            ),
+            SessionIssuer::new(
+                &stub_access_token_issuer,
+                &stub_refresh_token_issuer,
+                &mut mock_refresh_token_family_repository,
+                fake_generate_id,
+            ),
+            &stub_user_repository,
+            &mut stub_revoked_token_repository,
+            LoginThrottle::new(
+                &mut stub_login_attempt_repository,
+                &stub_login_throttle_policy,
+            ),
+            fake_get_timestamp,
+        );
+
+        let result = sign_in_mfa.execute("mfa_token", "123456");
+
+        assert!(matches!(result, Err(SignInMfaFailReason::InvalidToken)));
+        assert!(mock_refresh_token_family_repository.data.is_empty());
+    }
+
+    #[test]
+    fn execute_given_recovery_code_should_consume_it() {
+        let stub_mfa_token_verifier = FakeTokenVerifier::new(Some(mfa_token_claims()));
+        let stub_totp_authenticator = FakeTotpAuthenticator::new("SECRET", "123456", 58254564);
+        let stub_access_token_issuer = FakeTokenIssuer::new("access_token");
+        let stub_refresh_token_issuer = FakeTokenIssuer::new("refresh_token");
+        let stub_user_repository = setup_user_repository();
+        let mut stub_refresh_token_family_repository = FakeRefreshTokenFamilyRepository::new();
+        let mut stub_revoked_token_repository = FakeRevokedTokenRepository::new();
+        let mut stub_login_attempt_repository = FakeLoginAttemptRepository::new();
+        let mut mock_totp_credential_repository = setup_totp_credential_repository(0);
+        let stub_login_throttle_policy = login_throttle_policy();
+        let sign_in_mfa = SignInMfaUseCase::new(
+            &stub_mfa_token_verifier,
+            SecondFactor::new(
+                &stub_totp_authenticator,
+                &mut mock_totp_credential_repository,
+                fake_hash_secret,
+            ),
+            SessionIssuer::new(
+                &stub_access_token_issuer,
+                &stub_refresh_token_issuer,
+                &mut stub_refresh_token_family_repository,
+                fake_generate_id,
+            ),
+            &stub_user_repository,
+            &mut stub_revoked_token_repository,
+            LoginThrottle::new(
+                &mut stub_login_attempt_repository,
+                &stub_login_throttle_policy,
+            ),
+            fake_get_timestamp,
+        );
+
+        let result = sign_in_mfa.execute("mfa_token", " ABCDE-FGHIJ ");
+
+        assert!(result.is_ok());
+        let credential = &mock_totp_credential_repository.data[USER_ID];
+        assert_eq!(credential.recovery_codes, vec!["hashed_klmno-pqrst"]);
+        assert_eq!(credential.last_used_step, 0);
+    }
+
+    #[test]
+    fn execute_given_invalid_or_replayed_code_should_record_failure() {
+        let test_cases = vec![("654321", 0), ("123456", 58254564)];
+
+        for (code, last_used_step) in test_cases {
+            let stub_mfa_token_verifier = FakeTokenVerifier::new(Some(mfa_token_claims()));
+            let stub_totp_authenticator = FakeTotpAuthenticator::new("SECRET", "123456", 58254564);
+            let stub_access_token_issuer = FakeTokenIssuer::new("access_token");
+            let stub_refresh_token_issuer = FakeTokenIssuer::new("refresh_token");
+            let stub_user_repository = setup_user_repository();
+            let mut mock_refresh_token_family_repository = FakeRefreshTokenFamilyRepository::new();
+            let mut mock_revoked_token_repository = FakeRevokedTokenRepository::new();
+            let mut mock_login_attempt_repository = FakeLoginAttemptRepository::new();
+            let mut stub_totp_credential_repository =
+                setup_totp_credential_repository(last_used_step);
+            let stub_login_throttle_policy = login_throttle_policy();
+            let sign_in_mfa = SignInMfaUseCase::new(
+                &stub_mfa_token_verifier,
+                SecondFactor::new(
+                    &stub_totp_authenticator,
+                    &mut stub_totp_credential_repository,
+                    fake_hash_secret,
+                ),
+                SessionIssuer::new(
+                    &stub_access_token_issuer,
+                    &stub_refresh_token_issuer,
+                    &mut mock_refresh_token_family_repository,
+                    fake_generate_id,
+                ),
+                &stub_user_repository,
+                &mut mock_revoked_token_repository,
+                LoginThrottle::new(
+                    &mut mock_login_attempt_repository,
+                    &stub_login_throttle_policy,
+                ),
+                fake_get_timestamp,
+            );
+
+            let result = sign_in_mfa.execute("mfa_token", code);
+
+            assert!(matches!(result, Err(SignInMfaFailReason::InvalidCode)));
+            assert_eq!(
+                mock_login_attempt_repository.data[&format!("mfa:{}", USER_ID)].failures,
+                1
+            );
+            assert!(mock_revoked_token_repository.data.is_empty());
+            assert!(mock_refresh_token_family_repository.data.is_empty());
+        }
+    }
+
+    #[test]
+    fn execute_given_blocked_user_should_return_account_locked() {
+        let stub_mfa_token_verifier = FakeTokenVerifier::new(Some(mfa_token_claims()));
+        let stub_totp_authenticator = FakeTotpAuthenticator::new("SECRET", "123456", 58254564);
+        let stub_access_token_issuer = FakeTokenIssuer::new("access_token");
+        let stub_refresh_token_issuer = FakeTokenIssuer::new("refresh_token");
+        let stub_user_repository = setup_user_repository();
+        let mut mock_refresh_token_family_repository = FakeRefreshTokenFamilyRepository::new();
+        let mut stub_revoked_token_repository = FakeRevokedTokenRepository::new();
+        let mut stub_login_attempt_repository = FakeLoginAttemptRepository::new();
+        stub_login_attempt_repository.data.insert(
+            format!("mfa:{}", USER_ID),
+            LoginAttempt {
+                key: format!("mfa:{}", USER_ID),
+                failures: 3,
+                blocked_until: 1747637836,
+                expire_at: 1747637836,
+            },
+        );
+        let mut stub_totp_credential_repository = setup_totp_credential_repository(0);
+        let stub_login_throttle_policy = login_throttle_policy();
+        let sign_in_mfa = SignInMfaUseCase::new(
+            &stub_mfa_token_verifier,
+            SecondFactor::new(
+                &stub_totp_authenticator,
+                &mut stub_totp_credential_repository,
+                fake_hash_secret,
+            ),
+            SessionIssuer::new(
+                &stub_access_token_issuer,
+                &stub_refresh_token_issuer,
+                &mut mock_refresh_token_family_repository,
+                fake_generate_id,
+            ),
+            &stub_user_repository,
+            &mut stub_revoked_token_repository,
+            LoginThrottle::new(
+                &mut stub_login_attempt_repository,
+                &stub_login_throttle_policy,
+            ),
+            fake_get_timestamp,
+        );
+
+        let result = sign_in_mfa.execute("mfa_token", "123456");
+
+        assert!(matches!(
+            result,
+            Err(SignInMfaFailReason::AccountLocked { retry_after: 900 })
+        ));
+        assert!(mock_refresh_token_family_repository.data.is_empty());
+    }
+
+    #[test]
+    fn execute_given_invalid_mfa_token_should_return_invalid_token() {
+        let test_cases = vec![
+            None,
+            Some(TokenClaims {
+                jti: None,
+                ..mfa_token_claims()
+            }),
+        ];
+
+        for claims in test_cases {
+            let stub_mfa_token_verifier = FakeTokenVerifier::new(claims);
+            let stub_totp_authenticator = FakeTotpAuthenticator::new("SECRET", "123456", 58254564);
+            let stub_access_token_issuer = FakeTokenIssuer::new("access_token");
+            let stub_refresh_token_issuer = FakeTokenIssuer::new("refresh_token");
+            let stub_user_repository = setup_user_repository();
+            let mut mock_refresh_token_family_repository = FakeRefreshTokenFamilyRepository::new();
+            let mut stub_revoked_token_repository = FakeRevokedTokenRepository::new();
+            let mut stub_login_attempt_repository = FakeLoginAttemptRepository::new();
+            let mut stub_totp_credential_repository = setup_totp_credential_repository(0);
+            let stub_login_throttle_policy = login_throttle_policy();
+            let sign_in_mfa = SignInMfaUseCase::new(
+                &stub_mfa_token_verifier,
+                SecondFactor::new(
+                    &stub_totp_authenticator,
+                    &mut stub_totp_credential_repository,
+                    fake_hash_secret,
+                ),
+                SessionIssuer::new(
+                    &stub_access_token_issuer,
+                    &stub_refresh_token_issuer,
+                    &mut mock_refresh_token_family_repository,
+                    fake_generate_id,
+                ),
+                &stub_user_repository,
+                &mut stub_revoked_token_repository,
+                LoginThrottle::new(
+                    &mut stub_login_attempt_repository,
+                    &stub_login_throttle_policy,
+                ),
+                fake_get_timestamp,
+            );
+
+            let result = sign_in_mfa.execute("mfa_token", "123456");
+
+            assert!(matches!(result, Err(SignInMfaFailReason::InvalidToken)));
+            assert!(mock_refresh_token_family_repository.data.is_empty());
+        }
+    }
+}
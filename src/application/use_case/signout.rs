@@ -0,0 +1,154 @@
+use super::revoke::revoke;
+use crate::application::service::auth::TokenVerifier;
+use crate::domain::{
+    error,
+    repository::{RefreshTokenFamilyRepository, RevokedTokenRepository},
+};
+
+pub struct SignOutUseCase<'a> {
+    access_token_verifier: &'a dyn TokenVerifier,
+    refresh_token_verifier: &'a dyn TokenVerifier,
+    revoked_token_repository: &'a mut dyn RevokedTokenRepository,
+    refresh_token_family_repository: &'a mut dyn RefreshTokenFamilyRepository,
+}
+
+impl<'a> SignOutUseCase<'a> {
+    pub fn new(
+        access_token_verifier: &'a dyn TokenVerifier,
+        refresh_token_verifier: &'a dyn TokenVerifier,
+        revoked_token_repository: &'a mut dyn RevokedTokenRepository,
+        refresh_token_family_repository: &'a mut dyn RefreshTokenFamilyRepository,
+    ) -> Self {
+        SignOutUseCase {
+            access_token_verifier,
+            refresh_token_verifier,
+            revoked_token_repository,
+            refresh_token_family_repository,
+        }
+    }
+
+    pub fn execute(
+        self,
+        access_token: &str,
+        refresh_token: Option<&str>,
+    ) -> Result<(), error::InvalidToken> {
+        let access_claims = self.access_token_verifier.verify(access_token)?;
+        let refresh_claims = refresh_token
+            .and_then(|token| self.refresh_token_verifier.verify(token).ok())
+            .filter(|claims| claims.sub == access_claims.sub);
+
+        revoke(
+            access_claims,
+            self.revoked_token_repository,
+            self.refresh_token_family_repository,
+        );
+        if let Some(claims) = refresh_claims {
+            revoke(
+                claims,
+                self.revoked_token_repository,
+                self.refresh_token_family_repository,
+            );
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::application::service::auth::TokenClaims;
+    use crate::domain::entity::RefreshTokenFamily;
+    use crate::test_support::{
+        application::service::FakeTokenVerifier,
+        domain::repository::{FakeRefreshTokenFamilyRepository, FakeRevokedTokenRepository},
+    };
+
+    fn claims(sub: &str, jti: &str, fid: Option<&str>) -> TokenClaims {
+        TokenClaims {
+            sub: sub.to_string(),
+            iss: "example".to_string(),
+            aud: "example".to_string(),
+            iat: 1747636936,
+            exp: 1747640536,
+            jti: Some(jti.to_string()),
+            fid: fid.map(String::from),
+            email: None,
+            scope: None,
+        }
+    }
+
+    fn setup_family_repository() -> FakeRefreshTokenFamilyRepository {
+        let mut repo = FakeRefreshTokenFamilyRepository::new();
+        repo.data.insert(
+            "family_id".to_string(),
+            RefreshTokenFamily {
+                id: "family_id".to_string(),
+                subject: "foo".to_string(),
+                current_token_id: "refresh_token_id".to_string(),
+                revoked: false,
+            },
+        );
+        repo
+    }
+
+    #[test]
+    fn execute_given_access_and_refresh_token_should_revoke_both() {
+        let stub_access_token_verifier =
+            FakeTokenVerifier::new(Some(claims("foo", "access_token_id", None)));
+        let stub_refresh_token_verifier =
+            FakeTokenVerifier::new(Some(claims("foo", "refresh_token_id", Some("family_id"))));
+        let mut mock_revoked_token_repository = FakeRevokedTokenRepository::new();
+        let mut mock_refresh_token_family_repository = setup_family_repository();
+        let sign_out = SignOutUseCase::new(
+            &stub_access_token_verifier,
+            &stub_refresh_token_verifier,
+            &mut mock_revoked_token_repository,
+            &mut mock_refresh_token_family_repository,
+        );
+
+        let result = sign_out.execute("access_token", Some("refresh_token"));
+
+        assert!(result.is_ok());
+        assert!(mock_revoked_token_repository.is_revoked("access_token_id"));
+        assert!(mock_refresh_token_family_repository.data["family_id"].revoked);
+    }
+
+    #[test]
+    fn execute_given_refresh_token_of_another_user_should_keep_its_family() {
+        let stub_access_token_verifier =
+            FakeTokenVerifier::new(Some(claims("bar", "access_token_id", None)));
+        let stub_refresh_token_verifier =
+            FakeTokenVerifier::new(Some(claims("foo", "refresh_token_id", Some("family_id"))));
+        let mut stub_revoked_token_repository = FakeRevokedTokenRepository::new();
+        let mut mock_refresh_token_family_repository = setup_family_repository();
+        let sign_out = SignOutUseCase::new(
+            &stub_access_token_verifier,
+            &stub_refresh_token_verifier,
+            &mut stub_revoked_token_repository,
+            &mut mock_refresh_token_family_repository,
+        );
+
+        let _ = sign_out.execute("access_token", Some("refresh_token"));
+
+        assert!(!mock_refresh_token_family_repository.data["family_id"].revoked);
+    }
+
+    #[test]
+    fn execute_given_invalid_access_token_should_return_invalid_token() {
+        let stub_access_token_verifier = FakeTokenVerifier::new(None);
+        let stub_refresh_token_verifier = FakeTokenVerifier::new(None);
+        let mut stub_revoked_token_repository = FakeRevokedTokenRepository::new();
+        let mut stub_refresh_token_family_repository = setup_family_repository();
+        let sign_out = SignOutUseCase::new(
+            &stub_access_token_verifier,
+            &stub_refresh_token_verifier,
+            &mut stub_revoked_token_repository,
+            &mut stub_refresh_token_family_repository,
+        );
+
+        let result = sign_out.execute("invalid_token", None);
+
+        assert!(result.is_err_and(|err| matches!(err, error::InvalidToken {})));
+    }
+}
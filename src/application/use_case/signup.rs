@@ -1,68 +1,151 @@
-use crate::application::service::auth::PasswordHasher;
-use crate::domain::{entity::User, error, repository::UserRepository, value_object::EmailAddress};
+use crate::application::service::auth::{BreachedPasswordChecker, PasswordHasher};
+use crate::domain::{
+    entity::User,
+    error,
+    repository::UserRepository,
+    value_object::{EmailAddress, Password, PasswordPolicy, UserId},
+};
 
 pub struct SignUpUseCase<'a> {
     password_hasher: &'a dyn PasswordHasher,
     user_repository: &'a mut dyn UserRepository,
+    password_rules: PasswordRules<'a>,
     get_timestamp: fn() -> u64,
+    generate_id: fn() -> String,
 }
 
 impl<'a> SignUpUseCase<'a> {
     pub fn new(
         password_hasher: &'a dyn PasswordHasher,
         user_repository: &'a mut dyn UserRepository,
+        password_rules: PasswordRules<'a>,
         get_timestamp: fn() -> u64,
+        generate_id: fn() -> String,
     ) -> Self {
         SignUpUseCase {
             password_hasher,
             user_repository,
+            password_rules,
             get_timestamp,
+            generate_id,
         }
     }
 
-    pub fn execute(&mut self, user_data: CreateUserDTO) -> Result<(), error::EntityConflict> {
+    pub fn execute(&mut self, user_data: CreateUserDTO) -> Result<(), SignUpFailReason> {
+        let password = self
+            .password_rules
+            .check(
+                &user_data.password,
+                &user_data.email_address,
+                &user_data.username,
+            )
+            .map_err(SignUpFailReason::InvalidPassword)?;
         let now = (self.get_timestamp)();
         let user = User {
+            id: UserId::new(&(self.generate_id)()).expect("generated user id should be valid"),
             email: user_data.email_address,
+            email_verified: false,
+            pending_email: None,
             username: user_data.username,
-            password: self.password_hasher.hash(&user_data.password),
+            password: self.password_hasher.hash(password.as_str()),
             create_at: now,
             update_at: now,
         };
-        self.user_repository.create(user)
+        self.user_repository
+            .create(user)
+            .map_err(SignUpFailReason::EmailConflict)
     }
 }
 
+#[derive(Debug)]
+pub enum SignUpFailReason {
+    InvalidPassword(error::ValidationError),
+    EmailConflict(error::EntityConflict),
+}
+
 pub struct CreateUserDTO {
     pub email_address: EmailAddress,
     pub username: String,
     pub password: String,
 }
 
+pub struct PasswordRules<'a> {
+    password_policy: &'a PasswordPolicy,
+    breached_password_checker: &'a dyn BreachedPasswordChecker,
+}
+
+impl<'a> PasswordRules<'a> {
+    pub fn new(
+        password_policy: &'a PasswordPolicy,
+        breached_password_checker: &'a dyn BreachedPasswordChecker,
+    ) -> Self {
+        PasswordRules {
+            password_policy,
+            breached_password_checker,
+        }
+    }
+
+    pub(super) fn check(
+        &self,
+        password: &str,
+        email: &EmailAddress,
+        username: &str,
+    ) -> Result<Password, error::ValidationError> {
+        Password::new(
+            password,
+            self.password_policy,
+            email,
+            username,
+            self.breached_password_checker.is_breached(password),
+        )
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
     use crate::test_support::{
-        application::service::FakePasswordHasher, domain::repository::FakeUserRepository,
+        application::service::{FakeBreachedPasswordChecker, FakePasswordHasher},
+        domain::repository::FakeUserRepository,
     };
 
     fn fake_get_timestamp() -> u64 {
         1747636936
     }
 
+    fn password_policy() -> PasswordPolicy {
+        PasswordPolicy {
+            min_length: 8,
+            max_length: 64,
+            require_lowercase: false,
+            require_uppercase: false,
+            require_digit: false,
+            require_symbol: false,
+            forbid_common: true,
+        }
+    }
+
+    fn fake_generate_id() -> String {
+        "0196e2a4-5c1b-4f7e-9a3d-2b8c6d4e1f00".to_string()
+    }
+
     #[test]
     fn execute_given_user_information_should_persist_to_repository() {
         let mut mock_user_repository = FakeUserRepository::new();
         let stub_password_hasher = FakePasswordHasher::new("hashed");
+        let stub_password_policy = password_policy();
+        let stub_breached_password_checker = FakeBreachedPasswordChecker::new(false);
         let mut sign_up = SignUpUseCase::new(
             &stub_password_hasher,
             &mut mock_user_repository,
+            PasswordRules::new(&stub_password_policy, &stub_breached_password_checker),
             fake_get_timestamp,
+            fake_generate_id,
         );
         let user = CreateUserDTO {
             email_address: EmailAddress::new("example@example.com").unwrap(),
             username: "test".to_string(),
-            password: "password".to_string(),
+            password: "correct horse".to_string(),
         };
 
         sign_up.execute(user).unwrap();
@@ -70,7 +153,8 @@ mod test {
         assert!(
             mock_user_repository
                 .data
-                .contains_key("example@example.com")
+                .get("0196e2a4-5c1b-4f7e-9a3d-2b8c6d4e1f00")
+                .is_some_and(|u| u.email.as_str() == "example@example.com")
         )
     }
 
@@ -79,28 +163,91 @@ mod test {
         let mut mock_user_repository = FakeUserRepository::new();
         let stub_password_hasher = FakePasswordHasher::new("hashed");
         mock_user_repository.data.insert(
-            "example@example.com".to_string(),
+            "0196e2a4-5c1b-4f7e-9a3d-2b8c6d4e1f01".to_string(),
             User {
+                id: UserId::new("0196e2a4-5c1b-4f7e-9a3d-2b8c6d4e1f01").unwrap(),
                 email: EmailAddress::new("example@example.com").unwrap(),
+                email_verified: false,
+                pending_email: None,
                 username: "foo".to_string(),
                 password: "bar".to_string(),
                 create_at: 1747636936,
                 update_at: 1747636936,
             },
         );
+        let user = CreateUserDTO {
+            email_address: EmailAddress::new("example@example.com").unwrap(),
+            username: "test".to_string(),
+            password: "correct horse".to_string(),
+        };
+        let stub_password_policy = password_policy();
+        let stub_breached_password_checker = FakeBreachedPasswordChecker::new(false);
+        let mut sign_up = SignUpUseCase::new(
+            &stub_password_hasher,
+            &mut mock_user_repository,
+            PasswordRules::new(&stub_password_policy, &stub_breached_password_checker),
+            fake_get_timestamp,
+            fake_generate_id,
+        );
+
+        let result = sign_up.execute(user);
+
+        assert!(matches!(
+            result,
+            Err(SignUpFailReason::EmailConflict(error::EntityConflict {}))
+        ));
+    }
+
+    #[test]
+    fn execute_given_password_violating_policy_should_return_invalid_password() {
+        let mut mock_user_repository = FakeUserRepository::new();
+        let stub_password_hasher = FakePasswordHasher::new("hashed");
+        let stub_password_policy = password_policy();
+        let stub_breached_password_checker = FakeBreachedPasswordChecker::new(false);
+        let mut sign_up = SignUpUseCase::new(
+            &stub_password_hasher,
+            &mut mock_user_repository,
+            PasswordRules::new(&stub_password_policy, &stub_breached_password_checker),
+            fake_get_timestamp,
+            fake_generate_id,
+        );
         let user = CreateUserDTO {
             email_address: EmailAddress::new("example@example.com").unwrap(),
             username: "test".to_string(),
             password: "password".to_string(),
         };
+
+        let result = sign_up.execute(user);
+
+        assert!(matches!(result, Err(SignUpFailReason::InvalidPassword(_))));
+        assert!(mock_user_repository.data.is_empty());
+    }
+
+    #[test]
+    fn execute_given_breached_password_should_return_invalid_password() {
+        let mut mock_user_repository = FakeUserRepository::new();
+        let stub_password_hasher = FakePasswordHasher::new("hashed");
+        let stub_password_policy = password_policy();
+        let stub_breached_password_checker = FakeBreachedPasswordChecker::new(true);
         let mut sign_up = SignUpUseCase::new(
             &stub_password_hasher,
             &mut mock_user_repository,
+            PasswordRules::new(&stub_password_policy, &stub_breached_password_checker),
             fake_get_timestamp,
+            fake_generate_id,
         );
+        let user = CreateUserDTO {
+            email_address: EmailAddress::new("example@example.com").unwrap(),
+            username: "test".to_string(),
+            password: "correct horse".to_string(),
+        };
 
         let result = sign_up.execute(user);
 
-        assert!(matches!(result, Err(error::EntityConflict {})));
+        assert!(matches!(
+            result,
+            Err(SignUpFailReason::InvalidPassword(err))
+                if err.violations() == ["Password has appeared in a data breach"]
+        ));
     }
 }
@@ -0,0 +1,230 @@
+use crate::application::service::auth::TokenVerifier;
+use crate::domain::{
+    error,
+    repository::{RevokedTokenRepository, UserRepository},
+    value_object::UserId,
+};
+
+pub struct VerifyEmailUseCase<'a> {
+    verification_token_verifier: &'a dyn TokenVerifier,
+    user_repository: &'a mut dyn UserRepository,
+    revoked_token_repository: &'a mut dyn RevokedTokenRepository,
+    get_timestamp: fn() -> u64,
+}
+
+impl<'a> VerifyEmailUseCase<'a> {
+    pub fn new(
+        verification_token_verifier: &'a dyn TokenVerifier,
+        user_repository: &'a mut dyn UserRepository,
+        revoked_token_repository: &'a mut dyn RevokedTokenRepository,
+        get_timestamp: fn() -> u64,
+    ) -> Self {
+        VerifyEmailUseCase {
+            verification_token_verifier,
+            user_repository,
+            revoked_token_repository,
+            get_timestamp,
+        }
+    }
+
+    pub fn execute(self, token: &str) -> Result<(), error::InvalidToken> {
+        let claims = self.verification_token_verifier.verify(token)?;
+        let (Some(jti), Some(email)) = (claims.jti, claims.email) else {
+            return Err(error::InvalidToken {});
+        };
+        let id = UserId::new(&claims.sub).map_err(|_| error::InvalidToken {})?;
+        let mut user = self
+            .user_repository
+            .get_by_id(&id)
+            .map_err(|_| error::InvalidToken {})?;
+        let applies_pending_email = user.email.as_str() != email;
+        if applies_pending_email
+            && user
+                .pending_email
+                .as_ref()
+                .is_none_or(|pending_email| pending_email.as_str() != email)
+        {
+            return Err(error::InvalidToken {});
+        }
+        if !self
+            .revoked_token_repository
+            .revoke_if_absent(&jti, claims.exp)
+        {
+            return Err(error::InvalidToken {});
+        }
+
+        if applies_pending_email {
+            user.email = user.pending_email.take().ok_or(error::InvalidToken {})?;
+        } else if user.email_verified {
+            return Ok(());
+        }
+        user.email_verified = true;
+        user.update_at = (self.get_timestamp)();
+        self.user_repository
+            .update(user)
+            .map_err(|_| error::InvalidToken {})
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::application::service::auth::TokenClaims;
+    use crate::domain::{entity::User, value_object::EmailAddress};
+    use crate::test_support::{
+        application::service::FakeTokenVerifier,
+        domain::repository::{FakeRevokedTokenRepository, FakeUserRepository},
+    };
+
+    const USER_ID: &str = "0196e2a4-5c1b-4f7e-9a3d-2b8c6d4e1f00";
+
+    fn fake_get_timestamp() -> u64 {
+        1747640536
+    }
+
+    fn claims(email: &str) -> TokenClaims {
+        TokenClaims {
+            sub: USER_ID.to_string(),
+            iss: "example".to_string(),
+            aud: "example".to_string(),
+            iat: 1747636936,
+            exp: 1747723336,
+            jti: Some("token_id".to_string()),
+            fid: None,
+            email: Some(email.to_string()),
+            scope: None,
+        }
+    }
+
+    fn setup_repository() -> FakeUserRepository {
+        let mut repo = FakeUserRepository::new();
+        repo.data.insert(
+            USER_ID.to_string(),
+            User {
+                id: UserId::new(USER_ID).unwrap(),
+                email: EmailAddress::new("example@example.com").unwrap(),
+                email_verified: false,
+                pending_email: None,
+                username: "foo".to_string(),
+                password: "hashed".to_string(),
+                create_at: 1747636936,
+                update_at: 1747636936,
+            },
+        );
+        repo
+    }
+
+    #[test]
+    fn execute_given_valid_token_should_mark_email_verified_and_consume_token() {
+        let stub_token_verifier = FakeTokenVerifier::new(Some(claims("example@example.com")));
+        let mut mock_user_repository = setup_repository();
+        let mut mock_revoked_token_repository = FakeRevokedTokenRepository::new();
+        let verify_email = VerifyEmailUseCase::new(
+            &stub_token_verifier,
+            &mut mock_user_repository,
+            &mut mock_revoked_token_repository,
+            fake_get_timestamp,
+        );
+
+        let result = verify_email.execute("verification_token");
+
+        assert!(result.is_ok());
+        let user = &mock_user_repository.data[USER_ID];
+        assert!(user.email_verified);
+        assert_eq!(user.update_at, 1747640536);
+        assert_eq!(mock_revoked_token_repository.data["token_id"], 1747723336);
+    }
+
+    #[test]
+    fn execute_given_token_for_previous_email_should_return_invalid_token() {
+        let stub_token_verifier = FakeTokenVerifier::new(Some(claims("previous@example.com")));
+        let mut mock_user_repository = setup_repository();
+        let mut mock_revoked_token_repository = FakeRevokedTokenRepository::new();
+        let verify_email = VerifyEmailUseCase::new(
+            &stub_token_verifier,
+            &mut mock_user_repository,
+            &mut mock_revoked_token_repository,
+            fake_get_timestamp,
+        );
+
+        let result = verify_email.execute("verification_token");
+
+        assert!(result.is_err());
+        assert!(!mock_user_repository.data[USER_ID].email_verified);
+        assert!(mock_revoked_token_repository.data.is_empty());
+    }
+
+    #[test]
+    fn execute_given_token_for_pending_email_should_apply_pending_email() {
+        let stub_token_verifier = FakeTokenVerifier::new(Some(claims("changed@example.com")));
+        let mut mock_user_repository = setup_repository();
+        mock_user_repository
+            .data
+            .get_mut(USER_ID)
+            .unwrap()
+            .pending_email = Some(EmailAddress::new("changed@example.com").unwrap());
+        let mut mock_revoked_token_repository = FakeRevokedTokenRepository::new();
+        let verify_email = VerifyEmailUseCase::new(
+            &stub_token_verifier,
+            &mut mock_user_repository,
+            &mut mock_revoked_token_repository,
+            fake_get_timestamp,
+        );
+
+        let result = verify_email.execute("verification_token");
+
+        assert!(result.is_ok());
+        let user = &mock_user_repository.data[USER_ID];
+        assert_eq!(user.email.as_str(), "changed@example.com");
+        assert!(user.email_verified);
+        assert!(user.pending_email.is_none());
+        assert_eq!(mock_revoked_token_repository.data["token_id"], 1747723336);
+    }
+
+    #[test]
+    fn execute_given_consumed_token_should_return_invalid_token() {
+        let stub_token_verifier = FakeTokenVerifier::new(Some(claims("example@example.com")));
+        let mut mock_user_repository = setup_repository();
+        let mut stub_revoked_token_repository = FakeRevokedTokenRepository::new();
+        stub_revoked_token_repository
+            .data
+            .insert("token_id".to_string(), 1747723336);
+        let verify_email = VerifyEmailUseCase::new(
+            &stub_token_verifier,
+            &mut mock_user_repository,
+            &mut stub_revoked_token_repository,
+            fake_get_timestamp,
+        );
+
+        let result = verify_email.execute("verification_token");
+
+        assert!(result.is_err());
+        assert!(!mock_user_repository.data[USER_ID].email_verified);
+    }
+
+    #[test]
+    fn execute_given_invalid_or_unbound_token_should_return_invalid_token() {
+        let unbound_claims = TokenClaims {
+            email: None,
+            ..claims("example@example.com")
+        };
+        let test_cases = vec![None, Some(unbound_claims)];
+
+        for to_return in test_cases {
+            let stub_token_verifier = FakeTokenVerifier::new(to_return);
+            let mut mock_user_repository = setup_repository();
+            let mut stub_revoked_token_repository = FakeRevokedTokenRepository::new();
+            let verify_email = VerifyEmailUseCase::new(
+                &stub_token_verifier,
+                &mut mock_user_repository,
+                &mut stub_revoked_token_repository,
+                fake_get_timestamp,
+            );
+
+            let result = verify_email.execute("verification_token");
+
+            assert!(result.is_err());
+            assert!(!mock_user_repository.data[USER_ID].email_verified);
+        }
+    }
+}
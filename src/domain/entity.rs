@@ -1,9 +1,80 @@
-use super::value_object::EmailAddress;
+use super::value_object::{EmailAddress, UserId};
 
+#[derive(Clone)]
 pub struct User {
+    pub id: UserId,
     pub email: EmailAddress,
+    pub email_verified: bool,
+    pub pending_email: Option<EmailAddress>,
     pub username: String,
     pub password: String,
     pub create_at: u64,
     pub update_at: u64,
 }
+
+#[derive(Clone)]
+pub struct RefreshTokenFamily {
+    pub id: String,
+    pub subject: String,
+    pub current_token_id: String,
+    pub revoked: bool,
+}
+
+#[derive(Clone)]
+pub struct PasswordResetToken {
+    pub token_hash: String,
+    pub user_id: UserId,
+    pub expire_at: u64,
+}
+
+#[derive(Clone)]
+pub struct TotpCredential {
+    pub user_id: UserId,
+    pub secret: String,
+    pub confirmed: bool,
+    pub recovery_codes: Vec<String>,
+    pub last_used_step: u64,
+}
+
+#[derive(Clone)]
+pub struct PasskeyCredential {
+    pub id: String,
+    pub user_id: UserId,
+    pub public_key: String,
+    pub sign_count: u32,
+    pub create_at: u64,
+}
+
+#[derive(Clone)]
+pub struct PasskeyChallenge {
+    pub challenge: String,
+    pub user_id: Option<UserId>,
+    pub expire_at: u64,
+}
+
+#[derive(Clone)]
+pub struct OAuthClient {
+    pub id: String,
+    pub name: String,
+    pub redirect_uris: Vec<String>,
+    pub secret_hash: Option<String>,
+    pub scopes: Vec<String>,
+}
+
+#[derive(Clone)]
+pub struct AuthorizationCode {
+    pub code_hash: String,
+    pub client_id: String,
+    pub user_id: UserId,
+    pub redirect_uri: String,
+    pub code_challenge: String,
+    pub expire_at: u64,
+}
+
+#[derive(Clone)]
+pub struct LoginAttempt {
+    pub key: String,
+    pub failures: u32,
+    pub blocked_until: u64,
+    pub expire_at: u64,
+}
@@ -3,18 +3,31 @@ use std::{error::Error, fmt::Display};
 #[derive(Debug)]
 pub struct ValidationError {
     message: String,
+    violations: Vec<String>,
 }
 
 impl ValidationError {
     pub fn new(massage: &str) -> Self {
         ValidationError {
             message: massage.to_string(),
+            violations: Vec::new(),
+        }
+    }
+
+    pub fn with_violations(message: &str, violations: Vec<String>) -> Self {
+        ValidationError {
+            message: message.to_string(),
+            violations,
         }
     }
 
     pub fn message(&self) -> &str {
         self.message.as_str()
     }
+
+    pub fn violations(&self) -> &[String] {
+        &self.violations
+    }
 }
 
 impl Error for ValidationError {}
@@ -46,3 +59,79 @@ impl Display for EntityNotExist {
         write!(f, "Entity do not exist")
     }
 }
+
+#[derive(Debug)]
+pub enum UpdateUserError {
+    NotExist,
+    EmailConflict,
+}
+
+impl Error for UpdateUserError {}
+
+impl Display for UpdateUserError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            UpdateUserError::NotExist => write!(f, "User do not exist"),
+            UpdateUserError::EmailConflict => write!(f, "Email already used by another user"),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct InvalidToken {}
+
+impl Error for InvalidToken {}
+
+impl Display for InvalidToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Token is invalid")
+    }
+}
+
+#[derive(Debug)]
+pub struct RefreshTokenReused {}
+
+impl Error for RefreshTokenReused {}
+
+impl Display for RefreshTokenReused {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Refresh token was already used")
+    }
+}
+
+#[derive(Debug)]
+pub struct SignCountChanged {}
+
+impl Error for SignCountChanged {}
+
+impl Display for SignCountChanged {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Passkey sign count was changed by another sign-in")
+    }
+}
+
+#[derive(Debug)]
+pub struct MailDeliveryFailed {
+    pub reason: String,
+}
+
+impl Error for MailDeliveryFailed {}
+
+impl Display for MailDeliveryFailed {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Mail delivery failed: {}", self.reason)
+    }
+}
+
+#[derive(Debug)]
+pub struct InvalidPasskeyResponse {
+    pub reason: &'static str,
+}
+
+impl Error for InvalidPasskeyResponse {}
+
+impl Display for InvalidPasskeyResponse {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Passkey response is invalid: {}", self.reason)
+    }
+}
@@ -1,7 +1,115 @@
-use super::{entity::User, error, value_object::EmailAddress};
+use super::{
+    entity::{
+        AuthorizationCode, LoginAttempt, OAuthClient, PasskeyChallenge, PasskeyCredential,
+        PasswordResetToken, RefreshTokenFamily, TotpCredential, User,
+    },
+    error,
+    value_object::{EmailAddress, LockoutRule, UserId},
+};
 
 pub trait UserRepository {
     fn create(&mut self, user: User) -> Result<(), error::EntityConflict>;
 
     fn get(&self, email: EmailAddress) -> Result<User, error::EntityNotExist>;
+
+    fn get_by_id(&self, id: &UserId) -> Result<User, error::EntityNotExist>;
+
+    fn get_by_username(&self, username: &str) -> Result<User, error::EntityNotExist>;
+
+    fn list(&self, offset: usize, limit: usize) -> Vec<User>;
+
+    fn update(&mut self, user: User) -> Result<(), error::UpdateUserError>;
+
+    fn update_password_if_unchanged(
+        &mut self,
+        id: &UserId,
+        expected_password: &str,
+        password: &str,
+    ) -> bool;
+
+    fn delete(&mut self, id: &UserId) -> Result<(), error::EntityNotExist>;
+}
+
+pub trait RefreshTokenFamilyRepository {
+    fn create(&mut self, family: RefreshTokenFamily) -> Result<(), error::EntityConflict>;
+
+    fn get(&self, id: &str) -> Result<RefreshTokenFamily, error::EntityNotExist>;
+
+    fn update(&mut self, family: RefreshTokenFamily) -> Result<(), error::EntityNotExist>;
+
+    fn rotate(
+        &mut self,
+        id: &str,
+        expected_token_id: &str,
+        new_token_id: &str,
+    ) -> Result<(), error::RefreshTokenReused>;
+
+    fn revoke_by_subject(&mut self, subject: &str);
+}
+
+pub trait RevokedTokenRepository {
+    fn revoke(&mut self, token_id: &str, expire_at: u64);
+
+    fn revoke_if_absent(&mut self, token_id: &str, expire_at: u64) -> bool;
+
+    fn is_revoked(&self, token_id: &str) -> bool;
+}
+
+pub trait LoginAttemptRepository {
+    fn locked_until(&self, key: &str, now: u64) -> Option<u64>;
+
+    fn record_failure(&mut self, key: &str, rule: &LockoutRule, now: u64) -> LoginAttempt;
+
+    fn delete(&mut self, key: &str);
+}
+
+pub trait PasswordResetTokenRepository {
+    fn create(&mut self, token: PasswordResetToken) -> Result<(), error::EntityConflict>;
+
+    fn get(&self, token_hash: &str) -> Result<PasswordResetToken, error::EntityNotExist>;
+
+    fn take(&mut self, token_hash: &str) -> Result<PasswordResetToken, error::EntityNotExist>;
+}
+
+pub trait TotpCredentialRepository {
+    fn get(&self, user_id: &UserId) -> Result<TotpCredential, error::EntityNotExist>;
+
+    fn save(&mut self, credential: TotpCredential);
+
+    fn advance_step(&mut self, user_id: &UserId, step: u64) -> bool;
+
+    fn consume_recovery_code(&mut self, user_id: &UserId, recovery_code_hash: &str) -> bool;
+}
+
+pub trait PasskeyCredentialRepository {
+    fn create(&mut self, credential: PasskeyCredential) -> Result<(), error::EntityConflict>;
+
+    fn get(&self, id: &str) -> Result<PasskeyCredential, error::EntityNotExist>;
+
+    fn list_by_user(&self, user_id: &UserId) -> Vec<PasskeyCredential>;
+
+    fn update_sign_count(
+        &mut self,
+        id: &str,
+        expected_sign_count: u32,
+        sign_count: u32,
+    ) -> Result<(), error::SignCountChanged>;
+}
+
+pub trait PasskeyChallengeRepository {
+    fn create(&mut self, challenge: PasskeyChallenge) -> Result<(), error::EntityConflict>;
+
+    fn take(&mut self, challenge: &str) -> Result<PasskeyChallenge, error::EntityNotExist>;
+}
+
+pub trait OAuthClientRepository {
+    fn get(&self, id: &str) -> Result<OAuthClient, error::EntityNotExist>;
+
+    fn save(&mut self, client: OAuthClient);
+}
+
+pub trait AuthorizationCodeRepository {
+    fn create(&mut self, code: AuthorizationCode) -> Result<(), error::EntityConflict>;
+
+    fn take(&mut self, code_hash: &str) -> Result<AuthorizationCode, error::EntityNotExist>;
 }
@@ -1,5 +1,27 @@
-use super::error::ValidationError;
+use super::{entity::LoginAttempt, error::ValidationError};
+use uuid::Uuid;
 
+#[derive(Clone, PartialEq)]
+pub struct UserId {
+    id: String,
+}
+
+impl UserId {
+    pub fn as_str(&self) -> &str {
+        self.id.as_str()
+    }
+
+    pub fn new(id: &str) -> Result<Self, ValidationError> {
+        match Uuid::parse_str(id) {
+            Ok(uuid) => Ok(UserId {
+                id: uuid.hyphenated().to_string(),
+            }),
+            Err(_) => Err(ValidationError::new("Invalid user id")),
+        }
+    }
+}
+
+#[derive(Clone)]
 pub struct EmailAddress {
     address: String,
 }
@@ -58,6 +80,125 @@ impl EmailAddress {
     }
 }
 
+const COMMON_PASSWORDS: &str = include_str!("common_passwords.txt");
+
+pub struct PasswordPolicy {
+    pub min_length: usize,
+    pub max_length: usize,
+    pub require_lowercase: bool,
+    pub require_uppercase: bool,
+    pub require_digit: bool,
+    pub require_symbol: bool,
+    pub forbid_common: bool,
+}
+
+pub struct LockoutRule {
+    pub max_failures: u32,
+    pub backoff_base_seconds: u64,
+    pub backoff_max_seconds: u64,
+    pub lockout_seconds: u64,
+}
+
+impl LockoutRule {
+    pub fn next_attempt(&self, key: &str, previous_failures: u32, now: u64) -> LoginAttempt {
+        let failures = previous_failures.saturating_add(1);
+        let blocked_until = if failures >= self.max_failures {
+            now + self.lockout_seconds
+        } else {
+            let backoff = self
+                .backoff_base_seconds
+                .saturating_mul(1 << (failures - 1).min(32));
+            now + backoff.min(self.backoff_max_seconds)
+        };
+
+        LoginAttempt {
+            key: key.to_string(),
+            failures,
+            blocked_until,
+            expire_at: blocked_until.max(now + self.lockout_seconds),
+        }
+    }
+}
+
+pub struct Password {
+    raw: String,
+}
+
+impl Password {
+    pub fn as_str(&self) -> &str {
+        self.raw.as_str()
+    }
+
+    pub fn new(
+        raw: &str,
+        policy: &PasswordPolicy,
+        email: &EmailAddress,
+        username: &str,
+        is_breached: bool,
+    ) -> Result<Self, ValidationError> {
+        let mut violations = Password::validate(raw, policy, email, username);
+        if is_breached {
+            violations.push("Password has appeared in a data breach".to_string());
+        }
+        if !violations.is_empty() {
+            return Err(ValidationError::with_violations(
+                "Password does not satisfy the password policy",
+                violations,
+            ));
+        }
+        Ok(Password {
+            raw: raw.to_string(),
+        })
+    }
+
+    fn validate(
+        raw: &str,
+        policy: &PasswordPolicy,
+        email: &EmailAddress,
+        username: &str,
+    ) -> Vec<String> {
+        let mut violations = Vec::new();
+        let length = raw.chars().count();
+
+        if length < policy.min_length {
+            violations.push(format!(
+                "Password must be at least {} characters",
+                policy.min_length
+            ));
+        }
+        if length > policy.max_length {
+            violations.push(format!(
+                "Password must be at most {} characters",
+                policy.max_length
+            ));
+        }
+        if policy.require_lowercase && !raw.chars().any(|c| c.is_lowercase()) {
+            violations.push("Password must contain a lowercase letter".to_string());
+        }
+        if policy.require_uppercase && !raw.chars().any(|c| c.is_uppercase()) {
+            violations.push("Password must contain an uppercase letter".to_string());
+        }
+        if policy.require_digit && !raw.chars().any(|c| c.is_ascii_digit()) {
+            violations.push("Password must contain a digit".to_string());
+        }
+        if policy.require_symbol && raw.chars().all(|c| c.is_alphanumeric()) {
+            violations.push("Password must contain a symbol".to_string());
+        }
+        if raw.eq_ignore_ascii_case(email.as_str()) || raw.eq_ignore_ascii_case(username) {
+            violations.push("Password must not match the email address or username".to_string());
+        }
+        if policy.forbid_common
+            && COMMON_PASSWORDS
+                .lines()
+                .any(|common| raw.eq_ignore_ascii_case(common))
+        {
+            violations.push("Password is too common".to_string());
+        }
+
+        violations
+    }
+}
+
 #[cfg(test)]
 mod test_email_address {
     use super::EmailAddress;
@@ -96,3 +237,137 @@ mod test_email_address {
         }
     }
 }
+
+#[cfg(test)]
+mod test_user_id {
+    use super::UserId;
+
+    #[test]
+    fn new_given_uuid_should_return_instance() {
+        let user_id = UserId::new("0196e2a4-5c1b-4f7e-9a3d-2b8c6d4e1f00");
+
+        assert!(user_id.is_ok_and(|id| id.as_str() == "0196e2a4-5c1b-4f7e-9a3d-2b8c6d4e1f00"));
+    }
+
+    #[test]
+    fn new_given_uppercase_uuid_should_normalize_to_lowercase() {
+        let user_id = UserId::new("0196E2A4-5C1B-4F7E-9A3D-2B8C6D4E1F00");
+
+        assert!(user_id.is_ok_and(|id| id.as_str() == "0196e2a4-5c1b-4f7e-9a3d-2b8c6d4e1f00"));
+    }
+
+    #[test]
+    fn new_given_invalid_uuid_should_return_error() {
+        let test_cases = vec!["", "foo", "0196e2a4-5c1b-4f7e-9a3d"];
+
+        for test_case in test_cases {
+            assert!(UserId::new(test_case).is_err());
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_password {
+    use super::{EmailAddress, Password, PasswordPolicy};
+
+    fn policy() -> PasswordPolicy {
+        PasswordPolicy {
+            min_length: 8,
+            max_length: 64,
+            require_lowercase: true,
+            require_uppercase: true,
+            require_digit: true,
+            require_symbol: true,
+            forbid_common: true,
+        }
+    }
+
+    fn email() -> EmailAddress {
+        EmailAddress::new("example@example.com").unwrap()
+    }
+
+    #[test]
+    fn new_given_password_satisfying_policy_should_return_instance() {
+        let password = Password::new("Correct-Horse-7", &policy(), &email(), "foo", false);
+
+        assert!(password.is_ok_and(|p| p.as_str() == "Correct-Horse-7"));
+    }
+
+    #[test]
+    fn new_given_password_violating_policy_should_return_all_violations() {
+        let password = Password::new("abc", &policy(), &email(), "foo", false);
+
+        assert!(password.is_err_and(|err| err.violations()
+            == [
+                "Password must be at least 8 characters",
+                "Password must contain an uppercase letter",
+                "Password must contain a digit",
+                "Password must contain a symbol",
+            ]));
+    }
+
+    #[test]
+    fn new_given_too_long_password_should_return_error() {
+        let password = Password::new(&"Aa1-".repeat(17), &policy(), &email(), "foo", false);
+
+        assert!(
+            password
+                .is_err_and(|err| err.violations() == ["Password must be at most 64 characters"])
+        );
+    }
+
+    #[test]
+    fn new_given_password_equal_to_email_or_username_should_return_error() {
+        let test_cases = vec![
+            ("Example@Example.com", "foo"),
+            ("Foo-Bar-1234", "foo-bar-1234"),
+        ];
+
+        for (raw, username) in test_cases {
+            let password = Password::new(
+                raw,
+                &PasswordPolicy {
+                    require_uppercase: false,
+                    require_digit: false,
+                    ..policy()
+                },
+                &email(),
+                username,
+                false,
+            );
+
+            assert!(
+                password.is_err_and(|err| err.violations()
+                    == ["Password must not match the email address or username"])
+            );
+        }
+    }
+
+    #[test]
+    fn new_given_common_password_should_return_error() {
+        let password = Password::new(
+            "PASSWORD123",
+            &PasswordPolicy {
+                require_lowercase: false,
+                require_symbol: false,
+                ..policy()
+            },
+            &email(),
+            "foo",
+            false,
+        );
+
+        assert!(password.is_err_and(|err| err.violations() == ["Password is too common"]));
+    }
+
+    #[test]
+    fn new_given_breached_password_should_return_error_with_other_violations() {
+        let password = Password::new("Correct-Horse", &policy(), &email(), "foo", true);
+
+        assert!(password.is_err_and(|err| err.violations()
+            == [
+                "Password must contain a digit",
+                "Password has appeared in a data breach",
+            ]));
+    }
+}
@@ -0,0 +1,113 @@
+use crate::application::service::auth::BreachedPasswordChecker;
+use sha1::{Digest, Sha1};
+use std::{fs, path::PathBuf};
+
+const PREFIX_LENGTH: usize = 5;
+
+pub struct PwnedPasswordsCorpus {
+    dir: Option<PathBuf>,
+}
+
+impl PwnedPasswordsCorpus {
+    pub fn new(dir: Option<PathBuf>) -> Self {
+        PwnedPasswordsCorpus { dir }
+    }
+
+    fn read_bucket(&self, prefix: &str) -> Option<String> {
+        let dir = self.dir.as_ref()?;
+        fs::read_to_string(dir.join(format!("{}.txt", prefix)))
+            .or_else(|_| fs::read_to_string(dir.join(prefix)))
+            .ok()
+    }
+}
+
+impl BreachedPasswordChecker for PwnedPasswordsCorpus {
+    fn is_breached(&self, raw: &str) -> bool {
+        let digest: String = Sha1::digest(raw.as_bytes())
+            .iter()
+            .map(|byte| format!("{:02X}", byte))
+            .collect();
+        let (prefix, suffix) = digest.split_at(PREFIX_LENGTH);
+        let bucket = match self.read_bucket(prefix) {
+            Some(bucket) => bucket,
+            None => return false,
+        };
+
+        bucket
+            .lines()
+            .any(|line| match line.trim().split_once(':') {
+                Some((candidate, count)) => {
+                    candidate.eq_ignore_ascii_case(suffix)
+                        && count.trim().parse::<u64>().is_ok_and(|count| count > 0)
+                }
+                None => false,
+            })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_support::infratructure::temp_dir::TempDir;
+
+    const PASSWORD_SUFFIX: &str = "1E4C9B93F3F0682250B6CF8331B7EE68FD8";
+
+    fn create_dir(name: &str, files: &[(&str, &str)]) -> TempDir {
+        let dir = TempDir::new(&format!("pwned_{}", name));
+        for (file, content) in files {
+            fs::write(dir.path().join(file), content).unwrap();
+        }
+        dir
+    }
+
+    #[test]
+    fn is_breached_given_password_in_bucket_should_return_true() {
+        let test_cases = vec![("txt", "5BAA6.txt"), ("bare", "5BAA6")];
+
+        for (name, file) in test_cases {
+            let dir = create_dir(
+                name,
+                &[(
+                    file,
+                    &format!(
+                        "003D68EB55068C33ACE09247EE4C639306B:3\r\n{}:10434004\r\n",
+                        PASSWORD_SUFFIX
+                    ),
+                )],
+            );
+            let corpus = PwnedPasswordsCorpus::new(Some(dir.path().to_path_buf()));
+
+            assert!(corpus.is_breached("password"));
+        }
+    }
+
+    #[test]
+    fn is_breached_given_password_not_in_bucket_should_return_false() {
+        let dir = create_dir(
+            "absent",
+            &[("5BAA6.txt", "003D68EB55068C33ACE09247EE4C639306B:3\n")],
+        );
+        let corpus = PwnedPasswordsCorpus::new(Some(dir.path().to_path_buf()));
+
+        assert!(!corpus.is_breached("password"));
+    }
+
+    #[test]
+    fn is_breached_given_padding_entry_should_return_false() {
+        let dir = create_dir(
+            "padding",
+            &[("5BAA6.txt", &format!("{}:0\n", PASSWORD_SUFFIX))],
+        );
+        let corpus = PwnedPasswordsCorpus::new(Some(dir.path().to_path_buf()));
+
+        assert!(!corpus.is_breached("password"));
+    }
+
+    #[test]
+    fn is_breached_given_missing_bucket_or_corpus_should_return_false() {
+        let dir = create_dir("missing", &[]);
+
+        assert!(!PwnedPasswordsCorpus::new(Some(dir.path().to_path_buf())).is_breached("password"));
+        assert!(!PwnedPasswordsCorpus::new(None).is_breached("password"));
+    }
+}
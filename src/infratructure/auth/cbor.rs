@@ -0,0 +1,202 @@
+const MAX_DEPTH: usize = 16;
+
+#[derive(Debug, PartialEq)]
+pub enum CborValue {
+    Integer(i64),
+    Bytes(Vec<u8>),
+    Text(String),
+    Array(Vec<CborValue>),
+    Map(Vec<(CborValue, CborValue)>),
+    Bool(bool),
+    Null,
+}
+
+impl CborValue {
+    pub fn get(&self, key: &CborValue) -> Option<&CborValue> {
+        match self {
+            CborValue::Map(entries) => entries.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    pub fn get_int(&self, key: i64) -> Option<&CborValue> {
+        self.get(&CborValue::Integer(key))
+    }
+
+    pub fn get_text(&self, key: &str) -> Option<&CborValue> {
+        self.get(&CborValue::Text(key.to_string()))
+    }
+
+    pub fn as_integer(&self) -> Option<i64> {
+        match self {
+            CborValue::Integer(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn as_bytes(&self) -> Option<&[u8]> {
+        match self {
+            CborValue::Bytes(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn as_text(&self) -> Option<&str> {
+        match self {
+            CborValue::Text(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+pub fn decode(bytes: &[u8]) -> Option<(CborValue, usize)> {
+    let mut decoder = Decoder { bytes, position: 0 };
+    let value = decoder.value(0)?;
+
+    Some((value, decoder.position))
+}
+
+struct Decoder<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl Decoder<'_> {
+    fn take(&mut self, len: usize) -> Option<&[u8]> {
+        let end = self.position.checked_add(len)?;
+        let slice = self.bytes.get(self.position..end)?;
+        self.position = end;
+        Some(slice)
+    }
+
+    fn argument(&mut self, additional: u8) -> Option<u64> {
+        match additional {
+            0..=23 => Some(additional as u64),
+            24 => Some(self.take(1)?[0] as u64),
+            25 => Some(u16::from_be_bytes(self.take(2)?.try_into().ok()?) as u64),
+            26 => Some(u32::from_be_bytes(self.take(4)?.try_into().ok()?) as u64),
+            27 => Some(u64::from_be_bytes(self.take(8)?.try_into().ok()?)),
+            _ => None,
+        }
+    }
+
+    fn length(&mut self, additional: u8) -> Option<usize> {
+        let len = usize::try_from(self.argument(additional)?).ok()?;
+        if len > self.bytes.len() - self.position {
+            return None;
+        }
+        Some(len)
+    }
+
+    fn value(&mut self, depth: usize) -> Option<CborValue> {
+        if depth > MAX_DEPTH {
+            return None;
+        }
+        let initial = self.take(1)?[0];
+        let (major, additional) = (initial >> 5, initial & 0x1f);
+
+        match major {
+            0 => Some(CborValue::Integer(
+                i64::try_from(self.argument(additional)?).ok()?,
+            )),
+            1 => Some(CborValue::Integer(
+                -1 - i64::try_from(self.argument(additional)?).ok()?,
+            )),
+            2 => {
+                let len = self.length(additional)?;
+                Some(CborValue::Bytes(self.take(len)?.to_vec()))
+            }
+            3 => {
+                let len = self.length(additional)?;
+                Some(CborValue::Text(
+                    String::from_utf8(self.take(len)?.to_vec()).ok()?,
+                ))
+            }
+            4 => {
+                let len = self.length(additional)?;
+                (0..len)
+                    .map(|_| self.value(depth + 1))
+                    .collect::<Option<Vec<_>>>()
+                    .map(CborValue::Array)
+            }
+            5 => {
+                let len = self.length(additional)?;
+                (0..len)
+                    .map(|_| Some((self.value(depth + 1)?, self.value(depth + 1)?)))
+                    .collect::<Option<Vec<_>>>()
+                    .map(CborValue::Map)
+            }
+            7 => match additional {
+                20 => Some(CborValue::Bool(false)),
+                21 => Some(CborValue::Bool(true)),
+                22 => Some(CborValue::Null),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn decode_given_rfc_8949_examples_should_return_value() {
+        let test_cases = vec![
+            (vec![0x00], CborValue::Integer(0)),
+            (vec![0x17], CborValue::Integer(23)),
+            (vec![0x18, 0x64], CborValue::Integer(100)),
+            (vec![0x19, 0x03, 0xe8], CborValue::Integer(1000)),
+            (vec![0x20], CborValue::Integer(-1)),
+            (vec![0x38, 0x63], CborValue::Integer(-100)),
+            (
+                vec![0x43, 0x01, 0x02, 0x03],
+                CborValue::Bytes(vec![1, 2, 3]),
+            ),
+            (vec![0x62, 0x49, 0x45], CborValue::Text("IE".to_string())),
+            (
+                vec![0x82, 0x01, 0x02],
+                CborValue::Array(vec![CborValue::Integer(1), CborValue::Integer(2)]),
+            ),
+            (
+                vec![0xa1, 0x61, 0x61, 0x01],
+                CborValue::Map(vec![(
+                    CborValue::Text("a".to_string()),
+                    CborValue::Integer(1),
+                )]),
+            ),
+            (vec![0xf5], CborValue::Bool(true)),
+            (vec![0xf6], CborValue::Null),
+        ];
+
+        for (bytes, expected) in test_cases {
+            assert_eq!(decode(&bytes), Some((expected, bytes.len())));
+        }
+    }
+
+    #[test]
+    fn decode_given_trailing_bytes_should_report_consumed_length() {
+        let (value, consumed) = decode(&[0xa1, 0x01, 0x02, 0xff, 0xff]).unwrap();
+
+        assert_eq!(value.get_int(1), Some(&CborValue::Integer(2)));
+        assert_eq!(consumed, 3);
+    }
+
+    #[test]
+    fn decode_given_malformed_input_should_return_none() {
+        let test_cases = vec![
+            vec![],
+            vec![0x18],
+            vec![0x43, 0x01],
+            vec![0x5b, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff],
+            vec![0x9f, 0x01, 0xff],
+            vec![0x62, 0xff, 0xfe],
+            vec![0x81; MAX_DEPTH + 2],
+        ];
+
+        for bytes in test_cases {
+            assert_eq!(decode(&bytes), None);
+        }
+    }
+}
@@ -0,0 +1,320 @@
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use hmac::{Hmac, Mac};
+use rsa::{
+    RsaPrivateKey,
+    pkcs1::DecodeRsaPrivateKey,
+    pkcs1v15,
+    pkcs8::DecodePrivateKey,
+    signature::{Keypair, SignatureEncoding, Signer, Verifier},
+    traits::PublicKeyParts,
+};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::{error::Error, fmt::Display};
+
+pub struct SigningKey {
+    kid: Option<String>,
+    material: KeyMaterial,
+}
+
+enum KeyMaterial {
+    Hmac(Vec<u8>),
+    Rsa(Box<pkcs1v15::SigningKey<Sha256>>),
+    Ecdsa(p256::ecdsa::SigningKey),
+    Ed25519(ed25519_dalek::SigningKey),
+}
+
+impl SigningKey {
+    pub fn hmac(secret: &[u8], kid: Option<String>) -> Self {
+        SigningKey {
+            kid,
+            material: KeyMaterial::Hmac(secret.to_vec()),
+        }
+    }
+
+    pub fn from_pem(pem: &str, kid: Option<String>) -> Result<Self, InvalidKey> {
+        let material = if let Ok(key) = RsaPrivateKey::from_pkcs8_pem(pem) {
+            KeyMaterial::Rsa(Box::new(pkcs1v15::SigningKey::new(key)))
+        } else if let Ok(key) = p256::ecdsa::SigningKey::from_pkcs8_pem(pem) {
+            KeyMaterial::Ecdsa(key)
+        } else if let Ok(key) = ed25519_dalek::SigningKey::from_pkcs8_pem(pem) {
+            KeyMaterial::Ed25519(key)
+        } else if let Ok(key) = RsaPrivateKey::from_pkcs1_pem(pem) {
+            KeyMaterial::Rsa(Box::new(pkcs1v15::SigningKey::new(key)))
+        } else if let Ok(key) = p256::SecretKey::from_sec1_pem(pem) {
+            KeyMaterial::Ecdsa(key.into())
+        } else {
+            return Err(InvalidKey {});
+        };
+
+        Ok(SigningKey { kid, material })
+    }
+
+    pub fn with_default_kid(self) -> Self {
+        let kid = self
+            .kid
+            .clone()
+            .or_else(|| self.public_jwk().map(|jwk| jwk.thumbprint()));
+        SigningKey { kid, ..self }
+    }
+
+    pub fn kid(&self) -> Option<&str> {
+        self.kid.as_deref()
+    }
+
+    pub fn algorithm(&self) -> &'static str {
+        match self.material {
+            KeyMaterial::Hmac(_) => "HS256",
+            KeyMaterial::Rsa(_) => "RS256",
+            KeyMaterial::Ecdsa(_) => "ES256",
+            KeyMaterial::Ed25519(_) => "EdDSA",
+        }
+    }
+
+    pub fn sign(&self, message: &[u8]) -> Vec<u8> {
+        match &self.material {
+            KeyMaterial::Hmac(secret) => {
+                let mut mac: Hmac<Sha256> = Hmac::new_from_slice(secret).unwrap();
+                mac.update(message);
+                mac.finalize().into_bytes().to_vec()
+            }
+            KeyMaterial::Rsa(key) => key.sign(message).to_vec(),
+            KeyMaterial::Ecdsa(key) => {
+                let signature: p256::ecdsa::Signature = key.sign(message);
+                signature.to_bytes().to_vec()
+            }
+            KeyMaterial::Ed25519(key) => key.sign(message).to_bytes().to_vec(),
+        }
+    }
+
+    pub fn verify(&self, message: &[u8], signature: &[u8]) -> bool {
+        match &self.material {
+            KeyMaterial::Hmac(secret) => {
+                let mut mac: Hmac<Sha256> = Hmac::new_from_slice(secret).unwrap();
+                mac.update(message);
+                mac.verify_slice(signature).is_ok()
+            }
+            KeyMaterial::Rsa(key) => pkcs1v15::Signature::try_from(signature)
+                .is_ok_and(|s| key.verifying_key().verify(message, &s).is_ok()),
+            KeyMaterial::Ecdsa(key) => p256::ecdsa::Signature::from_slice(signature)
+                .is_ok_and(|s| key.verifying_key().verify(message, &s).is_ok()),
+            KeyMaterial::Ed25519(key) => ed25519_dalek::Signature::from_slice(signature)
+                .is_ok_and(|s| key.verifying_key().verify(message, &s).is_ok()),
+        }
+    }
+
+    pub fn public_jwk(&self) -> Option<Jwk> {
+        let jwk = Jwk {
+            kty: "",
+            kid: self.kid.clone(),
+            use_: "sig",
+            alg: self.algorithm(),
+            n: None,
+            e: None,
+            crv: None,
+            x: None,
+            y: None,
+        };
+        match &self.material {
+            KeyMaterial::Hmac(_) => None,
+            KeyMaterial::Rsa(key) => {
+                let private_key: &RsaPrivateKey = (**key).as_ref();
+                Some(Jwk {
+                    kty: "RSA",
+                    n: Some(URL_SAFE_NO_PAD.encode(private_key.n().to_bytes_be())),
+                    e: Some(URL_SAFE_NO_PAD.encode(private_key.e().to_bytes_be())),
+                    ..jwk
+                })
+            }
+            KeyMaterial::Ecdsa(key) => {
+                let point = key.verifying_key().to_encoded_point(false);
+                Some(Jwk {
+                    kty: "EC",
+                    crv: Some("P-256"),
+                    x: point.x().map(|x| URL_SAFE_NO_PAD.encode(x)),
+                    y: point.y().map(|y| URL_SAFE_NO_PAD.encode(y)),
+                    ..jwk
+                })
+            }
+            KeyMaterial::Ed25519(key) => Some(Jwk {
+                kty: "OKP",
+                crv: Some("Ed25519"),
+                x: Some(URL_SAFE_NO_PAD.encode(key.verifying_key().to_bytes())),
+                ..jwk
+            }),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct Jwk {
+    pub kty: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub kid: Option<String>,
+    #[serde(rename = "use")]
+    pub use_: &'static str,
+    pub alg: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub n: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub e: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub crv: Option<&'static str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub x: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub y: Option<String>,
+}
+
+impl Jwk {
+    pub fn thumbprint(&self) -> String {
+        let members = match self.kty {
+            "RSA" => format!(
+                r#"{{"e":"{}","kty":"RSA","n":"{}"}}"#,
+                self.e.as_deref().unwrap_or_default(),
+                self.n.as_deref().unwrap_or_default()
+            ),
+            "EC" => format!(
+                r#"{{"crv":"{}","kty":"EC","x":"{}","y":"{}"}}"#,
+                self.crv.unwrap_or_default(),
+                self.x.as_deref().unwrap_or_default(),
+                self.y.as_deref().unwrap_or_default()
+            ),
+            kty => format!(
+                r#"{{"crv":"{}","kty":"{}","x":"{}"}}"#,
+                self.crv.unwrap_or_default(),
+                kty,
+                self.x.as_deref().unwrap_or_default()
+            ),
+        };
+        URL_SAFE_NO_PAD.encode(Sha256::digest(members))
+    }
+}
+
+#[derive(Debug)]
+pub struct InvalidKey {}
+
+impl Error for InvalidKey {}
+
+impl Display for InvalidKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Key is not a supported RSA, P-256 or Ed25519 private key"
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_support::infratructure::key::{EC_PEM, ED25519_PEM, RSA_PEM};
+
+    const RFC_7638_N: &str = "0vx7agoebGcQSuuPiLJXZptN9nndrQmbXEps2aiAFbWhM78LhWx4cbbfAAtVT86zwu1RK7aPFFxuhDR1L6tSoc_BJECPebWKRXjBZCiFV4n3oknjhMstn64tZ_2W-5JsGY4Hc5n9yBXArwl93lqt7_RN5w6Cf0h4QyQ5v-65YGjQR0_FDW2QvzqY368QQMicAtaSqzs8KJZgnYb9c7d0zgdAZHzu6qMQvRL5hajrn1n91CbOpbISD08qNLyrdkt-bFTWhAI4vMQFh6WeZu0fM4lFd2NcRwr3XPksINHaQ-G_xBniIqbw0Ls1jF44-csFCur-kEgU8awapJzKnqDKgw";
+
+    #[test]
+    fn from_pem_given_private_keys_should_detect_algorithm() {
+        let test_cases = vec![
+            (RSA_PEM, "RS256"),
+            (EC_PEM, "ES256"),
+            (ED25519_PEM, "EdDSA"),
+        ];
+
+        for (pem, algorithm) in test_cases {
+            let key = SigningKey::from_pem(pem, None).unwrap();
+            assert_eq!(key.algorithm(), algorithm);
+        }
+    }
+
+    #[test]
+    fn from_pem_given_invalid_pem_should_return_invalid_key() {
+        let key = SigningKey::from_pem("not a pem", None);
+
+        assert!(key.is_err_and(|err| matches!(err, InvalidKey {})));
+    }
+
+    #[test]
+    fn verify_given_signed_message_should_return_is_valid() {
+        let keys = vec![
+            SigningKey::hmac(b"secret", None),
+            SigningKey::from_pem(RSA_PEM, None).unwrap(),
+            SigningKey::from_pem(EC_PEM, None).unwrap(),
+            SigningKey::from_pem(ED25519_PEM, None).unwrap(),
+        ];
+
+        for key in keys {
+            let signature = key.sign(b"message");
+            assert!(key.verify(b"message", &signature));
+            assert!(!key.verify(b"tampered", &signature));
+        }
+    }
+
+    #[test]
+    fn public_jwk_given_asymmetric_key_should_return_public_parameters() {
+        let rsa = SigningKey::from_pem(RSA_PEM, Some("rsa".to_string()))
+            .unwrap()
+            .public_jwk()
+            .unwrap();
+        let ec = SigningKey::from_pem(EC_PEM, None)
+            .unwrap()
+            .public_jwk()
+            .unwrap();
+        let ed25519 = SigningKey::from_pem(ED25519_PEM, None)
+            .unwrap()
+            .public_jwk()
+            .unwrap();
+
+        assert!(rsa.kty == "RSA" && rsa.kid.as_deref() == Some("rsa") && rsa.e.is_some());
+        assert!(ec.kty == "EC" && ec.crv == Some("P-256") && ec.y.is_some());
+        assert!(ed25519.kty == "OKP" && ed25519.crv == Some("Ed25519") && ed25519.x.is_some());
+    }
+
+    #[test]
+    fn thumbprint_given_rsa_jwk_should_match_rfc_7638_example() {
+        let jwk = Jwk {
+            kty: "RSA",
+            kid: None,
+            use_: "sig",
+            alg: "RS256",
+            n: Some(RFC_7638_N.to_string()),
+            e: Some("AQAB".to_string()),
+            crv: None,
+            x: None,
+            y: None,
+        };
+
+        assert_eq!(
+            jwk.thumbprint(),
+            "NzbLsXh8uDCcd-6MNwXF4W_7noWXFZAfHkxZsRGC9Xs"
+        );
+    }
+
+    #[test]
+    fn with_default_kid_given_no_kid_should_use_jwk_thumbprint() {
+        let keys = vec![
+            SigningKey::from_pem(RSA_PEM, None).unwrap(),
+            SigningKey::from_pem(EC_PEM, None).unwrap(),
+            SigningKey::from_pem(ED25519_PEM, None).unwrap(),
+        ];
+
+        for key in keys {
+            let thumbprint = key.public_jwk().unwrap().thumbprint();
+            assert_eq!(key.with_default_kid().kid(), Some(thumbprint.as_str()));
+        }
+        let named = SigningKey::from_pem(RSA_PEM, Some("rsa".to_string())).unwrap();
+        assert_eq!(named.with_default_kid().kid(), Some("rsa"));
+        assert!(
+            SigningKey::hmac(b"secret", None)
+                .with_default_kid()
+                .kid()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn public_jwk_given_hmac_key_should_return_none() {
+        let key = SigningKey::hmac(b"secret", None);
+
+        assert!(key.public_jwk().is_none());
+    }
+}
@@ -0,0 +1,137 @@
+use super::key::{InvalidKey, Jwk, SigningKey};
+use std::{fs, path::Path, sync::RwLock};
+
+const CURRENT_KEY_FILE: &str = "current";
+
+pub struct TokenKeys {
+    pub access_token: RwLock<KeyRing>,
+    pub refresh_token: RwLock<KeyRing>,
+    pub email_verification_token: RwLock<KeyRing>,
+    pub mfa_token: RwLock<KeyRing>,
+    pub magic_link_token: RwLock<KeyRing>,
+}
+
+pub struct KeyRing {
+    current: usize,
+    keys: Vec<SigningKey>,
+}
+
+impl KeyRing {
+    pub fn single(key: SigningKey) -> Self {
+        KeyRing {
+            current: 0,
+            keys: vec![key],
+        }
+    }
+
+    pub fn load_dir(dir: &Path) -> Result<Self, InvalidKey> {
+        let current_kid = fs::read_to_string(dir.join(CURRENT_KEY_FILE))
+            .map_err(|_| InvalidKey {})?
+            .trim()
+            .to_string();
+        let mut keys = Vec::new();
+        for entry in fs::read_dir(dir).map_err(|_| InvalidKey {})? {
+            let path = entry.map_err(|_| InvalidKey {})?.path();
+            let kid = path.file_stem().and_then(|s| s.to_str()).map(String::from);
+            match path.extension().and_then(|s| s.to_str()) {
+                Some("pem") => keys.push(SigningKey::from_pem(
+                    &fs::read_to_string(&path).map_err(|_| InvalidKey {})?,
+                    kid,
+                )?),
+                Some("secret") => keys.push(SigningKey::hmac(
+                    &fs::read(&path).map_err(|_| InvalidKey {})?,
+                    kid,
+                )),
+                _ => continue,
+            }
+        }
+        let current = keys
+            .iter()
+            .position(|key| key.kid() == Some(current_kid.as_str()))
+            .ok_or(InvalidKey {})?;
+
+        Ok(KeyRing { current, keys })
+    }
+
+    pub fn current(&self) -> &SigningKey {
+        &self.keys[self.current]
+    }
+
+    pub fn find(&self, kid: Option<&str>) -> Option<&SigningKey> {
+        self.keys.iter().find(|key| key.kid() == kid)
+    }
+
+    pub fn public_jwks(&self) -> Vec<Jwk> {
+        self.keys
+            .iter()
+            .filter_map(SigningKey::public_jwk)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_support::infratructure::{
+        key::{EC_PEM, RSA_PEM},
+        temp_dir::TempDir,
+    };
+
+    fn create_dir(name: &str, files: &[(&str, &str)]) -> TempDir {
+        let dir = TempDir::new(&format!("key_ring_{}", name));
+        for (file, content) in files {
+            fs::write(dir.path().join(file), content).unwrap();
+        }
+        dir
+    }
+
+    #[test]
+    fn load_dir_given_keys_should_sign_with_current_key() {
+        let dir = create_dir(
+            "current",
+            &[
+                ("current", "2026-10\n"),
+                ("2026-09.pem", RSA_PEM),
+                ("2026-10.pem", EC_PEM),
+                ("legacy.secret", "secret"),
+            ],
+        );
+
+        let key_ring = KeyRing::load_dir(dir.path()).unwrap();
+
+        assert_eq!(key_ring.current().kid(), Some("2026-10"));
+        assert_eq!(key_ring.current().algorithm(), "ES256");
+        assert!(key_ring.find(Some("2026-09")).is_some());
+        assert!(key_ring.find(Some("legacy")).is_some());
+        assert_eq!(key_ring.public_jwks().len(), 2);
+    }
+
+    #[test]
+    fn load_dir_given_unknown_current_kid_should_return_invalid_key() {
+        let dir = create_dir(
+            "unknown",
+            &[("current", "2026-11"), ("2026-10.pem", EC_PEM)],
+        );
+
+        let key_ring = KeyRing::load_dir(dir.path());
+
+        assert!(key_ring.is_err_and(|err| matches!(err, InvalidKey {})));
+    }
+
+    #[test]
+    fn load_dir_given_invalid_pem_should_return_invalid_key() {
+        let dir = create_dir("invalid", &[("current", "2026-10"), ("2026-10.pem", "bad")]);
+
+        let key_ring = KeyRing::load_dir(dir.path());
+
+        assert!(key_ring.is_err_and(|err| matches!(err, InvalidKey {})));
+    }
+
+    #[test]
+    fn find_given_unknown_kid_should_return_none() {
+        let key_ring = KeyRing::single(SigningKey::hmac(b"secret", None));
+
+        assert!(key_ring.find(None).is_some());
+        assert!(key_ring.find(Some("unknown")).is_none());
+    }
+}
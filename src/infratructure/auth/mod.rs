@@ -1,5 +1,16 @@
+mod breached;
+mod cbor;
 mod jwt;
+mod key;
+mod key_ring;
 mod password;
+mod totp;
+mod webauthn;
 
-pub use jwt::{InfraClaims, JWTIssuer};
-pub use password::{BcryptHasher, BcryptValidator};
+pub use breached::PwnedPasswordsCorpus;
+pub use jwt::{ExpectedClaims, InfraClaims, JWTIssuer, JWTVerifier};
+pub use key::{Jwk, SigningKey};
+pub use key_ring::{KeyRing, TokenKeys};
+pub use password::{Argon2Hasher, Argon2Validator};
+pub use totp::Totp;
+pub use webauthn::WebAuthn;
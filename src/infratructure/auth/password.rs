@@ -1,27 +1,76 @@
 use crate::application::service::auth::{PasswordHasher, PasswordValidator};
-use bcrypt::{hash, verify};
+use argon2::{
+    Algorithm, Argon2, Params, PasswordHash, Version,
+    password_hash::{PasswordHasher as _, PasswordVerifier as _, SaltString, rand_core::OsRng},
+};
 
-pub struct BcryptHasher {
-    round: u32,
+pub struct Argon2Hasher {
+    params: Params,
+    dummy_hash: String,
 }
 
-impl BcryptHasher {
-    pub fn new(round: u32) -> BcryptHasher {
-        BcryptHasher { round }
+impl Argon2Hasher {
+    pub fn new(memory_kib: u32, iterations: u32, parallelism: u32) -> Argon2Hasher {
+        let params = Params::new(memory_kib, iterations, parallelism, None)
+            .expect("argon2 parameters should be valid");
+        let dummy_hash =
+            Argon2Hasher::hash_with(&params, SaltString::generate(&mut OsRng).as_str());
+        Argon2Hasher { params, dummy_hash }
+    }
+
+    fn hash_with(params: &Params, raw: &str) -> String {
+        Argon2::new(Algorithm::Argon2id, Version::V0x13, params.clone())
+            .hash_password(raw.as_bytes(), &SaltString::generate(&mut OsRng))
+            .unwrap()
+            .to_string()
     }
 }
 
-impl PasswordHasher for BcryptHasher {
+impl PasswordHasher for Argon2Hasher {
     fn hash(&self, raw: &str) -> String {
-        hash(raw, self.round).unwrap()
+        Argon2Hasher::hash_with(&self.params, raw)
+    }
+
+    fn dummy_hash(&self) -> &str {
+        &self.dummy_hash
+    }
+
+    fn needs_rehash(&self, hashed: &str) -> bool {
+        let hash = match PasswordHash::new(hashed) {
+            Ok(hash) => hash,
+            Err(_) => return true,
+        };
+        if hash.algorithm != Algorithm::Argon2id.ident()
+            || hash.version != Some(Version::V0x13.into())
+        {
+            return true;
+        }
+
+        match Params::try_from(&hash) {
+            Ok(params) => {
+                params.m_cost() != self.params.m_cost()
+                    || params.t_cost() != self.params.t_cost()
+                    || params.p_cost() != self.params.p_cost()
+            }
+            Err(_) => true,
+        }
     }
 }
 
-pub struct BcryptValidator {}
+pub struct Argon2Validator {}
 
-impl PasswordValidator for BcryptValidator {
+impl PasswordValidator for Argon2Validator {
     fn verify(&self, raw: &str, hashed: &str) -> bool {
-        verify(raw, hashed).expect("hashed password from application should always be valid")
+        if hashed.starts_with("$2") {
+            return bcrypt::verify(raw, hashed)
+                .expect("hashed password from application should always be valid");
+        }
+
+        let hash =
+            PasswordHash::new(hashed).expect("hashed password from application should be PHC");
+        Argon2::default()
+            .verify_password(raw.as_bytes(), &hash)
+            .is_ok()
     }
 }
 
@@ -29,29 +78,79 @@ impl PasswordValidator for BcryptValidator {
 mod test {
     use super::*;
 
+    const BCRYPT_HASH: &str = "$2a$04$FlenKTKcUW/BI0HBwCPTReMLMh0uo8zuKfja7N.Uo3IHjM3Kp0SIK";
+
     #[test]
-    fn bcrypt_hasher_given_raw_password_should_return_hashed_password() {
-        let password = "password";
-        let hasher = BcryptHasher::new(4);
+    fn argon2_hasher_given_raw_password_should_return_argon2id_phc_string() {
+        let hasher = Argon2Hasher::new(64, 1, 1);
+
+        let hashed_password = hasher.hash("password");
 
-        let hashed_password = hasher.hash(&password);
+        assert!(hashed_password.starts_with("$argon2id$v=19$m=64,t=1,p=1$"));
+        assert!(Argon2Validator {}.verify("password", &hashed_password));
+    }
+
+    #[test]
+    fn argon2_hasher_dummy_hash_should_use_current_parameters() {
+        let hasher = Argon2Hasher::new(64, 1, 1);
 
-        assert!(verify(password, hashed_password.as_str()).is_ok());
+        assert!(
+            hasher
+                .dummy_hash()
+                .starts_with("$argon2id$v=19$m=64,t=1,p=1$")
+        );
+        assert!(!hasher.needs_rehash(hasher.dummy_hash()));
+    }
+
+    #[test]
+    fn argon2_hasher_given_same_parameters_should_not_need_rehash() {
+        let hasher = Argon2Hasher::new(64, 1, 1);
+
+        let hashed_password = hasher.hash("password");
+
+        assert!(!hasher.needs_rehash(&hashed_password));
+    }
+
+    #[test]
+    fn argon2_hasher_given_outdated_hash_should_need_rehash() {
+        let hasher = Argon2Hasher::new(128, 2, 1);
+        let outdated_hashes = vec![
+            Argon2Hasher::new(64, 2, 1).hash("password"),
+            Argon2Hasher::new(128, 1, 1).hash("password"),
+            Argon2::new(
+                Algorithm::Argon2i,
+                Version::V0x13,
+                Params::new(128, 2, 1, None).unwrap(),
+            )
+            .hash_password(b"password", &SaltString::generate(&mut OsRng))
+            .unwrap()
+            .to_string(),
+            BCRYPT_HASH.to_string(),
+        ];
+
+        for hashed_password in outdated_hashes {
+            assert!(hasher.needs_rehash(&hashed_password));
+        }
+    }
+
+    #[test]
+    fn argon2_validator_given_raw_and_argon2_hash_should_return_is_valid() {
+        let passwords = vec![("correct_password", true), ("wrong_password", false)];
+        let hashed_password = Argon2Hasher::new(64, 1, 1).hash("correct_password");
+        let validator = Argon2Validator {};
+
+        for (password, is_valid) in passwords {
+            assert_eq!(validator.verify(password, &hashed_password), is_valid);
+        }
     }
 
     #[test]
-    fn bcrypt_validator_given_raw_and_hashed_password_should_return_is_valid() {
+    fn argon2_validator_given_raw_and_bcrypt_hash_should_return_is_valid() {
         let passwords = vec![("correct_password", true), ("wrong_password", false)];
-        let validator = BcryptValidator {};
+        let validator = Argon2Validator {};
 
         for (password, is_valid) in passwords {
-            assert_eq!(
-                validator.verify(
-                    password,
-                    "$2a$04$FlenKTKcUW/BI0HBwCPTReMLMh0uo8zuKfja7N.Uo3IHjM3Kp0SIK"
-                ),
-                is_valid
-            );
+            assert_eq!(validator.verify(password, BCRYPT_HASH), is_valid);
         }
     }
 }
@@ -0,0 +1,210 @@
+use argon2::password_hash::rand_core::{OsRng, RngCore};
+use hmac::{Hmac, Mac};
+use sha1::Sha1;
+
+use crate::application::service::auth::TotpAuthenticator;
+
+const BASE32_ALPHABET: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+const SECRET_BYTES: usize = 20;
+const DIGITS: u32 = 6;
+const STEP_SECONDS: u64 = 30;
+const ALLOWED_DRIFT_STEPS: u64 = 1;
+
+pub struct Totp {
+    issuer: String,
+}
+
+impl Totp {
+    pub fn new(issuer: &str) -> Self {
+        Totp {
+            issuer: issuer.to_string(),
+        }
+    }
+}
+
+impl TotpAuthenticator for Totp {
+    fn generate_secret(&self) -> String {
+        let mut bytes = [0u8; SECRET_BYTES];
+        OsRng.fill_bytes(&mut bytes);
+        base32_encode(&bytes)
+    }
+
+    fn provisioning_uri(&self, secret: &str, account: &str) -> String {
+        format!(
+            "otpauth://totp/{}:{}?secret={}&issuer={}&algorithm=SHA1&digits={}&period={}",
+            percent_encode(&self.issuer),
+            percent_encode(account),
+            secret,
+            percent_encode(&self.issuer),
+            DIGITS,
+            STEP_SECONDS
+        )
+    }
+
+    fn verify(&self, secret: &str, code: &str, now: u64) -> Option<u64> {
+        if code.len() != DIGITS as usize || !code.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        let key = base32_decode(secret)?;
+        let current_step = now / STEP_SECONDS;
+
+        (current_step.saturating_sub(ALLOWED_DRIFT_STEPS)..=current_step + ALLOWED_DRIFT_STEPS)
+            .find(|step| hotp(&key, *step) == code)
+    }
+}
+
+fn hotp(key: &[u8], counter: u64) -> String {
+    let mut mac: Hmac<Sha1> = Hmac::new_from_slice(key).unwrap();
+    mac.update(&counter.to_be_bytes());
+    let digest = mac.finalize().into_bytes();
+    let offset = (digest[digest.len() - 1] & 0x0f) as usize;
+    let binary = u32::from_be_bytes(digest[offset..offset + 4].try_into().unwrap()) & 0x7fff_ffff;
+
+    format!(
+        "{:0width$}",
+        binary % 10u32.pow(DIGITS),
+        width = DIGITS as usize
+    )
+}
+
+fn base32_encode(bytes: &[u8]) -> String {
+    let mut encoded = String::new();
+    let mut buffer = 0u32;
+    let mut bits = 0;
+    for byte in bytes {
+        buffer = (buffer << 8) | *byte as u32;
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            encoded.push(BASE32_ALPHABET[((buffer >> bits) & 0x1f) as usize] as char);
+        }
+    }
+    if bits > 0 {
+        encoded.push(BASE32_ALPHABET[((buffer << (5 - bits)) & 0x1f) as usize] as char);
+    }
+
+    encoded
+}
+
+fn base32_decode(encoded: &str) -> Option<Vec<u8>> {
+    let mut decoded = Vec::new();
+    let mut buffer = 0u32;
+    let mut bits = 0;
+    for c in encoded.trim_end_matches('=').bytes() {
+        let value = BASE32_ALPHABET
+            .iter()
+            .position(|a| *a == c.to_ascii_uppercase())?;
+        buffer = (buffer << 5) | value as u32;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            decoded.push((buffer >> bits) as u8);
+        }
+    }
+
+    Some(decoded)
+}
+
+fn percent_encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const RFC_6238_SECRET: &str = "GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ";
+
+    #[test]
+    fn verify_given_rfc_6238_test_vectors_should_return_matching_step() {
+        let test_cases = vec![
+            (59, "287082"),
+            (1111111109, "081804"),
+            (1111111111, "050471"),
+            (1234567890, "005924"),
+            (2000000000, "279037"),
+        ];
+        let totp = Totp::new("app");
+
+        for (now, code) in test_cases {
+            assert_eq!(totp.verify(RFC_6238_SECRET, code, now), Some(now / 30));
+        }
+    }
+
+    #[test]
+    fn verify_given_code_from_adjacent_step_should_accept_it() {
+        let totp = Totp::new("app");
+
+        assert_eq!(
+            totp.verify(RFC_6238_SECRET, "081804", 1111111109 + 30),
+            Some(1111111109 / 30)
+        );
+        assert_eq!(
+            totp.verify(RFC_6238_SECRET, "081804", 1111111109 - 30),
+            Some(1111111109 / 30)
+        );
+        assert_eq!(
+            totp.verify(RFC_6238_SECRET, "081804", 1111111109 + 60),
+            None
+        );
+    }
+
+    #[test]
+    fn verify_given_malformed_code_should_return_none() {
+        let test_cases = vec!["", "28708", "2870820", "28708a", "287 82"];
+        let totp = Totp::new("app");
+
+        for code in test_cases {
+            assert_eq!(totp.verify(RFC_6238_SECRET, code, 59), None);
+        }
+    }
+
+    #[test]
+    fn generate_secret_should_return_distinct_160_bit_base32_secrets() {
+        let totp = Totp::new("app");
+
+        let secret = totp.generate_secret();
+
+        assert_eq!(secret.len(), 32);
+        assert_eq!(base32_decode(&secret).unwrap().len(), 20);
+        assert_ne!(secret, totp.generate_secret());
+    }
+
+    #[test]
+    fn base32_given_rfc_4648_test_vectors_should_round_trip() {
+        let test_cases = vec![
+            ("", ""),
+            ("f", "MY"),
+            ("fo", "MZXQ"),
+            ("foo", "MZXW6"),
+            ("foob", "MZXW6YQ"),
+            ("fooba", "MZXW6YTB"),
+            ("foobar", "MZXW6YTBOI"),
+        ];
+
+        for (raw, encoded) in test_cases {
+            assert_eq!(base32_encode(raw.as_bytes()), encoded);
+            assert_eq!(base32_decode(encoded).unwrap(), raw.as_bytes());
+        }
+    }
+
+    #[test]
+    fn provisioning_uri_given_account_should_percent_encode_label() {
+        let totp = Totp::new("Simple Auth");
+
+        let uri = totp.provisioning_uri(RFC_6238_SECRET, "example+1@example.com");
+
+        assert_eq!(
+            uri,
+            "otpauth://totp/Simple%20Auth:example%2B1%40example.com?secret=GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ&issuer=Simple%20Auth&algorithm=SHA1&digits=6&period=30"
+        );
+    }
+}
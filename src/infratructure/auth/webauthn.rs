@@ -0,0 +1,372 @@
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use p256::{EncodedPoint, ecdsa::signature::Verifier};
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+
+use super::cbor::{self, CborValue};
+use crate::application::service::auth::{AttestedCredential, PasskeyVerifier};
+use crate::domain::error::InvalidPasskeyResponse;
+
+const FLAG_USER_PRESENT: u8 = 0x01;
+const FLAG_USER_VERIFIED: u8 = 0x04;
+const FLAG_ATTESTED_CREDENTIAL_DATA: u8 = 0x40;
+const COSE_ALGORITHM_ES256: i64 = -7;
+const COSE_ALGORITHM_EDDSA: i64 = -8;
+
+pub struct WebAuthn {
+    rp_id: String,
+    origin: String,
+}
+
+impl WebAuthn {
+    pub fn new(rp_id: &str, origin: &str) -> Self {
+        WebAuthn {
+            rp_id: rp_id.to_string(),
+            origin: origin.to_string(),
+        }
+    }
+
+    pub fn supported_algorithms() -> [i64; 2] {
+        [COSE_ALGORITHM_ES256, COSE_ALGORITHM_EDDSA]
+    }
+
+    fn verify_client_data(
+        &self,
+        client_data_json: &[u8],
+        ceremony: &str,
+        challenge: &str,
+    ) -> Result<(), InvalidPasskeyResponse> {
+        let client_data: ClientData = serde_json::from_slice(client_data_json)
+            .map_err(|_| invalid("malformed client data"))?;
+        if client_data.ceremony != ceremony {
+            return Err(invalid("unexpected ceremony type"));
+        }
+        if client_data.challenge != challenge {
+            return Err(invalid("challenge mismatch"));
+        }
+        if client_data.origin != self.origin {
+            return Err(invalid("origin mismatch"));
+        }
+
+        Ok(())
+    }
+
+    fn verify_authenticator_data<'a>(
+        &self,
+        authenticator_data: &'a [u8],
+    ) -> Result<AuthenticatorData<'a>, InvalidPasskeyResponse> {
+        let authenticator_data = AuthenticatorData::parse(authenticator_data)
+            .ok_or(invalid("malformed authenticator data"))?;
+        if authenticator_data.rp_id_hash != Sha256::digest(self.rp_id.as_bytes()).as_slice() {
+            return Err(invalid("relying party mismatch"));
+        }
+        if authenticator_data.flags & FLAG_USER_PRESENT == 0 {
+            return Err(invalid("user not present"));
+        }
+        if authenticator_data.flags & FLAG_USER_VERIFIED == 0 {
+            return Err(invalid("user not verified"));
+        }
+
+        Ok(authenticator_data)
+    }
+}
+
+impl PasskeyVerifier for WebAuthn {
+    fn verify_registration(
+        &self,
+        challenge: &str,
+        client_data_json: &[u8],
+        attestation_object: &[u8],
+    ) -> Result<AttestedCredential, InvalidPasskeyResponse> {
+        self.verify_client_data(client_data_json, "webauthn.create", challenge)?;
+        let attestation = match cbor::decode(attestation_object) {
+            Some((attestation, consumed)) if consumed == attestation_object.len() => attestation,
+            _ => return Err(invalid("malformed attestation object")),
+        };
+        if attestation.get_text("fmt").and_then(CborValue::as_text) != Some("none")
+            || attestation.get_text("attStmt") != Some(&CborValue::Map(vec![]))
+        {
+            return Err(invalid("unsupported attestation format"));
+        }
+        let authenticator_data = attestation
+            .get_text("authData")
+            .and_then(CborValue::as_bytes)
+            .ok_or(invalid("missing authenticator data"))?;
+        let authenticator_data = self.verify_authenticator_data(authenticator_data)?;
+        let (credential_id, public_key) = authenticator_data
+            .attested_credential
+            .ok_or(invalid("missing attested credential"))?;
+        PublicKey::from_cose(public_key)?;
+
+        Ok(AttestedCredential {
+            id: URL_SAFE_NO_PAD.encode(credential_id),
+            public_key: URL_SAFE_NO_PAD.encode(public_key),
+            sign_count: authenticator_data.sign_count,
+        })
+    }
+
+    fn verify_assertion(
+        &self,
+        challenge: &str,
+        public_key: &str,
+        client_data_json: &[u8],
+        authenticator_data: &[u8],
+        signature: &[u8],
+    ) -> Result<u32, InvalidPasskeyResponse> {
+        self.verify_client_data(client_data_json, "webauthn.get", challenge)?;
+        let parsed_authenticator_data = self.verify_authenticator_data(authenticator_data)?;
+        let public_key = URL_SAFE_NO_PAD
+            .decode(public_key)
+            .map_err(|_| invalid("malformed stored public key"))?;
+        let public_key = PublicKey::from_cose(&public_key)?;
+
+        let message = [
+            authenticator_data,
+            Sha256::digest(client_data_json).as_slice(),
+        ]
+        .concat();
+        if !public_key.verify(&message, signature) {
+            return Err(invalid("signature mismatch"));
+        }
+
+        Ok(parsed_authenticator_data.sign_count)
+    }
+}
+
+#[derive(Deserialize)]
+struct ClientData {
+    #[serde(rename = "type")]
+    ceremony: String,
+    challenge: String,
+    origin: String,
+}
+
+struct AuthenticatorData<'a> {
+    rp_id_hash: &'a [u8],
+    flags: u8,
+    sign_count: u32,
+    attested_credential: Option<(&'a [u8], &'a [u8])>,
+}
+
+impl<'a> AuthenticatorData<'a> {
+    fn parse(bytes: &'a [u8]) -> Option<Self> {
+        let rp_id_hash = bytes.get(..32)?;
+        let flags = *bytes.get(32)?;
+        let sign_count = u32::from_be_bytes(bytes.get(33..37)?.try_into().ok()?);
+        let attested_credential = if flags & FLAG_ATTESTED_CREDENTIAL_DATA != 0 {
+            let rest = bytes.get(37 + 16..)?;
+            let id_len = u16::from_be_bytes(rest.get(..2)?.try_into().ok()?) as usize;
+            let credential_id = rest.get(2..2 + id_len)?;
+            let rest = rest.get(2 + id_len..)?;
+            let (_, key_len) = cbor::decode(rest)?;
+            Some((credential_id, &rest[..key_len]))
+        } else {
+            None
+        };
+
+        Some(AuthenticatorData {
+            rp_id_hash,
+            flags,
+            sign_count,
+            attested_credential,
+        })
+    }
+}
+
+enum PublicKey {
+    Es256(p256::ecdsa::VerifyingKey),
+    EdDsa(ed25519_dalek::VerifyingKey),
+}
+
+impl PublicKey {
+    fn from_cose(bytes: &[u8]) -> Result<Self, InvalidPasskeyResponse> {
+        let key = match cbor::decode(bytes) {
+            Some((key, consumed)) if consumed == bytes.len() => key,
+            _ => return Err(invalid("malformed public key")),
+        };
+        let kty = key.get_int(1).and_then(CborValue::as_integer);
+        let alg = key.get_int(3).and_then(CborValue::as_integer);
+        let crv = key.get_int(-1).and_then(CborValue::as_integer);
+        let x = key.get_int(-2).and_then(CborValue::as_bytes);
+
+        match (kty, alg, crv, x) {
+            (Some(2), Some(COSE_ALGORITHM_ES256), Some(1), Some(x)) if x.len() == 32 => {
+                let y = key
+                    .get_int(-3)
+                    .and_then(CborValue::as_bytes)
+                    .filter(|y| y.len() == 32)
+                    .ok_or(invalid("malformed public key"))?;
+                let point = EncodedPoint::from_affine_coordinates(x.into(), y.into(), false);
+                p256::ecdsa::VerifyingKey::from_encoded_point(&point)
+                    .map(PublicKey::Es256)
+                    .map_err(|_| invalid("malformed public key"))
+            }
+            (Some(1), Some(COSE_ALGORITHM_EDDSA), Some(6), Some(x)) => {
+                let x: [u8; 32] = x.try_into().map_err(|_| invalid("malformed public key"))?;
+                ed25519_dalek::VerifyingKey::from_bytes(&x)
+                    .map(PublicKey::EdDsa)
+                    .map_err(|_| invalid("malformed public key"))
+            }
+            _ => Err(invalid("unsupported public key algorithm")),
+        }
+    }
+
+    fn verify(&self, message: &[u8], signature: &[u8]) -> bool {
+        match self {
+            PublicKey::Es256(key) => p256::ecdsa::Signature::from_der(signature)
+                .is_ok_and(|s| key.verify(message, &s).is_ok()),
+            PublicKey::EdDsa(key) => ed25519_dalek::Signature::from_slice(signature)
+                .is_ok_and(|s| key.verify_strict(message, &s).is_ok()),
+        }
+    }
+}
+
+fn invalid(reason: &'static str) -> InvalidPasskeyResponse {
+    InvalidPasskeyResponse { reason }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_support::infratructure::authenticator::SoftwareAuthenticator;
+
+    const RP_ID: &str = "localhost";
+    const ORIGIN: &str = "http://localhost:8080";
+    const CHALLENGE: &str = "K7gNU3sdo-OL0wNhqoVWhr3g6s1xYv72ol_pe_Unols";
+
+    fn authenticators() -> Vec<SoftwareAuthenticator> {
+        vec![
+            SoftwareAuthenticator::es256(RP_ID, ORIGIN),
+            SoftwareAuthenticator::ed25519(RP_ID, ORIGIN),
+        ]
+    }
+
+    #[test]
+    fn verify_registration_given_none_attestation_should_return_credential() {
+        let webauthn = WebAuthn::new(RP_ID, ORIGIN);
+
+        for authenticator in authenticators() {
+            let (client_data_json, attestation_object) = authenticator.attest(CHALLENGE);
+
+            let credential =
+                webauthn.verify_registration(CHALLENGE, &client_data_json, &attestation_object);
+
+            assert!(credential.is_ok_and(|credential| {
+                credential.id == authenticator.credential_id()
+                    && credential.public_key == authenticator.public_key()
+                    && credential.sign_count == 0
+            }));
+        }
+    }
+
+    #[test]
+    fn verify_registration_given_mismatched_client_data_should_return_error() {
+        let test_cases = vec![
+            (
+                SoftwareAuthenticator::es256(RP_ID, ORIGIN),
+                "other_challenge",
+            ),
+            (
+                SoftwareAuthenticator::es256(RP_ID, "https://evil.example"),
+                CHALLENGE,
+            ),
+            (
+                SoftwareAuthenticator::es256("evil.example", ORIGIN),
+                CHALLENGE,
+            ),
+        ];
+        let webauthn = WebAuthn::new(RP_ID, ORIGIN);
+
+        for (authenticator, challenge) in test_cases {
+            let (client_data_json, attestation_object) = authenticator.attest(challenge);
+
+            let credential =
+                webauthn.verify_registration(CHALLENGE, &client_data_json, &attestation_object);
+
+            assert!(credential.is_err());
+        }
+    }
+
+    #[test]
+    fn verify_registration_given_assertion_client_data_should_return_error() {
+        let webauthn = WebAuthn::new(RP_ID, ORIGIN);
+        let authenticator = SoftwareAuthenticator::es256(RP_ID, ORIGIN);
+        let (_, attestation_object) = authenticator.attest(CHALLENGE);
+        let assertion = authenticator.assert(CHALLENGE, 1);
+
+        let credential = webauthn.verify_registration(
+            CHALLENGE,
+            &assertion.client_data_json,
+            &attestation_object,
+        );
+
+        assert!(credential.is_err_and(|err| err.reason == "unexpected ceremony type"));
+    }
+
+    #[test]
+    fn verify_assertion_given_valid_signature_should_return_sign_count() {
+        let webauthn = WebAuthn::new(RP_ID, ORIGIN);
+
+        for authenticator in authenticators() {
+            let assertion = authenticator.assert(CHALLENGE, 7);
+
+            let sign_count = webauthn.verify_assertion(
+                CHALLENGE,
+                &authenticator.public_key(),
+                &assertion.client_data_json,
+                &assertion.authenticator_data,
+                &assertion.signature,
+            );
+
+            assert_eq!(sign_count.ok(), Some(7));
+        }
+    }
+
+    #[test]
+    fn verify_assertion_given_tampered_response_should_return_error() {
+        let webauthn = WebAuthn::new(RP_ID, ORIGIN);
+
+        for authenticator in authenticators() {
+            let assertion = authenticator.assert(CHALLENGE, 7);
+            let mut tampered_authenticator_data = assertion.authenticator_data.clone();
+            tampered_authenticator_data[36] += 1;
+            let mut tampered_signature = assertion.signature.clone();
+            let last = tampered_signature.len() - 1;
+            tampered_signature[last] ^= 0x01;
+            let test_cases = vec![
+                (tampered_authenticator_data, assertion.signature.clone()),
+                (assertion.authenticator_data.clone(), tampered_signature),
+            ];
+
+            for (authenticator_data, signature) in test_cases {
+                let sign_count = webauthn.verify_assertion(
+                    CHALLENGE,
+                    &authenticator.public_key(),
+                    &assertion.client_data_json,
+                    &authenticator_data,
+                    &signature,
+                );
+
+                assert!(sign_count.is_err());
+            }
+        }
+    }
+
+    #[test]
+    fn verify_assertion_given_key_of_other_authenticator_should_return_error() {
+        let webauthn = WebAuthn::new(RP_ID, ORIGIN);
+        let authenticator = SoftwareAuthenticator::es256(RP_ID, ORIGIN);
+        let other_authenticator = SoftwareAuthenticator::ed25519(RP_ID, ORIGIN);
+        let assertion = authenticator.assert(CHALLENGE, 1);
+
+        let sign_count = webauthn.verify_assertion(
+            CHALLENGE,
+            &other_authenticator.public_key(),
+            &assertion.client_data_json,
+            &assertion.authenticator_data,
+            &assertion.signature,
+        );
+
+        assert!(sign_count.is_err_and(|err| err.reason == "signature mismatch"));
+    }
+}
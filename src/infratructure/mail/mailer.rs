@@ -0,0 +1,16 @@
+use super::{OutboxMailSender, SmtpMailSender};
+use crate::application::service::mail::MailSender;
+
+pub enum Mailer {
+    Outbox(OutboxMailSender),
+    Smtp(SmtpMailSender),
+}
+
+impl Mailer {
+    pub fn mail_sender(&self) -> &dyn MailSender {
+        match self {
+            Mailer::Outbox(sender) => sender,
+            Mailer::Smtp(sender) => sender,
+        }
+    }
+}
@@ -0,0 +1,7 @@
+mod mailer;
+mod outbox;
+mod smtp;
+
+pub use mailer::Mailer;
+pub use outbox::OutboxMailSender;
+pub use smtp::SmtpMailSender;
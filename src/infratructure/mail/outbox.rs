@@ -0,0 +1,65 @@
+use crate::application::service::mail::{Mail, MailSender};
+use crate::domain::error::MailDeliveryFailed;
+use std::{fs, path::PathBuf};
+
+pub struct OutboxMailSender {
+    dir: PathBuf,
+    from: String,
+    generate_id: fn() -> String,
+}
+
+impl OutboxMailSender {
+    pub fn new(dir: PathBuf, from: &str, generate_id: fn() -> String) -> OutboxMailSender {
+        OutboxMailSender {
+            dir,
+            from: from.to_string(),
+            generate_id,
+        }
+    }
+}
+
+impl MailSender for OutboxMailSender {
+    fn send(&self, mail: &Mail) -> Result<(), MailDeliveryFailed> {
+        let to_failure = |err: std::io::Error| MailDeliveryFailed {
+            reason: err.to_string(),
+        };
+        fs::create_dir_all(&self.dir).map_err(to_failure)?;
+        fs::write(
+            self.dir.join(format!("{}.eml", (self.generate_id)())),
+            format!(
+                "From: {}\r\nTo: {}\r\nSubject: {}\r\nContent-Type: text/plain; charset=utf-8\r\n\r\n{}",
+                self.from, mail.to, mail.subject, mail.body
+            ),
+        )
+        .map_err(to_failure)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_support::infratructure::temp_dir::TempDir;
+
+    fn fake_generate_id() -> String {
+        "mail_id".to_string()
+    }
+
+    #[test]
+    fn send_given_mail_should_write_message_to_outbox() {
+        let temp_dir = TempDir::new("outbox");
+        let dir = temp_dir.path().join("outbox");
+        let sender = OutboxMailSender::new(dir.clone(), "no-reply@example.com", fake_generate_id);
+
+        let result = sender.send(&Mail {
+            to: "example@example.com".to_string(),
+            subject: "Verify your email address".to_string(),
+            body: "body".to_string(),
+        });
+
+        assert!(result.is_ok());
+        let message = fs::read_to_string(dir.join("mail_id.eml")).unwrap();
+        assert!(message.starts_with("From: no-reply@example.com\r\nTo: example@example.com\r\n"));
+        assert!(message.contains("Subject: Verify your email address\r\n"));
+        assert!(message.ends_with("\r\n\r\nbody"));
+    }
+}
@@ -0,0 +1,91 @@
+use crate::application::service::mail::{Mail, MailSender};
+use crate::domain::error::MailDeliveryFailed;
+use lettre::{
+    Message, SmtpTransport, Transport, message::header::ContentType,
+    transport::smtp::authentication::Credentials,
+};
+
+pub struct SmtpMailSender {
+    transport: SmtpTransport,
+    from: String,
+}
+
+impl SmtpMailSender {
+    pub fn new(
+        host: &str,
+        port: u16,
+        credentials: Option<(String, String)>,
+        from: &str,
+    ) -> SmtpMailSender {
+        let mut builder = SmtpTransport::starttls_relay(host)
+            .expect("SMTP relay should be a valid host")
+            .port(port);
+        if let Some((username, password)) = credentials {
+            builder = builder.credentials(Credentials::new(username, password));
+        }
+
+        SmtpMailSender {
+            transport: builder.build(),
+            from: from.to_string(),
+        }
+    }
+}
+
+fn build_message(from: &str, mail: &Mail) -> Result<Message, MailDeliveryFailed> {
+    let to_failure = |err: lettre::address::AddressError| MailDeliveryFailed {
+        reason: err.to_string(),
+    };
+    Message::builder()
+        .from(from.parse().map_err(to_failure)?)
+        .to(mail.to.parse().map_err(to_failure)?)
+        .subject(&mail.subject)
+        .header(ContentType::TEXT_PLAIN)
+        .body(mail.body.clone())
+        .map_err(|err| MailDeliveryFailed {
+            reason: err.to_string(),
+        })
+}
+
+impl MailSender for SmtpMailSender {
+    fn send(&self, mail: &Mail) -> Result<(), MailDeliveryFailed> {
+        self.transport
+            .send(&build_message(&self.from, mail)?)
+            .map(|_| ())
+            .map_err(|err| MailDeliveryFailed {
+                reason: err.to_string(),
+            })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn mail(to: &str) -> Mail {
+        Mail {
+            to: to.to_string(),
+            subject: "Verify your email address".to_string(),
+            body: "body".to_string(),
+        }
+    }
+
+    #[test]
+    fn build_message_given_mail_should_set_envelope_and_headers() {
+        let message = build_message("no-reply@example.com", &mail("example@example.com")).unwrap();
+
+        let formatted = String::from_utf8(message.formatted()).unwrap();
+        assert_eq!(
+            message.envelope().to()[0].to_string(),
+            "example@example.com"
+        );
+        assert!(formatted.contains("From: no-reply@example.com\r\n"));
+        assert!(formatted.contains("Subject: Verify your email address\r\n"));
+    }
+
+    #[test]
+    fn build_message_given_invalid_address_should_return_mail_delivery_failed() {
+        let result = build_message("no-reply@example.com", &mail("not an address"));
+
+        assert!(result.is_err());
+    }
+}
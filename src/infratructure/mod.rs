@@ -1,4 +1,5 @@
 mod auth;
+mod mail;
 mod repository;
 mod system;
 mod web;
@@ -1,8 +1,18 @@
 use crate::domain::{
-    entity::User,
-    error::{EntityConflict, EntityNotExist},
-    repository::UserRepository,
-    value_object::EmailAddress,
+    entity::{
+        AuthorizationCode, LoginAttempt, OAuthClient, PasskeyChallenge, PasskeyCredential,
+        PasswordResetToken, RefreshTokenFamily, TotpCredential, User,
+    },
+    error::{
+        EntityConflict, EntityNotExist, RefreshTokenReused, SignCountChanged, UpdateUserError,
+    },
+    repository::{
+        AuthorizationCodeRepository, LoginAttemptRepository, OAuthClientRepository,
+        PasskeyChallengeRepository, PasskeyCredentialRepository, PasswordResetTokenRepository,
+        RefreshTokenFamilyRepository, RevokedTokenRepository, TotpCredentialRepository,
+        UserRepository,
+    },
+    value_object::{EmailAddress, LockoutRule, UserId},
 };
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
@@ -22,80 +32,1191 @@ impl InMemoryUserRepository {
 impl UserRepository for InMemoryUserRepository {
     fn create(&mut self, user: User) -> Result<(), EntityConflict> {
         let mut table = self.data.lock().unwrap();
-        if table.contains_key(user.email.as_str()) {
+        if table.contains_key(user.id.as_str())
+            || table
+                .values()
+                .any(|stored| stored.email.as_str() == user.email.as_str())
+        {
             return Err(EntityConflict {});
         }
-        table.insert(user.email.as_str().to_string(), user);
+        table.insert(user.id.as_str().to_string(), user);
 
         Ok(())
     }
 
     fn get(&self, email: EmailAddress) -> Result<User, EntityNotExist> {
         let table = self.data.lock().unwrap();
-        match table.get(email.as_str()) {
-            Some(user) => Ok(User {
-                email: EmailAddress::new(user.email.as_str()).unwrap(),
-                username: user.username.clone(),
-                password: user.password.clone(),
-                create_at: user.create_at,
-                update_at: user.update_at,
-            }),
+        table
+            .values()
+            .find(|user| user.email.as_str() == email.as_str())
+            .cloned()
+            .ok_or(EntityNotExist {})
+    }
+
+    fn get_by_id(&self, id: &UserId) -> Result<User, EntityNotExist> {
+        let table = self.data.lock().unwrap();
+        table.get(id.as_str()).cloned().ok_or(EntityNotExist {})
+    }
+
+    fn get_by_username(&self, username: &str) -> Result<User, EntityNotExist> {
+        let table = self.data.lock().unwrap();
+        table
+            .values()
+            .filter(|user| user.username == username)
+            .min_by_key(|user| (user.create_at, user.email.as_str().to_string()))
+            .cloned()
+            .ok_or(EntityNotExist {})
+    }
+
+    fn list(&self, offset: usize, limit: usize) -> Vec<User> {
+        let table = self.data.lock().unwrap();
+        let mut users: Vec<&User> = table.values().collect();
+        users.sort_by_key(|user| (user.create_at, user.email.as_str().to_string()));
+        users
+            .into_iter()
+            .skip(offset)
+            .take(limit)
+            .cloned()
+            .collect()
+    }
+
+    fn update(&mut self, user: User) -> Result<(), UpdateUserError> {
+        let mut table = self.data.lock().unwrap();
+        if table.values().any(|stored| {
+            stored.id.as_str() != user.id.as_str() && stored.email.as_str() == user.email.as_str()
+        }) {
+            return Err(UpdateUserError::EmailConflict);
+        }
+        match table.get_mut(user.id.as_str()) {
+            Some(stored) => {
+                *stored = user;
+                Ok(())
+            }
+            None => Err(UpdateUserError::NotExist),
+        }
+    }
+
+    fn update_password_if_unchanged(
+        &mut self,
+        id: &UserId,
+        expected_password: &str,
+        password: &str,
+    ) -> bool {
+        let mut table = self.data.lock().unwrap();
+        match table.get_mut(id.as_str()) {
+            Some(stored) if stored.password == expected_password => {
+                stored.password = password.to_string();
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn delete(&mut self, id: &UserId) -> Result<(), EntityNotExist> {
+        let mut table = self.data.lock().unwrap();
+        table
+            .remove(id.as_str())
+            .map(|_| ())
+            .ok_or(EntityNotExist {})
+    }
+}
+
+pub struct InMemoryRefreshTokenFamilyRepository {
+    data: Arc<Mutex<HashMap<String, RefreshTokenFamily>>>,
+}
+
+impl InMemoryRefreshTokenFamilyRepository {
+    pub fn new(in_memory_table: Arc<Mutex<HashMap<String, RefreshTokenFamily>>>) -> Self {
+        InMemoryRefreshTokenFamilyRepository {
+            data: in_memory_table,
+        }
+    }
+}
+
+impl RefreshTokenFamilyRepository for InMemoryRefreshTokenFamilyRepository {
+    fn create(&mut self, family: RefreshTokenFamily) -> Result<(), EntityConflict> {
+        let mut table = self.data.lock().unwrap();
+        if table.contains_key(&family.id) {
+            return Err(EntityConflict {});
+        }
+        table.insert(family.id.clone(), family);
+
+        Ok(())
+    }
+
+    fn get(&self, id: &str) -> Result<RefreshTokenFamily, EntityNotExist> {
+        let table = self.data.lock().unwrap();
+        table.get(id).cloned().ok_or(EntityNotExist {})
+    }
+
+    fn update(&mut self, family: RefreshTokenFamily) -> Result<(), EntityNotExist> {
+        let mut table = self.data.lock().unwrap();
+        match table.get_mut(&family.id) {
+            Some(stored) => {
+                *stored = family;
+                Ok(())
+            }
             None => Err(EntityNotExist {}),
         }
     }
+
+    fn rotate(
+        &mut self,
+        id: &str,
+        expected_token_id: &str,
+        new_token_id: &str,
+    ) -> Result<(), RefreshTokenReused> {
+        let mut table = self.data.lock().unwrap();
+        let family = table.get_mut(id).ok_or(RefreshTokenReused {})?;
+        if family.revoked {
+            return Err(RefreshTokenReused {});
+        }
+        if family.current_token_id != expected_token_id {
+            family.revoked = true;
+            return Err(RefreshTokenReused {});
+        }
+        family.current_token_id = new_token_id.to_string();
+
+        Ok(())
+    }
+
+    fn revoke_by_subject(&mut self, subject: &str) {
+        let mut table = self.data.lock().unwrap();
+        table
+            .values_mut()
+            .filter(|family| family.subject == subject)
+            .for_each(|family| family.revoked = true);
+    }
+}
+
+pub struct InMemoryRevokedTokenRepository {
+    data: Arc<Mutex<HashMap<String, u64>>>,
+    get_timestamp: fn() -> u64,
+}
+
+impl InMemoryRevokedTokenRepository {
+    pub fn new(
+        in_memory_table: Arc<Mutex<HashMap<String, u64>>>,
+        get_timestamp: fn() -> u64,
+    ) -> Self {
+        InMemoryRevokedTokenRepository {
+            data: in_memory_table,
+            get_timestamp,
+        }
+    }
+}
+
+impl RevokedTokenRepository for InMemoryRevokedTokenRepository {
+    fn revoke(&mut self, token_id: &str, expire_at: u64) {
+        let now = (self.get_timestamp)();
+        let mut table = self.data.lock().unwrap();
+        table.retain(|_, expire_at| *expire_at > now);
+        if expire_at > now {
+            table.insert(token_id.to_string(), expire_at);
+        }
+    }
+
+    fn revoke_if_absent(&mut self, token_id: &str, expire_at: u64) -> bool {
+        let now = (self.get_timestamp)();
+        let mut table = self.data.lock().unwrap();
+        table.retain(|_, expire_at| *expire_at > now);
+        if table.contains_key(token_id) {
+            return false;
+        }
+        if expire_at > now {
+            table.insert(token_id.to_string(), expire_at);
+        }
+
+        true
+    }
+
+    fn is_revoked(&self, token_id: &str) -> bool {
+        let table = self.data.lock().unwrap();
+        table.contains_key(token_id)
+    }
+}
+
+pub struct InMemoryLoginAttemptRepository {
+    data: Arc<Mutex<HashMap<String, LoginAttempt>>>,
+}
+
+impl InMemoryLoginAttemptRepository {
+    pub fn new(in_memory_table: Arc<Mutex<HashMap<String, LoginAttempt>>>) -> Self {
+        InMemoryLoginAttemptRepository {
+            data: in_memory_table,
+        }
+    }
+}
+
+impl LoginAttemptRepository for InMemoryLoginAttemptRepository {
+    fn locked_until(&self, key: &str, now: u64) -> Option<u64> {
+        let table = self.data.lock().unwrap();
+        table
+            .get(key)
+            .map(|attempt| attempt.blocked_until)
+            .filter(|blocked_until| *blocked_until > now)
+    }
+
+    fn record_failure(&mut self, key: &str, rule: &LockoutRule, now: u64) -> LoginAttempt {
+        let mut table = self.data.lock().unwrap();
+        table.retain(|_, attempt| attempt.expire_at > now);
+        let previous_failures = table.get(key).map_or(0, |attempt| attempt.failures);
+        let attempt = rule.next_attempt(key, previous_failures, now);
+        table.insert(attempt.key.clone(), attempt.clone());
+
+        attempt
+    }
+
+    fn delete(&mut self, key: &str) {
+        let mut table = self.data.lock().unwrap();
+        table.remove(key);
+    }
+}
+
+pub struct InMemoryPasswordResetTokenRepository {
+    data: Arc<Mutex<HashMap<String, PasswordResetToken>>>,
+    get_timestamp: fn() -> u64,
+}
+
+impl InMemoryPasswordResetTokenRepository {
+    pub fn new(
+        in_memory_table: Arc<Mutex<HashMap<String, PasswordResetToken>>>,
+        get_timestamp: fn() -> u64,
+    ) -> Self {
+        InMemoryPasswordResetTokenRepository {
+            data: in_memory_table,
+            get_timestamp,
+        }
+    }
+}
+
+impl PasswordResetTokenRepository for InMemoryPasswordResetTokenRepository {
+    fn create(&mut self, token: PasswordResetToken) -> Result<(), EntityConflict> {
+        let now = (self.get_timestamp)();
+        let mut table = self.data.lock().unwrap();
+        table.retain(|_, token| token.expire_at > now);
+        if table.contains_key(&token.token_hash) {
+            return Err(EntityConflict {});
+        }
+        table.insert(token.token_hash.clone(), token);
+
+        Ok(())
+    }
+
+    fn get(&self, token_hash: &str) -> Result<PasswordResetToken, EntityNotExist> {
+        let now = (self.get_timestamp)();
+        let table = self.data.lock().unwrap();
+        table
+            .get(token_hash)
+            .filter(|token| token.expire_at > now)
+            .cloned()
+            .ok_or(EntityNotExist {})
+    }
+
+    fn take(&mut self, token_hash: &str) -> Result<PasswordResetToken, EntityNotExist> {
+        let now = (self.get_timestamp)();
+        let mut table = self.data.lock().unwrap();
+        table
+            .remove(token_hash)
+            .filter(|token| token.expire_at > now)
+            .ok_or(EntityNotExist {})
+    }
+}
+
+pub struct InMemoryTotpCredentialRepository {
+    data: Arc<Mutex<HashMap<String, TotpCredential>>>,
+}
+
+impl InMemoryTotpCredentialRepository {
+    pub fn new(in_memory_table: Arc<Mutex<HashMap<String, TotpCredential>>>) -> Self {
+        InMemoryTotpCredentialRepository {
+            data: in_memory_table,
+        }
+    }
+}
+
+impl TotpCredentialRepository for InMemoryTotpCredentialRepository {
+    fn get(&self, user_id: &UserId) -> Result<TotpCredential, EntityNotExist> {
+        let table = self.data.lock().unwrap();
+        table
+            .get(user_id.as_str())
+            .cloned()
+            .ok_or(EntityNotExist {})
+    }
+
+    fn save(&mut self, credential: TotpCredential) {
+        let mut table = self.data.lock().unwrap();
+        table.insert(credential.user_id.as_str().to_string(), credential);
+    }
+
+    fn advance_step(&mut self, user_id: &UserId, step: u64) -> bool {
+        let mut table = self.data.lock().unwrap();
+        match table.get_mut(user_id.as_str()) {
+            Some(credential) if credential.confirmed && step > credential.last_used_step => {
+                credential.last_used_step = step;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn consume_recovery_code(&mut self, user_id: &UserId, recovery_code_hash: &str) -> bool {
+        let mut table = self.data.lock().unwrap();
+        let Some(credential) = table
+            .get_mut(user_id.as_str())
+            .filter(|credential| credential.confirmed)
+        else {
+            return false;
+        };
+        let count = credential.recovery_codes.len();
+        credential
+            .recovery_codes
+            .retain(|recovery_code| recovery_code != recovery_code_hash);
+
+        credential.recovery_codes.len() < count
+    }
+}
+
+pub struct InMemoryPasskeyCredentialRepository {
+    data: Arc<Mutex<HashMap<String, PasskeyCredential>>>,
+}
+
+impl InMemoryPasskeyCredentialRepository {
+    pub fn new(in_memory_table: Arc<Mutex<HashMap<String, PasskeyCredential>>>) -> Self {
+        InMemoryPasskeyCredentialRepository {
+            data: in_memory_table,
+        }
+    }
+}
+
+impl PasskeyCredentialRepository for InMemoryPasskeyCredentialRepository {
+    fn create(&mut self, credential: PasskeyCredential) -> Result<(), EntityConflict> {
+        let mut table = self.data.lock().unwrap();
+        if table.contains_key(&credential.id) {
+            return Err(EntityConflict {});
+        }
+        table.insert(credential.id.clone(), credential);
+
+        Ok(())
+    }
+
+    fn get(&self, id: &str) -> Result<PasskeyCredential, EntityNotExist> {
+        let table = self.data.lock().unwrap();
+        table.get(id).cloned().ok_or(EntityNotExist {})
+    }
+
+    fn list_by_user(&self, user_id: &UserId) -> Vec<PasskeyCredential> {
+        let table = self.data.lock().unwrap();
+        let mut credentials: Vec<PasskeyCredential> = table
+            .values()
+            .filter(|credential| credential.user_id.as_str() == user_id.as_str())
+            .cloned()
+            .collect();
+        credentials.sort_by_key(|credential| credential.create_at);
+
+        credentials
+    }
+
+    fn update_sign_count(
+        &mut self,
+        id: &str,
+        expected_sign_count: u32,
+        sign_count: u32,
+    ) -> Result<(), SignCountChanged> {
+        let mut table = self.data.lock().unwrap();
+        match table.get_mut(id) {
+            Some(credential) if credential.sign_count == expected_sign_count => {
+                credential.sign_count = sign_count;
+                Ok(())
+            }
+            _ => Err(SignCountChanged {}),
+        }
+    }
+}
+
+pub struct InMemoryPasskeyChallengeRepository {
+    data: Arc<Mutex<HashMap<String, PasskeyChallenge>>>,
+    get_timestamp: fn() -> u64,
+}
+
+impl InMemoryPasskeyChallengeRepository {
+    pub fn new(
+        in_memory_table: Arc<Mutex<HashMap<String, PasskeyChallenge>>>,
+        get_timestamp: fn() -> u64,
+    ) -> Self {
+        InMemoryPasskeyChallengeRepository {
+            data: in_memory_table,
+            get_timestamp,
+        }
+    }
+}
+
+impl PasskeyChallengeRepository for InMemoryPasskeyChallengeRepository {
+    fn create(&mut self, challenge: PasskeyChallenge) -> Result<(), EntityConflict> {
+        let now = (self.get_timestamp)();
+        let mut table = self.data.lock().unwrap();
+        table.retain(|_, challenge| challenge.expire_at > now);
+        if table.contains_key(&challenge.challenge) {
+            return Err(EntityConflict {});
+        }
+        table.insert(challenge.challenge.clone(), challenge);
+
+        Ok(())
+    }
+
+    fn take(&mut self, challenge: &str) -> Result<PasskeyChallenge, EntityNotExist> {
+        let now = (self.get_timestamp)();
+        let mut table = self.data.lock().unwrap();
+        table
+            .remove(challenge)
+            .filter(|challenge| challenge.expire_at > now)
+            .ok_or(EntityNotExist {})
+    }
+}
+
+pub struct InMemoryOAuthClientRepository {
+    data: Arc<Mutex<HashMap<String, OAuthClient>>>,
+}
+
+impl InMemoryOAuthClientRepository {
+    pub fn new(in_memory_table: Arc<Mutex<HashMap<String, OAuthClient>>>) -> Self {
+        InMemoryOAuthClientRepository {
+            data: in_memory_table,
+        }
+    }
+}
+
+impl OAuthClientRepository for InMemoryOAuthClientRepository {
+    fn get(&self, id: &str) -> Result<OAuthClient, EntityNotExist> {
+        let table = self.data.lock().unwrap();
+        table.get(id).cloned().ok_or(EntityNotExist {})
+    }
+
+    fn save(&mut self, client: OAuthClient) {
+        let mut table = self.data.lock().unwrap();
+        table.insert(client.id.clone(), client);
+    }
+}
+
+pub struct InMemoryAuthorizationCodeRepository {
+    data: Arc<Mutex<HashMap<String, AuthorizationCode>>>,
+    get_timestamp: fn() -> u64,
+}
+
+impl InMemoryAuthorizationCodeRepository {
+    pub fn new(
+        in_memory_table: Arc<Mutex<HashMap<String, AuthorizationCode>>>,
+        get_timestamp: fn() -> u64,
+    ) -> Self {
+        InMemoryAuthorizationCodeRepository {
+            data: in_memory_table,
+            get_timestamp,
+        }
+    }
+}
+
+impl AuthorizationCodeRepository for InMemoryAuthorizationCodeRepository {
+    fn create(&mut self, code: AuthorizationCode) -> Result<(), EntityConflict> {
+        let now = (self.get_timestamp)();
+        let mut table = self.data.lock().unwrap();
+        table.retain(|_, code| code.expire_at > now);
+        if table.contains_key(&code.code_hash) {
+            return Err(EntityConflict {});
+        }
+        table.insert(code.code_hash.clone(), code);
+
+        Ok(())
+    }
+
+    fn take(&mut self, code_hash: &str) -> Result<AuthorizationCode, EntityNotExist> {
+        let now = (self.get_timestamp)();
+        let mut table = self.data.lock().unwrap();
+        table
+            .remove(code_hash)
+            .filter(|code| code.expire_at > now)
+            .ok_or(EntityNotExist {})
+    }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::test_support::domain::{
+        oauth_client_repository_suite, passkey_credential_repository_suite,
+        totp_credential_repository_suite, user_repository_suite,
+    };
 
-    fn create_user() -> User {
-        User {
-            email: EmailAddress::new("example@example.com").unwrap(),
-            username: "foo".to_string(),
-            password: "bar".to_string(),
-            create_at: 1747636936,
-            update_at: 1747636936,
-        }
+    fn create_repository() -> InMemoryUserRepository {
+        InMemoryUserRepository::new(Arc::new(Mutex::new(HashMap::new())))
     }
 
     #[test]
     fn create_given_user_should_persist_to_data() {
-        let mut repo = InMemoryUserRepository::new(Arc::new(Mutex::new(HashMap::new())));
-        let user = create_user();
+        user_repository_suite::create_given_user_should_persist_to_data(&mut create_repository());
+    }
 
-        let result = repo.create(user);
+    #[test]
+    fn create_given_conflict_email_should_return_entity_conflict() {
+        user_repository_suite::create_given_conflict_email_should_return_entity_conflict(
+            &mut create_repository(),
+        );
+    }
 
-        assert!(result.is_ok());
+    #[test]
+    fn get_given_email_should_return_user() {
+        user_repository_suite::get_given_email_should_return_user(&mut create_repository());
     }
 
     #[test]
-    fn create_given_conflict_email_should_return_entity_conflict() {
-        let mut repo = InMemoryUserRepository::new(Arc::new(Mutex::new(HashMap::new())));
-        repo.create(create_user()).expect("should be ok");
-        let user = create_user();
+    fn get_given_not_exist_email_should_return_entity_not_exist() {
+        user_repository_suite::get_given_not_exist_email_should_return_entity_not_exist(
+            &mut create_repository(),
+        );
+    }
+
+    #[test]
+    fn get_by_id_given_id_should_return_user() {
+        user_repository_suite::get_by_id_given_id_should_return_user(&mut create_repository());
+    }
+
+    #[test]
+    fn get_by_id_given_not_exist_id_should_return_entity_not_exist() {
+        user_repository_suite::get_by_id_given_not_exist_id_should_return_entity_not_exist(
+            &mut create_repository(),
+        );
+    }
+
+    #[test]
+    fn get_by_username_given_username_should_return_user() {
+        user_repository_suite::get_by_username_given_username_should_return_user(
+            &mut create_repository(),
+        );
+    }
+
+    #[test]
+    fn get_by_username_given_not_exist_username_should_return_entity_not_exist() {
+        user_repository_suite::get_by_username_given_not_exist_username_should_return_entity_not_exist(
+            &mut create_repository(),
+        );
+    }
+
+    #[test]
+    fn list_given_offset_and_limit_should_return_page_in_creation_order() {
+        user_repository_suite::list_given_offset_and_limit_should_return_page_in_creation_order(
+            &mut create_repository(),
+        );
+    }
+
+    #[test]
+    fn list_given_offset_beyond_data_should_return_empty() {
+        user_repository_suite::list_given_offset_beyond_data_should_return_empty(
+            &mut create_repository(),
+        );
+    }
+
+    #[test]
+    fn update_given_user_should_persist_changes() {
+        user_repository_suite::update_given_user_should_persist_changes(&mut create_repository());
+    }
 
-        let result = repo.create(user);
+    #[test]
+    fn update_password_if_unchanged_given_expected_password_should_replace_password() {
+        user_repository_suite::update_password_if_unchanged_given_expected_password_should_replace_password(
+            &mut create_repository(),
+        );
+    }
+
+    #[test]
+    fn update_password_if_unchanged_given_changed_password_should_keep_password() {
+        user_repository_suite::update_password_if_unchanged_given_changed_password_should_keep_password(
+            &mut create_repository(),
+        );
+    }
+
+    #[test]
+    fn update_given_not_exist_user_should_return_entity_not_exist() {
+        user_repository_suite::update_given_not_exist_user_should_return_entity_not_exist(
+            &mut create_repository(),
+        );
+    }
+
+    #[test]
+    fn update_given_email_of_another_user_should_return_email_conflict() {
+        user_repository_suite::update_given_email_of_another_user_should_return_email_conflict(
+            &mut create_repository(),
+        );
+    }
+
+    #[test]
+    fn delete_given_id_should_remove_user() {
+        user_repository_suite::delete_given_id_should_remove_user(&mut create_repository());
+    }
+
+    #[test]
+    fn delete_given_not_exist_id_should_return_entity_not_exist() {
+        user_repository_suite::delete_given_not_exist_id_should_return_entity_not_exist(
+            &mut create_repository(),
+        );
+    }
+
+    fn create_family() -> RefreshTokenFamily {
+        RefreshTokenFamily {
+            id: "family_id".to_string(),
+            subject: "foo".to_string(),
+            current_token_id: "token_id".to_string(),
+            revoked: false,
+        }
+    }
+
+    #[test]
+    fn refresh_token_family_update_given_family_should_persist_changes() {
+        let mut repo =
+            InMemoryRefreshTokenFamilyRepository::new(Arc::new(Mutex::new(HashMap::new())));
+        repo.create(create_family()).expect("should be ok");
+
+        repo.update(RefreshTokenFamily {
+            revoked: true,
+            ..create_family()
+        })
+        .expect("should be ok");
+
+        assert!(repo.get("family_id").is_ok_and(|f| f.revoked));
+    }
+
+    #[test]
+    fn refresh_token_family_create_given_conflict_id_should_return_entity_conflict() {
+        let mut repo =
+            InMemoryRefreshTokenFamilyRepository::new(Arc::new(Mutex::new(HashMap::new())));
+        repo.create(create_family()).expect("should be ok");
+
+        let result = repo.create(create_family());
 
         assert!(result.is_err_and(|err| matches!(err, EntityConflict {})));
     }
 
     #[test]
-    fn get_given_email_should_return_user() {
-        let mut repo = InMemoryUserRepository::new(Arc::new(Mutex::new(HashMap::new())));
-        repo.create(create_user()).expect("should be ok");
+    fn refresh_token_family_update_given_not_exist_family_should_return_entity_not_exist() {
+        let mut repo =
+            InMemoryRefreshTokenFamilyRepository::new(Arc::new(Mutex::new(HashMap::new())));
 
-        let user = repo.get(EmailAddress::new("example@example.com").unwrap());
+        let result = repo.update(create_family());
 
-        assert!(user.is_ok());
+        assert!(result.is_err_and(|err| matches!(err, EntityNotExist {})));
     }
 
     #[test]
-    fn get_given_not_exist_email_should_return_entity_not_exist() {
-        let repo = InMemoryUserRepository::new(Arc::new(Mutex::new(HashMap::new())));
+    fn refresh_token_family_rotate_given_current_token_id_should_replace_it() {
+        let mut repo =
+            InMemoryRefreshTokenFamilyRepository::new(Arc::new(Mutex::new(HashMap::new())));
+        repo.create(create_family()).expect("should be ok");
+
+        let result = repo.rotate("family_id", "token_id", "next_token_id");
+
+        assert!(result.is_ok());
+        assert!(
+            repo.get("family_id")
+                .is_ok_and(|f| f.current_token_id == "next_token_id" && !f.revoked)
+        );
+    }
+
+    #[test]
+    fn refresh_token_family_rotate_given_stale_token_id_should_lose_and_revoke_family() {
+        let mut repo =
+            InMemoryRefreshTokenFamilyRepository::new(Arc::new(Mutex::new(HashMap::new())));
+        repo.create(create_family()).expect("should be ok");
+        repo.rotate("family_id", "token_id", "next_token_id")
+            .expect("should be ok");
+
+        let result = repo.rotate("family_id", "token_id", "another_token_id");
+
+        assert!(result.is_err_and(|err| matches!(err, RefreshTokenReused {})));
+        assert!(
+            repo.get("family_id")
+                .is_ok_and(|f| f.current_token_id == "next_token_id" && f.revoked)
+        );
+    }
+
+    #[test]
+    fn refresh_token_family_rotate_given_concurrent_rotations_should_let_only_one_win() {
+        let table = Arc::new(Mutex::new(HashMap::new()));
+        InMemoryRefreshTokenFamilyRepository::new(table.clone())
+            .create(create_family())
+            .expect("should be ok");
+
+        let handles: Vec<_> = (0..8)
+            .map(|i| {
+                let mut repo = InMemoryRefreshTokenFamilyRepository::new(table.clone());
+                std::thread::spawn(move || {
+                    repo.rotate("family_id", "token_id", &format!("token_id_{}", i))
+                        .is_ok()
+                })
+            })
+            .collect();
+        let winners = handles
+            .into_iter()
+            .map(|handle| handle.join().unwrap())
+            .filter(|won| *won)
+            .count();
+
+        assert_eq!(winners, 1);
+    }
+
+    #[test]
+    fn refresh_token_family_revoke_by_subject_given_subject_should_revoke_its_families() {
+        let mut repo =
+            InMemoryRefreshTokenFamilyRepository::new(Arc::new(Mutex::new(HashMap::new())));
+        repo.create(create_family()).expect("should be ok");
+        repo.create(RefreshTokenFamily {
+            id: "another_family_id".to_string(),
+            subject: "bar".to_string(),
+            ..create_family()
+        })
+        .expect("should be ok");
+
+        repo.revoke_by_subject("foo");
+
+        assert!(repo.get("family_id").is_ok_and(|f| f.revoked));
+        assert!(repo.get("another_family_id").is_ok_and(|f| !f.revoked));
+    }
+
+    fn fake_get_timestamp() -> u64 {
+        1747636936
+    }
+
+    #[test]
+    fn revoked_token_revoke_given_token_id_should_be_revoked() {
+        let mut repo = InMemoryRevokedTokenRepository::new(
+            Arc::new(Mutex::new(HashMap::new())),
+            fake_get_timestamp,
+        );
+
+        repo.revoke("token_id", 1747640536);
+
+        assert!(repo.is_revoked("token_id"));
+        assert!(!repo.is_revoked("another_token_id"));
+    }
+
+    #[test]
+    fn revoked_token_revoke_given_expired_entries_should_prune_them() {
+        let table = Arc::new(Mutex::new(HashMap::from([
+            ("expired_token_id".to_string(), 1747636936),
+            ("valid_token_id".to_string(), 1747640536),
+        ])));
+        let mut repo = InMemoryRevokedTokenRepository::new(table.clone(), fake_get_timestamp);
+
+        repo.revoke("token_id", 1747640536);
+
+        let table = table.lock().unwrap();
+        assert!(!table.contains_key("expired_token_id"));
+        assert!(table.contains_key("valid_token_id") && table.contains_key("token_id"));
+    }
+
+    #[test]
+    fn revoked_token_revoke_if_absent_given_revoked_token_id_should_return_false() {
+        let mut repo = InMemoryRevokedTokenRepository::new(
+            Arc::new(Mutex::new(HashMap::new())),
+            fake_get_timestamp,
+        );
+
+        let first = repo.revoke_if_absent("token_id", 1747640536);
+        let second = repo.revoke_if_absent("token_id", 1747640536);
+
+        assert!(first);
+        assert!(!second);
+        assert!(repo.is_revoked("token_id"));
+    }
+
+    fn create_attempt(key: &str, failures: u32, expire_at: u64) -> LoginAttempt {
+        LoginAttempt {
+            key: key.to_string(),
+            failures,
+            blocked_until: 1747636937,
+            expire_at,
+        }
+    }
+
+    fn lockout_rule() -> LockoutRule {
+        LockoutRule {
+            max_failures: 5,
+            backoff_base_seconds: 1,
+            backoff_max_seconds: 30,
+            lockout_seconds: 900,
+        }
+    }
+
+    #[test]
+    fn login_attempt_record_failure_given_expired_attempt_should_start_over() {
+        let table = Arc::new(Mutex::new(HashMap::from([(
+            "email:foo".to_string(),
+            create_attempt("email:foo", 4, 1747636936),
+        )])));
+        let mut repo = InMemoryLoginAttemptRepository::new(table);
+
+        let attempt = repo.record_failure("email:foo", &lockout_rule(), fake_get_timestamp());
+
+        assert_eq!(attempt.failures, 1);
+        assert_eq!(attempt.blocked_until, 1747636937);
+    }
+
+    #[test]
+    fn login_attempt_record_failure_given_expired_entries_should_prune_them() {
+        let table = Arc::new(Mutex::new(HashMap::from([(
+            "email:old".to_string(),
+            create_attempt("email:old", 1, 1747636900),
+        )])));
+        let mut repo = InMemoryLoginAttemptRepository::new(table.clone());
+
+        repo.record_failure("email:new", &lockout_rule(), fake_get_timestamp());
+
+        let table = table.lock().unwrap();
+        assert!(!table.contains_key("email:old"));
+        assert!(table.get("email:new").is_some_and(|a| a.failures == 1));
+    }
+
+    #[test]
+    fn login_attempt_record_failure_given_concurrent_failures_should_trip_lock() {
+        let table = Arc::new(Mutex::new(HashMap::new()));
+
+        let handles: Vec<_> = (0..lockout_rule().max_failures)
+            .map(|_| {
+                let mut repo = InMemoryLoginAttemptRepository::new(table.clone());
+                std::thread::spawn(move || {
+                    repo.record_failure("email:foo", &lockout_rule(), fake_get_timestamp())
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        let repo = InMemoryLoginAttemptRepository::new(table);
+        assert_eq!(
+            repo.locked_until("email:foo", fake_get_timestamp()),
+            Some(fake_get_timestamp() + lockout_rule().lockout_seconds)
+        );
+    }
+
+    #[test]
+    fn login_attempt_locked_until_given_elapsed_block_should_return_none() {
+        let table = Arc::new(Mutex::new(HashMap::from([(
+            "email:foo".to_string(),
+            create_attempt("email:foo", 1, 1747637836),
+        )])));
+        let repo = InMemoryLoginAttemptRepository::new(table);
+
+        assert_eq!(repo.locked_until("email:foo", 1747636936), Some(1747636937));
+        assert_eq!(repo.locked_until("email:foo", 1747636937), None);
+    }
+
+    #[test]
+    fn login_attempt_delete_given_key_should_remove_attempt() {
+        let table = Arc::new(Mutex::new(HashMap::new()));
+        let mut repo = InMemoryLoginAttemptRepository::new(table);
+        repo.record_failure("email:foo", &lockout_rule(), fake_get_timestamp());
+
+        repo.delete("email:foo");
+
+        assert_eq!(repo.locked_until("email:foo", fake_get_timestamp()), None);
+    }
+
+    fn create_reset_token(token_hash: &str, expire_at: u64) -> PasswordResetToken {
+        PasswordResetToken {
+            token_hash: token_hash.to_string(),
+            user_id: UserId::new("0196e2a4-5c1b-4f7e-9a3d-2b8c6d4e1f00").unwrap(),
+            expire_at,
+        }
+    }
+
+    #[test]
+    fn password_reset_token_get_given_expired_token_should_return_entity_not_exist() {
+        let table = Arc::new(Mutex::new(HashMap::new()));
+        let mut repo = InMemoryPasswordResetTokenRepository::new(table, fake_get_timestamp);
+        repo.create(create_reset_token("expired", 1747636936))
+            .expect("should be ok");
+        repo.create(create_reset_token("valid", 1747637836))
+            .expect("should be ok");
+
+        assert!(repo.get("expired").is_err());
+        assert!(repo.get("valid").is_ok());
+    }
+
+    #[test]
+    fn password_reset_token_create_given_expired_entries_should_prune_them() {
+        let table = Arc::new(Mutex::new(HashMap::new()));
+        let mut repo = InMemoryPasswordResetTokenRepository::new(table.clone(), fake_get_timestamp);
+        table
+            .lock()
+            .unwrap()
+            .insert("old".to_string(), create_reset_token("old", 1747636900));
+
+        repo.create(create_reset_token("new", 1747637836))
+            .expect("should be ok");
+
+        assert!(!table.lock().unwrap().contains_key("old"));
+    }
+
+    #[test]
+    fn password_reset_token_take_given_hash_should_remove_token() {
+        let table = Arc::new(Mutex::new(HashMap::new()));
+        let mut repo = InMemoryPasswordResetTokenRepository::new(table, fake_get_timestamp);
+        repo.create(create_reset_token("valid", 1747637836))
+            .expect("should be ok");
+
+        let first = repo.take("valid");
+        let second = repo.take("valid");
+
+        assert!(first.is_ok_and(|token| token.token_hash == "valid"));
+        assert!(second.is_err_and(|err| matches!(err, EntityNotExist {})));
+        assert!(repo.get("valid").is_err());
+    }
+
+    #[test]
+    fn password_reset_token_take_given_concurrent_takes_should_let_only_one_win() {
+        let table = Arc::new(Mutex::new(HashMap::new()));
+        InMemoryPasswordResetTokenRepository::new(table.clone(), fake_get_timestamp)
+            .create(create_reset_token("valid", 1747637836))
+            .expect("should be ok");
+
+        let handles: Vec<_> = (0..8)
+            .map(|_| {
+                let mut repo =
+                    InMemoryPasswordResetTokenRepository::new(table.clone(), fake_get_timestamp);
+                std::thread::spawn(move || repo.take("valid").is_ok())
+            })
+            .collect();
+        let winners = handles
+            .into_iter()
+            .map(|handle| handle.join().unwrap())
+            .filter(|won| *won)
+            .count();
+
+        assert_eq!(winners, 1);
+    }
+
+    #[test]
+    fn totp_credential_save_given_credential_should_persist_to_data() {
+        totp_credential_repository_suite::save_given_credential_should_persist_to_data(
+            &mut InMemoryTotpCredentialRepository::new(Arc::new(Mutex::new(HashMap::new()))),
+        );
+    }
+
+    #[test]
+    fn totp_credential_save_given_existing_credential_should_replace_it() {
+        totp_credential_repository_suite::save_given_existing_credential_should_replace_it(
+            &mut InMemoryTotpCredentialRepository::new(Arc::new(Mutex::new(HashMap::new()))),
+        );
+    }
+
+    #[test]
+    fn totp_credential_get_given_not_exist_user_should_return_entity_not_exist() {
+        totp_credential_repository_suite::get_given_not_exist_user_should_return_entity_not_exist(
+            &mut InMemoryTotpCredentialRepository::new(Arc::new(Mutex::new(HashMap::new()))),
+        );
+    }
+
+    #[test]
+    fn totp_credential_advance_step_given_newer_step_should_advance_only_once() {
+        totp_credential_repository_suite::advance_step_given_newer_step_should_advance_only_once(
+            &mut InMemoryTotpCredentialRepository::new(Arc::new(Mutex::new(HashMap::new()))),
+        );
+    }
+
+    #[test]
+    fn totp_credential_advance_step_given_unconfirmed_credential_should_return_false() {
+        totp_credential_repository_suite::advance_step_given_unconfirmed_credential_should_return_false(
+            &mut InMemoryTotpCredentialRepository::new(Arc::new(Mutex::new(HashMap::new()))),
+        );
+    }
+
+    #[test]
+    fn totp_credential_consume_recovery_code_given_code_should_remove_it_only_once() {
+        totp_credential_repository_suite::consume_recovery_code_given_code_should_remove_it_only_once(
+            &mut InMemoryTotpCredentialRepository::new(Arc::new(Mutex::new(HashMap::new()))),
+        );
+    }
+
+    #[test]
+    fn passkey_credential_create_given_credential_should_persist_to_data() {
+        passkey_credential_repository_suite::create_given_credential_should_persist_to_data(
+            &mut InMemoryPasskeyCredentialRepository::new(Arc::new(Mutex::new(HashMap::new()))),
+        );
+    }
+
+    #[test]
+    fn passkey_credential_create_given_conflict_id_should_return_entity_conflict() {
+        passkey_credential_repository_suite::create_given_conflict_id_should_return_entity_conflict(
+            &mut InMemoryPasskeyCredentialRepository::new(Arc::new(Mutex::new(HashMap::new()))),
+        );
+    }
+
+    #[test]
+    fn passkey_credential_list_by_user_should_return_only_credentials_of_user() {
+        passkey_credential_repository_suite::list_by_user_should_return_only_credentials_of_user(
+            &mut InMemoryPasskeyCredentialRepository::new(Arc::new(Mutex::new(HashMap::new()))),
+        );
+    }
+
+    #[test]
+    fn passkey_credential_update_sign_count_given_expected_count_should_persist_sign_count() {
+        passkey_credential_repository_suite::update_sign_count_given_expected_count_should_persist_sign_count(
+            &mut InMemoryPasskeyCredentialRepository::new(Arc::new(Mutex::new(HashMap::new()))),
+        );
+    }
+
+    #[test]
+    fn passkey_credential_update_sign_count_given_changed_count_should_return_sign_count_changed() {
+        passkey_credential_repository_suite::update_sign_count_given_changed_count_should_return_sign_count_changed(
+            &mut InMemoryPasskeyCredentialRepository::new(Arc::new(Mutex::new(HashMap::new()))),
+        );
+    }
+
+    #[test]
+    fn passkey_credential_update_sign_count_given_not_exist_credential_should_return_sign_count_changed()
+     {
+        passkey_credential_repository_suite::update_sign_count_given_not_exist_credential_should_return_sign_count_changed(
+            &mut InMemoryPasskeyCredentialRepository::new(Arc::new(Mutex::new(HashMap::new()))),
+        );
+    }
+
+    fn create_passkey_challenge(challenge: &str, expire_at: u64) -> PasskeyChallenge {
+        PasskeyChallenge {
+            challenge: challenge.to_string(),
+            user_id: None,
+            expire_at,
+        }
+    }
+
+    #[test]
+    fn passkey_challenge_take_given_expired_challenge_should_return_entity_not_exist() {
+        let table = Arc::new(Mutex::new(HashMap::from([
+            (
+                "expired".to_string(),
+                create_passkey_challenge("expired", 1747636936),
+            ),
+            (
+                "valid".to_string(),
+                create_passkey_challenge("valid", 1747637236),
+            ),
+        ])));
+        let mut repo = InMemoryPasskeyChallengeRepository::new(table, fake_get_timestamp);
+
+        assert!(repo.take("expired").is_err());
+        assert!(repo.take("valid").is_ok());
+    }
+
+    #[test]
+    fn passkey_challenge_create_given_expired_entries_should_prune_them() {
+        let table = Arc::new(Mutex::new(HashMap::new()));
+        let mut repo = InMemoryPasskeyChallengeRepository::new(table.clone(), fake_get_timestamp);
+        table.lock().unwrap().insert(
+            "old".to_string(),
+            create_passkey_challenge("old", 1747636900),
+        );
+
+        repo.create(create_passkey_challenge("new", 1747637236))
+            .expect("should be ok");
+
+        assert!(!table.lock().unwrap().contains_key("old"));
+    }
+
+    #[test]
+    fn passkey_challenge_take_given_concurrent_takes_should_let_only_one_win() {
+        let table = Arc::new(Mutex::new(HashMap::new()));
+        InMemoryPasskeyChallengeRepository::new(table.clone(), fake_get_timestamp)
+            .create(create_passkey_challenge("valid", 1747637236))
+            .expect("should be ok");
+
+        let handles: Vec<_> = (0..8)
+            .map(|_| {
+                let mut repo =
+                    InMemoryPasskeyChallengeRepository::new(table.clone(), fake_get_timestamp);
+                std::thread::spawn(move || repo.take("valid").is_ok())
+            })
+            .collect();
+        let winners = handles
+            .into_iter()
+            .map(|handle| handle.join().unwrap())
+            .filter(|won| *won)
+            .count();
+
+        assert_eq!(winners, 1);
+    }
+
+    #[test]
+    fn oauth_client_save_given_client_should_persist_to_data() {
+        oauth_client_repository_suite::save_given_client_should_persist_to_data(
+            &mut InMemoryOAuthClientRepository::new(Arc::new(Mutex::new(HashMap::new()))),
+        );
+    }
+
+    #[test]
+    fn oauth_client_save_given_existing_client_should_replace_it() {
+        oauth_client_repository_suite::save_given_existing_client_should_replace_it(
+            &mut InMemoryOAuthClientRepository::new(Arc::new(Mutex::new(HashMap::new()))),
+        );
+    }
+
+    #[test]
+    fn oauth_client_save_given_confidential_client_should_persist_credentials() {
+        oauth_client_repository_suite::save_given_confidential_client_should_persist_credentials(
+            &mut InMemoryOAuthClientRepository::new(Arc::new(Mutex::new(HashMap::new()))),
+        );
+    }
+
+    #[test]
+    fn oauth_client_get_given_not_exist_id_should_return_entity_not_exist() {
+        oauth_client_repository_suite::get_given_not_exist_id_should_return_entity_not_exist(
+            &mut InMemoryOAuthClientRepository::new(Arc::new(Mutex::new(HashMap::new()))),
+        );
+    }
+
+    fn create_authorization_code(code_hash: &str, expire_at: u64) -> AuthorizationCode {
+        AuthorizationCode {
+            code_hash: code_hash.to_string(),
+            client_id: "client_id".to_string(),
+            user_id: UserId::new("0196e2a4-5c1b-4f7e-9a3d-2b8c6d4e1f00").unwrap(),
+            redirect_uri: "https://app.example.com/callback".to_string(),
+            code_challenge: "code_challenge".to_string(),
+            expire_at,
+        }
+    }
+
+    #[test]
+    fn authorization_code_take_given_code_should_return_it_only_once() {
+        let table = Arc::new(Mutex::new(HashMap::new()));
+        let mut repo = InMemoryAuthorizationCodeRepository::new(table, fake_get_timestamp);
+        repo.create(create_authorization_code("valid", 1747636996))
+            .expect("should be ok");
+
+        assert!(
+            repo.take("valid")
+                .is_ok_and(|code| code.client_id == "client_id")
+        );
+        assert!(repo.take("valid").is_err());
+    }
+
+    #[test]
+    fn authorization_code_take_given_expired_code_should_return_entity_not_exist() {
+        let table = Arc::new(Mutex::new(HashMap::new()));
+        let mut repo = InMemoryAuthorizationCodeRepository::new(table.clone(), fake_get_timestamp);
+        table.lock().unwrap().insert(
+            "expired".to_string(),
+            create_authorization_code("expired", 1747636936),
+        );
+
+        assert!(repo.take("expired").is_err());
+        assert!(table.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn authorization_code_create_given_conflict_hash_should_return_entity_conflict() {
+        let table = Arc::new(Mutex::new(HashMap::new()));
+        let mut repo = InMemoryAuthorizationCodeRepository::new(table, fake_get_timestamp);
+        repo.create(create_authorization_code("valid", 1747636996))
+            .expect("should be ok");
 
-        let user = repo.get(EmailAddress::new("example@example.com").unwrap());
+        let result = repo.create(create_authorization_code("valid", 1747636996));
 
-        assert!(user.is_err_and(|err| matches!(err, EntityNotExist {})));
+        assert!(result.is_err());
     }
 }
@@ -1,5 +1,18 @@
 mod generic;
 mod in_memory;
+mod sqlite;
+mod storage;
 
 pub use generic::GenericTableManager;
-pub use in_memory::InMemoryUserRepository;
+pub use in_memory::{
+    InMemoryAuthorizationCodeRepository, InMemoryLoginAttemptRepository,
+    InMemoryOAuthClientRepository, InMemoryPasskeyChallengeRepository,
+    InMemoryPasskeyCredentialRepository, InMemoryPasswordResetTokenRepository,
+    InMemoryRefreshTokenFamilyRepository, InMemoryRevokedTokenRepository,
+    InMemoryTotpCredentialRepository, InMemoryUserRepository,
+};
+pub use sqlite::{
+    SqliteDatabase, SqliteOAuthClientRepository, SqlitePasskeyCredentialRepository,
+    SqliteTotpCredentialRepository, SqliteUserRepository,
+};
+pub use storage::{SessionStorage, UserStorage};
@@ -0,0 +1,910 @@
+use crate::domain::{
+    entity::{OAuthClient, PasskeyCredential, TotpCredential, User},
+    error::{EntityConflict, EntityNotExist, SignCountChanged, UpdateUserError, ValidationError},
+    repository::{
+        OAuthClientRepository, PasskeyCredentialRepository, TotpCredentialRepository,
+        UserRepository,
+    },
+    value_object::{EmailAddress, UserId},
+};
+use rusqlite::{Connection, ErrorCode, OptionalExtension, Row, params, types::Type};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+const MIGRATIONS: &[&str] = &[
+    "
+    CREATE TABLE users (
+        email TEXT NOT NULL,
+        username TEXT NOT NULL,
+        password TEXT NOT NULL,
+        create_at INTEGER NOT NULL,
+        update_at INTEGER NOT NULL
+    );
+    CREATE UNIQUE INDEX users_email ON users (email);
+",
+    "
+    ALTER TABLE users RENAME TO users_v1;
+    DROP INDEX users_email;
+    CREATE TABLE users (
+        id TEXT NOT NULL PRIMARY KEY,
+        email TEXT NOT NULL,
+        username TEXT NOT NULL,
+        password TEXT NOT NULL,
+        create_at INTEGER NOT NULL,
+        update_at INTEGER NOT NULL
+    );
+    CREATE UNIQUE INDEX users_email ON users (email);
+    INSERT INTO users (id, email, username, password, create_at, update_at)
+    SELECT
+        lower(hex(randomblob(4))) || '-' || lower(hex(randomblob(2))) || '-4'
+            || substr(lower(hex(randomblob(2))), 2) || '-'
+            || substr('89ab', 1 + abs(random()) % 4, 1)
+            || substr(lower(hex(randomblob(2))), 2) || '-' || lower(hex(randomblob(6))),
+        email, username, password, create_at, update_at
+    FROM users_v1;
+    DROP TABLE users_v1;
+",
+    "
+    ALTER TABLE users ADD COLUMN email_verified INTEGER NOT NULL DEFAULT 0;
+",
+    "
+    CREATE TABLE totp_credentials (
+        user_id TEXT NOT NULL PRIMARY KEY,
+        secret TEXT NOT NULL,
+        confirmed INTEGER NOT NULL,
+        recovery_codes TEXT NOT NULL,
+        last_used_step INTEGER NOT NULL
+    );
+",
+    "
+    CREATE TABLE passkey_credentials (
+        id TEXT NOT NULL PRIMARY KEY,
+        user_id TEXT NOT NULL,
+        public_key TEXT NOT NULL,
+        sign_count INTEGER NOT NULL,
+        create_at INTEGER NOT NULL
+    );
+    CREATE INDEX passkey_credentials_user_id ON passkey_credentials (user_id);
+",
+    "
+    CREATE TABLE oauth_clients (
+        id TEXT NOT NULL PRIMARY KEY,
+        name TEXT NOT NULL,
+        redirect_uris TEXT NOT NULL
+    );
+",
+    "
+    ALTER TABLE oauth_clients ADD COLUMN secret_hash TEXT;
+    ALTER TABLE oauth_clients ADD COLUMN scopes TEXT NOT NULL DEFAULT '';
+",
+    "
+    ALTER TABLE users ADD COLUMN pending_email TEXT;
+",
+];
+
+pub struct SqliteDatabase {
+    connection: Arc<Mutex<Connection>>,
+}
+
+impl SqliteDatabase {
+    pub fn open(path: &str) -> rusqlite::Result<Self> {
+        SqliteDatabase::migrate(Connection::open(path)?)
+    }
+
+    #[cfg(test)]
+    pub fn open_in_memory() -> rusqlite::Result<Self> {
+        SqliteDatabase::migrate(Connection::open_in_memory()?)
+    }
+
+    pub fn get_connection(&self) -> Arc<Mutex<Connection>> {
+        self.connection.clone()
+    }
+
+    fn migrate(mut connection: Connection) -> rusqlite::Result<Self> {
+        let version: usize =
+            connection.pragma_query_value(None, "user_version", |row| row.get(0))?;
+        for (index, migration) in MIGRATIONS.iter().enumerate().skip(version) {
+            let transaction = connection.transaction()?;
+            transaction.execute_batch(migration)?;
+            transaction.pragma_update(None, "user_version", index + 1)?;
+            transaction.commit()?;
+        }
+
+        Ok(SqliteDatabase {
+            connection: Arc::new(Mutex::new(connection)),
+        })
+    }
+}
+
+fn lock(connection: &Mutex<Connection>) -> MutexGuard<'_, Connection> {
+    connection.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+fn parse_column<T>(index: usize, value: Result<T, ValidationError>) -> rusqlite::Result<T> {
+    value.map_err(|err| rusqlite::Error::FromSqlConversionFailure(index, Type::Text, Box::new(err)))
+}
+
+fn log_failure<T>(result: rusqlite::Result<T>, action: &str) -> Option<T> {
+    result
+        .inspect_err(|err| log::error!("failed to {}: {}", action, err))
+        .ok()
+}
+
+pub struct SqliteUserRepository {
+    connection: Arc<Mutex<Connection>>,
+}
+
+impl SqliteUserRepository {
+    pub fn new(connection: Arc<Mutex<Connection>>) -> Self {
+        SqliteUserRepository { connection }
+    }
+}
+
+impl UserRepository for SqliteUserRepository {
+    fn create(&mut self, user: User) -> Result<(), EntityConflict> {
+        let result = lock(&self.connection).execute(
+            "INSERT INTO users
+                 (id, email, username, password, create_at, update_at, email_verified,
+                  pending_email)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            params![
+                user.id.as_str(),
+                user.email.as_str(),
+                user.username,
+                user.password,
+                user.create_at,
+                user.update_at,
+                user.email_verified,
+                user.pending_email.as_ref().map(EmailAddress::as_str)
+            ],
+        );
+
+        match result {
+            Ok(_) => Ok(()),
+            Err(err) if err.sqlite_error_code() == Some(ErrorCode::ConstraintViolation) => {
+                Err(EntityConflict {})
+            }
+            Err(err) => panic!("failed to insert user: {}", err),
+        }
+    }
+
+    fn get(&self, email: EmailAddress) -> Result<User, EntityNotExist> {
+        let connection = lock(&self.connection);
+        log_failure(
+            connection
+                .query_row(
+                    "SELECT id, email, username, password, create_at, update_at, email_verified,
+                        pending_email
+                     FROM users WHERE email = ?1",
+                    params![email.as_str()],
+                    read_user,
+                )
+                .optional(),
+            "query user",
+        )
+        .flatten()
+        .ok_or(EntityNotExist {})
+    }
+
+    fn get_by_id(&self, id: &UserId) -> Result<User, EntityNotExist> {
+        let connection = lock(&self.connection);
+        log_failure(
+            connection
+                .query_row(
+                    "SELECT id, email, username, password, create_at, update_at, email_verified,
+                        pending_email
+                     FROM users WHERE id = ?1",
+                    params![id.as_str()],
+                    read_user,
+                )
+                .optional(),
+            "query user",
+        )
+        .flatten()
+        .ok_or(EntityNotExist {})
+    }
+
+    fn get_by_username(&self, username: &str) -> Result<User, EntityNotExist> {
+        let connection = lock(&self.connection);
+        log_failure(
+            connection
+                .query_row(
+                    "SELECT id, email, username, password, create_at, update_at, email_verified,
+                        pending_email
+                     FROM users WHERE username = ?1 ORDER BY create_at, email LIMIT 1",
+                    params![username],
+                    read_user,
+                )
+                .optional(),
+            "query user",
+        )
+        .flatten()
+        .ok_or(EntityNotExist {})
+    }
+
+    fn list(&self, offset: usize, limit: usize) -> Vec<User> {
+        let connection = lock(&self.connection);
+        let users = connection
+            .prepare(
+                "SELECT id, email, username, password, create_at, update_at, email_verified,
+                    pending_email
+                 FROM users ORDER BY create_at, email LIMIT ?1 OFFSET ?2",
+            )
+            .and_then(|mut statement| {
+                statement
+                    .query_map(params![limit, offset], read_user)?
+                    .collect()
+            });
+        log_failure(users, "query users").unwrap_or_default()
+    }
+
+    fn update(&mut self, user: User) -> Result<(), UpdateUserError> {
+        let result = lock(&self.connection).execute(
+            "UPDATE users
+                 SET email = ?2, username = ?3, password = ?4, create_at = ?5, update_at = ?6,
+                     email_verified = ?7, pending_email = ?8
+                 WHERE id = ?1",
+            params![
+                user.id.as_str(),
+                user.email.as_str(),
+                user.username,
+                user.password,
+                user.create_at,
+                user.update_at,
+                user.email_verified,
+                user.pending_email.as_ref().map(EmailAddress::as_str)
+            ],
+        );
+
+        match result {
+            Ok(0) => Err(UpdateUserError::NotExist),
+            Ok(_) => Ok(()),
+            Err(err) if err.sqlite_error_code() == Some(ErrorCode::ConstraintViolation) => {
+                Err(UpdateUserError::EmailConflict)
+            }
+            Err(err) => panic!("failed to update user: {}", err),
+        }
+    }
+
+    fn update_password_if_unchanged(
+        &mut self,
+        id: &UserId,
+        expected_password: &str,
+        password: &str,
+    ) -> bool {
+        let connection = lock(&self.connection);
+        log_failure(
+            connection.execute(
+                "UPDATE users SET password = ?3 WHERE id = ?1 AND password = ?2",
+                params![id.as_str(), expected_password, password],
+            ),
+            "update user password",
+        )
+        .is_some_and(|updated| updated > 0)
+    }
+
+    fn delete(&mut self, id: &UserId) -> Result<(), EntityNotExist> {
+        let connection = lock(&self.connection);
+        let changed = log_failure(
+            connection.execute("DELETE FROM users WHERE id = ?1", params![id.as_str()]),
+            "delete user",
+        );
+
+        match changed {
+            Some(0) | None => Err(EntityNotExist {}),
+            Some(_) => Ok(()),
+        }
+    }
+}
+
+fn read_user(row: &Row) -> rusqlite::Result<User> {
+    Ok(User {
+        id: parse_column(0, UserId::new(&row.get::<_, String>(0)?))?,
+        email: parse_column(1, EmailAddress::new(&row.get::<_, String>(1)?))?,
+        email_verified: row.get(6)?,
+        pending_email: parse_column(
+            7,
+            row.get::<_, Option<String>>(7)?
+                .map(|email| EmailAddress::new(&email))
+                .transpose(),
+        )?,
+        username: row.get(2)?,
+        password: row.get(3)?,
+        create_at: row.get(4)?,
+        update_at: row.get(5)?,
+    })
+}
+
+pub struct SqliteTotpCredentialRepository {
+    connection: Arc<Mutex<Connection>>,
+}
+
+impl SqliteTotpCredentialRepository {
+    pub fn new(connection: Arc<Mutex<Connection>>) -> Self {
+        SqliteTotpCredentialRepository { connection }
+    }
+}
+
+impl TotpCredentialRepository for SqliteTotpCredentialRepository {
+    fn get(&self, user_id: &UserId) -> Result<TotpCredential, EntityNotExist> {
+        let connection = lock(&self.connection);
+        log_failure(
+            connection
+                .query_row(
+                    "SELECT user_id, secret, confirmed, recovery_codes, last_used_step
+                     FROM totp_credentials WHERE user_id = ?1",
+                    params![user_id.as_str()],
+                    |row| {
+                        Ok(TotpCredential {
+                            user_id: parse_column(0, UserId::new(&row.get::<_, String>(0)?))?,
+                            secret: row.get(1)?,
+                            confirmed: row.get(2)?,
+                            recovery_codes: row
+                                .get::<_, String>(3)?
+                                .split_whitespace()
+                                .map(String::from)
+                                .collect(),
+                            last_used_step: row.get(4)?,
+                        })
+                    },
+                )
+                .optional(),
+            "query totp credential",
+        )
+        .flatten()
+        .ok_or(EntityNotExist {})
+    }
+
+    fn save(&mut self, credential: TotpCredential) {
+        let result = lock(&self.connection).execute(
+            "INSERT OR REPLACE INTO totp_credentials
+                 (user_id, secret, confirmed, recovery_codes, last_used_step)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                credential.user_id.as_str(),
+                credential.secret,
+                credential.confirmed,
+                credential.recovery_codes.join(" "),
+                credential.last_used_step
+            ],
+        );
+        result.expect("failed to save totp credential");
+    }
+
+    fn advance_step(&mut self, user_id: &UserId, step: u64) -> bool {
+        let connection = lock(&self.connection);
+        log_failure(
+            connection.execute(
+                "UPDATE totp_credentials SET last_used_step = ?2
+                 WHERE user_id = ?1 AND confirmed AND last_used_step < ?2",
+                params![user_id.as_str(), step],
+            ),
+            "advance totp step",
+        )
+        .is_some_and(|updated| updated > 0)
+    }
+
+    fn consume_recovery_code(&mut self, user_id: &UserId, recovery_code_hash: &str) -> bool {
+        let connection = lock(&self.connection);
+        let Some(recovery_codes) = log_failure(
+            connection
+                .query_row(
+                    "SELECT recovery_codes FROM totp_credentials WHERE user_id = ?1 AND confirmed",
+                    params![user_id.as_str()],
+                    |row| row.get::<_, String>(0),
+                )
+                .optional(),
+            "query recovery codes",
+        )
+        .flatten() else {
+            return false;
+        };
+        let remaining: Vec<&str> = recovery_codes
+            .split_whitespace()
+            .filter(|recovery_code| *recovery_code != recovery_code_hash)
+            .collect();
+        if remaining.len() == recovery_codes.split_whitespace().count() {
+            return false;
+        }
+        log_failure(
+            connection.execute(
+                "UPDATE totp_credentials SET recovery_codes = ?2 WHERE user_id = ?1",
+                params![user_id.as_str(), remaining.join(" ")],
+            ),
+            "consume recovery code",
+        )
+        .is_some()
+    }
+}
+
+pub struct SqlitePasskeyCredentialRepository {
+    connection: Arc<Mutex<Connection>>,
+}
+
+impl SqlitePasskeyCredentialRepository {
+    pub fn new(connection: Arc<Mutex<Connection>>) -> Self {
+        SqlitePasskeyCredentialRepository { connection }
+    }
+}
+
+fn read_passkey_credential(row: &Row) -> rusqlite::Result<PasskeyCredential> {
+    Ok(PasskeyCredential {
+        id: row.get(0)?,
+        user_id: parse_column(1, UserId::new(&row.get::<_, String>(1)?))?,
+        public_key: row.get(2)?,
+        sign_count: row.get(3)?,
+        create_at: row.get(4)?,
+    })
+}
+
+impl PasskeyCredentialRepository for SqlitePasskeyCredentialRepository {
+    fn create(&mut self, credential: PasskeyCredential) -> Result<(), EntityConflict> {
+        let result = lock(&self.connection).execute(
+            "INSERT INTO passkey_credentials (id, user_id, public_key, sign_count, create_at)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                credential.id,
+                credential.user_id.as_str(),
+                credential.public_key,
+                credential.sign_count,
+                credential.create_at
+            ],
+        );
+
+        match result {
+            Ok(_) => Ok(()),
+            Err(err) if err.sqlite_error_code() == Some(ErrorCode::ConstraintViolation) => {
+                Err(EntityConflict {})
+            }
+            Err(err) => panic!("failed to insert passkey credential: {}", err),
+        }
+    }
+
+    fn get(&self, id: &str) -> Result<PasskeyCredential, EntityNotExist> {
+        let connection = lock(&self.connection);
+        log_failure(
+            connection
+                .query_row(
+                    "SELECT id, user_id, public_key, sign_count, create_at
+                     FROM passkey_credentials WHERE id = ?1",
+                    params![id],
+                    read_passkey_credential,
+                )
+                .optional(),
+            "query passkey credential",
+        )
+        .flatten()
+        .ok_or(EntityNotExist {})
+    }
+
+    fn list_by_user(&self, user_id: &UserId) -> Vec<PasskeyCredential> {
+        let connection = lock(&self.connection);
+        let credentials = connection
+            .prepare(
+                "SELECT id, user_id, public_key, sign_count, create_at
+                 FROM passkey_credentials WHERE user_id = ?1 ORDER BY create_at",
+            )
+            .and_then(|mut statement| {
+                statement
+                    .query_map(params![user_id.as_str()], read_passkey_credential)?
+                    .collect()
+            });
+        log_failure(credentials, "query passkey credentials").unwrap_or_default()
+    }
+
+    fn update_sign_count(
+        &mut self,
+        id: &str,
+        expected_sign_count: u32,
+        sign_count: u32,
+    ) -> Result<(), SignCountChanged> {
+        let connection = lock(&self.connection);
+        let updated = log_failure(
+            connection.execute(
+                "UPDATE passkey_credentials SET sign_count = ?3 WHERE id = ?1 AND sign_count = ?2",
+                params![id, expected_sign_count, sign_count],
+            ),
+            "update passkey credential",
+        );
+
+        match updated {
+            Some(0) | None => Err(SignCountChanged {}),
+            Some(_) => Ok(()),
+        }
+    }
+}
+
+pub struct SqliteOAuthClientRepository {
+    connection: Arc<Mutex<Connection>>,
+}
+
+impl SqliteOAuthClientRepository {
+    pub fn new(connection: Arc<Mutex<Connection>>) -> Self {
+        SqliteOAuthClientRepository { connection }
+    }
+}
+
+impl OAuthClientRepository for SqliteOAuthClientRepository {
+    fn get(&self, id: &str) -> Result<OAuthClient, EntityNotExist> {
+        let connection = lock(&self.connection);
+        log_failure(
+            connection
+                .query_row(
+                    "SELECT id, name, redirect_uris, secret_hash, scopes
+                     FROM oauth_clients WHERE id = ?1",
+                    params![id],
+                    |row| {
+                        Ok(OAuthClient {
+                            id: row.get(0)?,
+                            name: row.get(1)?,
+                            redirect_uris: row
+                                .get::<_, String>(2)?
+                                .split_whitespace()
+                                .map(String::from)
+                                .collect(),
+                            secret_hash: row.get(3)?,
+                            scopes: row
+                                .get::<_, String>(4)?
+                                .split_whitespace()
+                                .map(String::from)
+                                .collect(),
+                        })
+                    },
+                )
+                .optional(),
+            "query oauth client",
+        )
+        .flatten()
+        .ok_or(EntityNotExist {})
+    }
+
+    fn save(&mut self, client: OAuthClient) {
+        let result = lock(&self.connection).execute(
+            "INSERT OR REPLACE INTO oauth_clients
+                 (id, name, redirect_uris, secret_hash, scopes)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                client.id,
+                client.name,
+                client.redirect_uris.join(" "),
+                client.secret_hash,
+                client.scopes.join(" ")
+            ],
+        );
+        result.expect("failed to save oauth client");
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_support::{
+        domain::{
+            oauth_client_repository_suite, passkey_credential_repository_suite,
+            totp_credential_repository_suite, user_repository_suite,
+        },
+        infratructure::temp_dir::TempDir,
+    };
+
+    fn create_repository() -> SqliteUserRepository {
+        SqliteUserRepository::new(SqliteDatabase::open_in_memory().unwrap().get_connection())
+    }
+
+    #[test]
+    fn open_given_migrated_database_should_keep_data() {
+        let dir = TempDir::new("sqlite_reopen");
+        let path = dir.path().join("auth.db");
+        let path = path.to_str().unwrap();
+        let mut repo =
+            SqliteUserRepository::new(SqliteDatabase::open(path).unwrap().get_connection());
+        user_repository_suite::create_given_user_should_persist_to_data(&mut repo);
+
+        let repo = SqliteUserRepository::new(SqliteDatabase::open(path).unwrap().get_connection());
+
+        assert!(
+            repo.get(EmailAddress::new("example@example.com").unwrap())
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn open_given_database_without_user_ids_should_assign_ids() {
+        let dir = TempDir::new("sqlite_v1");
+        let path = dir.path().join("auth.db");
+        let path = path.to_str().unwrap();
+        let connection = Connection::open(path).unwrap();
+        connection.execute_batch(MIGRATIONS[0]).unwrap();
+        connection.pragma_update(None, "user_version", 1).unwrap();
+        connection
+            .execute(
+                "INSERT INTO users (email, username, password, create_at, update_at)
+                 VALUES ('example@example.com', 'foo', 'bar', 1747636936, 1747636936)",
+                [],
+            )
+            .unwrap();
+        drop(connection);
+
+        let repo = SqliteUserRepository::new(SqliteDatabase::open(path).unwrap().get_connection());
+
+        let user = repo
+            .get(EmailAddress::new("example@example.com").unwrap())
+            .unwrap();
+        assert!(repo.get_by_id(&user.id).is_ok_and(|u| u.username == "foo"));
+    }
+
+    #[test]
+    fn get_given_poisoned_connection_should_still_query() {
+        let connection = SqliteDatabase::open_in_memory().unwrap().get_connection();
+        let mut repo = SqliteUserRepository::new(connection.clone());
+        user_repository_suite::create_given_user_should_persist_to_data(&mut repo);
+        let _ = std::thread::spawn(move || {
+            let _guard = connection.lock().unwrap();
+            panic!("poison connection");
+        })
+        .join();
+
+        assert!(
+            repo.get(EmailAddress::new("example@example.com").unwrap())
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn get_given_malformed_row_should_return_entity_not_exist() {
+        let connection = SqliteDatabase::open_in_memory().unwrap().get_connection();
+        connection
+            .lock()
+            .unwrap()
+            .execute(
+                "INSERT INTO users
+                     (id, email, username, password, create_at, update_at, email_verified)
+                 VALUES ('not-a-uuid', 'example@example.com', 'foo', 'bar', 1, 1, 0)",
+                [],
+            )
+            .unwrap();
+        let repo = SqliteUserRepository::new(connection);
+
+        assert!(
+            repo.get(EmailAddress::new("example@example.com").unwrap())
+                .is_err()
+        );
+        assert!(repo.list(0, 10).is_empty());
+    }
+
+    #[test]
+    fn create_given_user_should_persist_to_data() {
+        user_repository_suite::create_given_user_should_persist_to_data(&mut create_repository());
+    }
+
+    #[test]
+    fn create_given_conflict_email_should_return_entity_conflict() {
+        user_repository_suite::create_given_conflict_email_should_return_entity_conflict(
+            &mut create_repository(),
+        );
+    }
+
+    #[test]
+    fn get_given_email_should_return_user() {
+        user_repository_suite::get_given_email_should_return_user(&mut create_repository());
+    }
+
+    #[test]
+    fn get_given_not_exist_email_should_return_entity_not_exist() {
+        user_repository_suite::get_given_not_exist_email_should_return_entity_not_exist(
+            &mut create_repository(),
+        );
+    }
+
+    #[test]
+    fn get_by_id_given_id_should_return_user() {
+        user_repository_suite::get_by_id_given_id_should_return_user(&mut create_repository());
+    }
+
+    #[test]
+    fn get_by_id_given_not_exist_id_should_return_entity_not_exist() {
+        user_repository_suite::get_by_id_given_not_exist_id_should_return_entity_not_exist(
+            &mut create_repository(),
+        );
+    }
+
+    #[test]
+    fn get_by_username_given_username_should_return_user() {
+        user_repository_suite::get_by_username_given_username_should_return_user(
+            &mut create_repository(),
+        );
+    }
+
+    #[test]
+    fn get_by_username_given_not_exist_username_should_return_entity_not_exist() {
+        user_repository_suite::get_by_username_given_not_exist_username_should_return_entity_not_exist(
+            &mut create_repository(),
+        );
+    }
+
+    #[test]
+    fn list_given_offset_and_limit_should_return_page_in_creation_order() {
+        user_repository_suite::list_given_offset_and_limit_should_return_page_in_creation_order(
+            &mut create_repository(),
+        );
+    }
+
+    #[test]
+    fn list_given_offset_beyond_data_should_return_empty() {
+        user_repository_suite::list_given_offset_beyond_data_should_return_empty(
+            &mut create_repository(),
+        );
+    }
+
+    #[test]
+    fn update_given_user_should_persist_changes() {
+        user_repository_suite::update_given_user_should_persist_changes(&mut create_repository());
+    }
+
+    #[test]
+    fn update_password_if_unchanged_given_expected_password_should_replace_password() {
+        user_repository_suite::update_password_if_unchanged_given_expected_password_should_replace_password(
+            &mut create_repository(),
+        );
+    }
+
+    #[test]
+    fn update_password_if_unchanged_given_changed_password_should_keep_password() {
+        user_repository_suite::update_password_if_unchanged_given_changed_password_should_keep_password(
+            &mut create_repository(),
+        );
+    }
+
+    #[test]
+    fn update_given_not_exist_user_should_return_entity_not_exist() {
+        user_repository_suite::update_given_not_exist_user_should_return_entity_not_exist(
+            &mut create_repository(),
+        );
+    }
+
+    #[test]
+    fn update_given_email_of_another_user_should_return_email_conflict() {
+        user_repository_suite::update_given_email_of_another_user_should_return_email_conflict(
+            &mut create_repository(),
+        );
+    }
+
+    #[test]
+    fn delete_given_id_should_remove_user() {
+        user_repository_suite::delete_given_id_should_remove_user(&mut create_repository());
+    }
+
+    #[test]
+    fn delete_given_not_exist_id_should_return_entity_not_exist() {
+        user_repository_suite::delete_given_not_exist_id_should_return_entity_not_exist(
+            &mut create_repository(),
+        );
+    }
+
+    fn create_totp_credential_repository() -> SqliteTotpCredentialRepository {
+        SqliteTotpCredentialRepository::new(
+            SqliteDatabase::open_in_memory().unwrap().get_connection(),
+        )
+    }
+
+    #[test]
+    fn totp_credential_save_given_credential_should_persist_to_data() {
+        totp_credential_repository_suite::save_given_credential_should_persist_to_data(
+            &mut create_totp_credential_repository(),
+        );
+    }
+
+    #[test]
+    fn totp_credential_save_given_existing_credential_should_replace_it() {
+        totp_credential_repository_suite::save_given_existing_credential_should_replace_it(
+            &mut create_totp_credential_repository(),
+        );
+    }
+
+    #[test]
+    fn totp_credential_get_given_not_exist_user_should_return_entity_not_exist() {
+        totp_credential_repository_suite::get_given_not_exist_user_should_return_entity_not_exist(
+            &mut create_totp_credential_repository(),
+        );
+    }
+
+    #[test]
+    fn totp_credential_advance_step_given_newer_step_should_advance_only_once() {
+        totp_credential_repository_suite::advance_step_given_newer_step_should_advance_only_once(
+            &mut create_totp_credential_repository(),
+        );
+    }
+
+    #[test]
+    fn totp_credential_advance_step_given_unconfirmed_credential_should_return_false() {
+        totp_credential_repository_suite::advance_step_given_unconfirmed_credential_should_return_false(
+            &mut create_totp_credential_repository(),
+        );
+    }
+
+    #[test]
+    fn totp_credential_consume_recovery_code_given_code_should_remove_it_only_once() {
+        totp_credential_repository_suite::consume_recovery_code_given_code_should_remove_it_only_once(
+            &mut create_totp_credential_repository(),
+        );
+    }
+
+    fn create_passkey_credential_repository() -> SqlitePasskeyCredentialRepository {
+        SqlitePasskeyCredentialRepository::new(
+            SqliteDatabase::open_in_memory().unwrap().get_connection(),
+        )
+    }
+
+    #[test]
+    fn passkey_credential_create_given_credential_should_persist_to_data() {
+        passkey_credential_repository_suite::create_given_credential_should_persist_to_data(
+            &mut create_passkey_credential_repository(),
+        );
+    }
+
+    #[test]
+    fn passkey_credential_create_given_conflict_id_should_return_entity_conflict() {
+        passkey_credential_repository_suite::create_given_conflict_id_should_return_entity_conflict(
+            &mut create_passkey_credential_repository(),
+        );
+    }
+
+    #[test]
+    fn passkey_credential_list_by_user_should_return_only_credentials_of_user() {
+        passkey_credential_repository_suite::list_by_user_should_return_only_credentials_of_user(
+            &mut create_passkey_credential_repository(),
+        );
+    }
+
+    #[test]
+    fn passkey_credential_update_sign_count_given_expected_count_should_persist_sign_count() {
+        passkey_credential_repository_suite::update_sign_count_given_expected_count_should_persist_sign_count(
+            &mut create_passkey_credential_repository(),
+        );
+    }
+
+    #[test]
+    fn passkey_credential_update_sign_count_given_changed_count_should_return_sign_count_changed() {
+        passkey_credential_repository_suite::update_sign_count_given_changed_count_should_return_sign_count_changed(
+            &mut create_passkey_credential_repository(),
+        );
+    }
+
+    #[test]
+    fn passkey_credential_update_sign_count_given_not_exist_credential_should_return_sign_count_changed()
+     {
+        passkey_credential_repository_suite::update_sign_count_given_not_exist_credential_should_return_sign_count_changed(
+            &mut create_passkey_credential_repository(),
+        );
+    }
+
+    fn create_oauth_client_repository() -> SqliteOAuthClientRepository {
+        SqliteOAuthClientRepository::new(SqliteDatabase::open_in_memory().unwrap().get_connection())
+    }
+
+    #[test]
+    fn oauth_client_save_given_client_should_persist_to_data() {
+        oauth_client_repository_suite::save_given_client_should_persist_to_data(
+            &mut create_oauth_client_repository(),
+        );
+    }
+
+    #[test]
+    fn oauth_client_save_given_existing_client_should_replace_it() {
+        oauth_client_repository_suite::save_given_existing_client_should_replace_it(
+            &mut create_oauth_client_repository(),
+        );
+    }
+
+    #[test]
+    fn oauth_client_save_given_confidential_client_should_persist_credentials() {
+        oauth_client_repository_suite::save_given_confidential_client_should_persist_credentials(
+            &mut create_oauth_client_repository(),
+        );
+    }
+
+    #[test]
+    fn oauth_client_get_given_not_exist_id_should_return_entity_not_exist() {
+        oauth_client_repository_suite::get_given_not_exist_id_should_return_entity_not_exist(
+            &mut create_oauth_client_repository(),
+        );
+    }
+}
@@ -0,0 +1,146 @@
+use super::{
+    GenericTableManager, InMemoryAuthorizationCodeRepository, InMemoryLoginAttemptRepository,
+    InMemoryOAuthClientRepository, InMemoryPasskeyChallengeRepository,
+    InMemoryPasskeyCredentialRepository, InMemoryPasswordResetTokenRepository,
+    InMemoryRefreshTokenFamilyRepository, InMemoryRevokedTokenRepository,
+    InMemoryTotpCredentialRepository, InMemoryUserRepository, SqliteDatabase,
+    SqliteOAuthClientRepository, SqlitePasskeyCredentialRepository, SqliteTotpCredentialRepository,
+    SqliteUserRepository,
+};
+use crate::domain::{
+    entity::{
+        AuthorizationCode, LoginAttempt, OAuthClient, PasskeyChallenge, PasskeyCredential,
+        PasswordResetToken, RefreshTokenFamily, TotpCredential, User,
+    },
+    repository::{
+        OAuthClientRepository, PasskeyCredentialRepository, TotpCredentialRepository,
+        UserRepository,
+    },
+};
+
+pub enum UserStorage {
+    InMemory {
+        users: GenericTableManager<User>,
+        totp_credentials: GenericTableManager<TotpCredential>,
+        passkey_credentials: GenericTableManager<PasskeyCredential>,
+        oauth_clients: GenericTableManager<OAuthClient>,
+    },
+    Sqlite(SqliteDatabase),
+}
+
+impl UserStorage {
+    pub fn in_memory() -> Self {
+        UserStorage::InMemory {
+            users: GenericTableManager::new(),
+            totp_credentials: GenericTableManager::new(),
+            passkey_credentials: GenericTableManager::new(),
+            oauth_clients: GenericTableManager::new(),
+        }
+    }
+
+    pub fn user_repository(&self) -> Box<dyn UserRepository> {
+        match self {
+            UserStorage::InMemory { users, .. } => {
+                Box::new(InMemoryUserRepository::new(users.get_table()))
+            }
+            UserStorage::Sqlite(database) => {
+                Box::new(SqliteUserRepository::new(database.get_connection()))
+            }
+        }
+    }
+
+    pub fn totp_credential_repository(&self) -> Box<dyn TotpCredentialRepository> {
+        match self {
+            UserStorage::InMemory {
+                totp_credentials, ..
+            } => Box::new(InMemoryTotpCredentialRepository::new(
+                totp_credentials.get_table(),
+            )),
+            UserStorage::Sqlite(database) => Box::new(SqliteTotpCredentialRepository::new(
+                database.get_connection(),
+            )),
+        }
+    }
+
+    pub fn passkey_credential_repository(&self) -> Box<dyn PasskeyCredentialRepository> {
+        match self {
+            UserStorage::InMemory {
+                passkey_credentials,
+                ..
+            } => Box::new(InMemoryPasskeyCredentialRepository::new(
+                passkey_credentials.get_table(),
+            )),
+            UserStorage::Sqlite(database) => Box::new(SqlitePasskeyCredentialRepository::new(
+                database.get_connection(),
+            )),
+        }
+    }
+
+    pub fn oauth_client_repository(&self) -> Box<dyn OAuthClientRepository> {
+        match self {
+            UserStorage::InMemory { oauth_clients, .. } => Box::new(
+                InMemoryOAuthClientRepository::new(oauth_clients.get_table()),
+            ),
+            UserStorage::Sqlite(database) => {
+                Box::new(SqliteOAuthClientRepository::new(database.get_connection()))
+            }
+        }
+    }
+}
+
+pub struct SessionStorage {
+    refresh_token_families: GenericTableManager<RefreshTokenFamily>,
+    revoked_tokens: GenericTableManager<u64>,
+    login_attempts: GenericTableManager<LoginAttempt>,
+    password_reset_tokens: GenericTableManager<PasswordResetToken>,
+    passkey_challenges: GenericTableManager<PasskeyChallenge>,
+    authorization_codes: GenericTableManager<AuthorizationCode>,
+    get_timestamp: fn() -> u64,
+}
+
+impl SessionStorage {
+    pub fn in_memory(get_timestamp: fn() -> u64) -> Self {
+        SessionStorage {
+            refresh_token_families: GenericTableManager::new(),
+            revoked_tokens: GenericTableManager::new(),
+            login_attempts: GenericTableManager::new(),
+            password_reset_tokens: GenericTableManager::new(),
+            passkey_challenges: GenericTableManager::new(),
+            authorization_codes: GenericTableManager::new(),
+            get_timestamp,
+        }
+    }
+
+    pub fn refresh_token_family_repository(&self) -> InMemoryRefreshTokenFamilyRepository {
+        InMemoryRefreshTokenFamilyRepository::new(self.refresh_token_families.get_table())
+    }
+
+    pub fn revoked_token_repository(&self) -> InMemoryRevokedTokenRepository {
+        InMemoryRevokedTokenRepository::new(self.revoked_tokens.get_table(), self.get_timestamp)
+    }
+
+    pub fn login_attempt_repository(&self) -> InMemoryLoginAttemptRepository {
+        InMemoryLoginAttemptRepository::new(self.login_attempts.get_table())
+    }
+
+    pub fn password_reset_token_repository(&self) -> InMemoryPasswordResetTokenRepository {
+        InMemoryPasswordResetTokenRepository::new(
+            self.password_reset_tokens.get_table(),
+            self.get_timestamp,
+        )
+    }
+
+    pub fn passkey_challenge_repository(&self) -> InMemoryPasskeyChallengeRepository {
+        InMemoryPasskeyChallengeRepository::new(
+            self.passkey_challenges.get_table(),
+            self.get_timestamp,
+        )
+    }
+
+    pub fn authorization_code_repository(&self) -> InMemoryAuthorizationCodeRepository {
+        InMemoryAuthorizationCodeRepository::new(
+            self.authorization_codes.get_table(),
+            self.get_timestamp,
+        )
+    }
+}
@@ -0,0 +1,117 @@
+use std::{
+    error::Error,
+    fmt::Display,
+    panic::{AssertUnwindSafe, catch_unwind},
+    sync::{
+        Arc, Mutex,
+        mpsc::{Receiver, SyncSender, sync_channel},
+    },
+    thread,
+};
+use tokio::sync::oneshot;
+
+type Job = Box<dyn FnOnce() + Send>;
+
+pub struct BlockingPool {
+    sender: SyncSender<Job>,
+}
+
+impl BlockingPool {
+    pub fn new(threads: usize, queue_size: usize) -> Self {
+        let (sender, receiver) = sync_channel::<Job>(queue_size);
+        let receiver = Arc::new(Mutex::new(receiver));
+        for index in 0..threads {
+            let receiver = receiver.clone();
+            thread::Builder::new()
+                .name(format!("blocking-pool-{}", index))
+                .spawn(move || BlockingPool::work(receiver))
+                .expect("blocking pool thread should be spawned");
+        }
+
+        BlockingPool { sender }
+    }
+
+    pub fn run<T, F>(&self, job: F) -> impl Future<Output = Result<T, PoolUnavailable>> + 'static
+    where
+        T: Send + 'static,
+        F: FnOnce() -> T + Send + 'static,
+    {
+        let (sender, receiver) = oneshot::channel();
+        let submitted = self
+            .sender
+            .try_send(Box::new(move || {
+                let _ = sender.send(job());
+            }))
+            .map_err(|_| PoolUnavailable {});
+
+        async move {
+            submitted?;
+            receiver.await.map_err(|_| PoolUnavailable {})
+        }
+    }
+
+    fn work(receiver: Arc<Mutex<Receiver<Job>>>) {
+        loop {
+            let job = match receiver.lock().unwrap().recv() {
+                Ok(job) => job,
+                Err(_) => return,
+            };
+            let _ = catch_unwind(AssertUnwindSafe(job));
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct PoolUnavailable {}
+
+impl Error for PoolUnavailable {}
+
+impl Display for PoolUnavailable {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Blocking pool is saturated")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::sync::mpsc::channel;
+
+    #[actix_web::test]
+    async fn run_given_job_should_return_its_result() {
+        let pool = BlockingPool::new(1, 1);
+
+        let result = pool.run(|| 1 + 1).await;
+
+        assert!(result.is_ok_and(|value| value == 2));
+    }
+
+    #[actix_web::test]
+    async fn run_given_saturated_pool_should_return_pool_unavailable() {
+        let pool = BlockingPool::new(1, 1);
+        let (release, wait) = channel::<()>();
+        let (started, is_started) = channel::<()>();
+        let _running = pool.run(move || {
+            started.send(()).unwrap();
+            wait.recv().unwrap();
+        });
+        is_started.recv().unwrap();
+        let _queued = pool.run(|| ());
+
+        let result = pool.run(|| ()).await;
+
+        assert!(result.is_err_and(|err| matches!(err, PoolUnavailable {})));
+        release.send(()).unwrap();
+    }
+
+    #[actix_web::test]
+    async fn run_given_panicking_job_should_keep_worker_alive() {
+        let pool = BlockingPool::new(1, 1);
+
+        let panicked = pool.run(|| panic!("job failed")).await;
+        let result = pool.run(|| "still running").await;
+
+        assert!(panicked.is_err());
+        assert!(result.is_ok_and(|value| value == "still running"));
+    }
+}
@@ -1,17 +1,115 @@
-use std::env;
+use crate::application::use_case::{LoginThrottlePolicy, PasswordResetPolicy};
+use crate::domain::value_object::PasswordPolicy;
+use log::LevelFilter;
+use std::{env, net::IpAddr, thread};
 
 pub struct EnvVar {
     pub app_name: String,
-    pub access_token_secret: Vec<u8>,
+    pub log_level: LevelFilter,
+    pub database_path: Option<String>,
+    pub access_token_secret: Option<Vec<u8>>,
+    pub access_token_private_key_path: Option<String>,
+    pub access_token_key_id: Option<String>,
+    pub access_token_keys_dir: Option<String>,
+    pub access_token_keys_reload_seconds: u64,
     pub access_token_valid_seconds: u64,
     pub refresh_token_secret: Vec<u8>,
     pub refresh_token_valid_seconds: u64,
+    pub password_hash_memory_kib: u32,
+    pub password_hash_iterations: u32,
+    pub password_hash_parallelism: u32,
+    pub password_min_length: usize,
+    pub password_max_length: usize,
+    pub password_require_lowercase: bool,
+    pub password_require_uppercase: bool,
+    pub password_require_digit: bool,
+    pub password_require_symbol: bool,
+    pub password_forbid_common: bool,
+    pub pwned_passwords_dir: Option<String>,
+    pub hashing_threads: usize,
+    pub hashing_queue_size: usize,
+    pub login_max_failures: u32,
+    pub login_ip_max_failures: u32,
+    pub login_backoff_base_seconds: u64,
+    pub login_backoff_max_seconds: u64,
+    pub login_lockout_seconds: u64,
+    pub trusted_proxies: Vec<IpAddr>,
+    pub rate_limit_capacity: u32,
+    pub rate_limit_refill_seconds: u64,
+    pub rate_limit_signin_capacity: u32,
+    pub rate_limit_signin_refill_seconds: u64,
+    pub rate_limit_signup_capacity: u32,
+    pub rate_limit_signup_refill_seconds: u64,
+    pub email_verification_token_secret: Vec<u8>,
+    pub email_verification_token_valid_seconds: u64,
+    pub email_verification_url: String,
+    pub require_verified_email: bool,
+    pub password_reset_url: String,
+    pub password_reset_token_valid_seconds: u64,
+    pub mfa_token_secret: Vec<u8>,
+    pub mfa_token_valid_seconds: u64,
+    pub magic_link_token_secret: Vec<u8>,
+    pub magic_link_url: String,
+    pub magic_link_token_valid_seconds: u64,
+    pub webauthn_rp_id: String,
+    pub webauthn_rp_name: Option<String>,
+    pub webauthn_origin: String,
+    pub passkey_challenge_valid_seconds: u64,
+    pub oauth_clients_path: Option<String>,
+    pub authorization_code_valid_seconds: u64,
+    pub mail_sender: String,
+    pub mail_from: String,
+    pub mail_outbox_dir: String,
+    pub smtp_host: Option<String>,
+    pub smtp_port: u16,
+    pub smtp_username: Option<String>,
+    pub smtp_password: Option<String>,
+}
+
+impl EnvVar {
+    pub fn password_policy(&self) -> PasswordPolicy {
+        PasswordPolicy {
+            min_length: self.password_min_length,
+            max_length: self.password_max_length,
+            require_lowercase: self.password_require_lowercase,
+            require_uppercase: self.password_require_uppercase,
+            require_digit: self.password_require_digit,
+            require_symbol: self.password_require_symbol,
+            forbid_common: self.password_forbid_common,
+        }
+    }
+
+    pub fn login_throttle_policy(&self) -> LoginThrottlePolicy {
+        LoginThrottlePolicy {
+            max_failures: self.login_max_failures,
+            ip_max_failures: self.login_ip_max_failures,
+            backoff_base_seconds: self.login_backoff_base_seconds,
+            backoff_max_seconds: self.login_backoff_max_seconds,
+            lockout_seconds: self.login_lockout_seconds,
+        }
+    }
+
+    pub fn password_reset_policy(&self) -> PasswordResetPolicy {
+        PasswordResetPolicy {
+            reset_url: self.password_reset_url.clone(),
+            token_valid_seconds: self.password_reset_token_valid_seconds,
+        }
+    }
 }
 
 pub fn get_envvar() -> EnvVar {
     EnvVar {
         app_name: env::var("APP_NAME").unwrap(),
-        access_token_secret: env::var("ACCESS_TOKEN_SECRET").unwrap().as_bytes().to_vec(),
+        log_level: env::var("LOG_LEVEL").map_or(LevelFilter::Info, |level| level.parse().unwrap()),
+        database_path: env::var("DATABASE_PATH").ok(),
+        access_token_secret: env::var("ACCESS_TOKEN_SECRET")
+            .ok()
+            .map(|secret| secret.as_bytes().to_vec()),
+        access_token_private_key_path: env::var("ACCESS_TOKEN_PRIVATE_KEY_PATH").ok(),
+        access_token_key_id: env::var("ACCESS_TOKEN_KEY_ID").ok(),
+        access_token_keys_dir: env::var("ACCESS_TOKEN_KEYS_DIR").ok(),
+        access_token_keys_reload_seconds: env::var("ACCESS_TOKEN_KEYS_RELOAD_SECONDS")
+            .map_or(60, |seconds| seconds.parse().unwrap()),
         access_token_valid_seconds: env::var("ACCESS_TOKEN_VALID_SECONDS")
             .unwrap()
             .parse()
@@ -24,5 +122,99 @@ pub fn get_envvar() -> EnvVar {
             .unwrap()
             .parse()
             .unwrap(),
+        password_hash_memory_kib: env::var("PASSWORD_HASH_MEMORY_KIB")
+            .map_or(19456, |memory| memory.parse().unwrap()),
+        password_hash_iterations: env::var("PASSWORD_HASH_ITERATIONS")
+            .map_or(2, |iterations| iterations.parse().unwrap()),
+        password_hash_parallelism: env::var("PASSWORD_HASH_PARALLELISM")
+            .map_or(1, |parallelism| parallelism.parse().unwrap()),
+        password_min_length: env::var("PASSWORD_MIN_LENGTH")
+            .map_or(8, |length| length.parse().unwrap()),
+        password_max_length: env::var("PASSWORD_MAX_LENGTH")
+            .map_or(128, |length| length.parse().unwrap()),
+        password_require_lowercase: env::var("PASSWORD_REQUIRE_LOWERCASE")
+            .is_ok_and(|flag| flag.parse().unwrap()),
+        password_require_uppercase: env::var("PASSWORD_REQUIRE_UPPERCASE")
+            .is_ok_and(|flag| flag.parse().unwrap()),
+        password_require_digit: env::var("PASSWORD_REQUIRE_DIGIT")
+            .is_ok_and(|flag| flag.parse().unwrap()),
+        password_require_symbol: env::var("PASSWORD_REQUIRE_SYMBOL")
+            .is_ok_and(|flag| flag.parse().unwrap()),
+        password_forbid_common: env::var("PASSWORD_FORBID_COMMON")
+            .map_or(true, |flag| flag.parse().unwrap()),
+        pwned_passwords_dir: env::var("PWNED_PASSWORDS_DIR").ok(),
+        hashing_threads: env::var("HASHING_THREADS").map_or_else(
+            |_| thread::available_parallelism().map_or(1, |threads| threads.get()),
+            |threads| threads.parse().unwrap(),
+        ),
+        hashing_queue_size: env::var("HASHING_QUEUE_SIZE").map_or(64, |size| size.parse().unwrap()),
+        login_max_failures: env::var("LOGIN_MAX_FAILURES")
+            .map_or(5, |failures| failures.parse().unwrap()),
+        login_ip_max_failures: env::var("LOGIN_IP_MAX_FAILURES")
+            .map_or(50, |failures| failures.parse().unwrap()),
+        login_backoff_base_seconds: env::var("LOGIN_BACKOFF_BASE_SECONDS")
+            .map_or(1, |seconds| seconds.parse().unwrap()),
+        login_backoff_max_seconds: env::var("LOGIN_BACKOFF_MAX_SECONDS")
+            .map_or(60, |seconds| seconds.parse().unwrap()),
+        login_lockout_seconds: env::var("LOGIN_LOCKOUT_SECONDS")
+            .map_or(900, |seconds| seconds.parse().unwrap()),
+        trusted_proxies: env::var("TRUSTED_PROXIES").map_or(vec![], |proxies| {
+            proxies
+                .split(',')
+                .map(|proxy| proxy.trim().parse().unwrap())
+                .collect()
+        }),
+        rate_limit_capacity: env::var("RATE_LIMIT_CAPACITY")
+            .map_or(30, |capacity| capacity.parse().unwrap()),
+        rate_limit_refill_seconds: env::var("RATE_LIMIT_REFILL_SECONDS")
+            .map_or(2, |seconds| seconds.parse().unwrap()),
+        rate_limit_signin_capacity: env::var("RATE_LIMIT_SIGNIN_CAPACITY")
+            .map_or(10, |capacity| capacity.parse().unwrap()),
+        rate_limit_signin_refill_seconds: env::var("RATE_LIMIT_SIGNIN_REFILL_SECONDS")
+            .map_or(6, |seconds| seconds.parse().unwrap()),
+        rate_limit_signup_capacity: env::var("RATE_LIMIT_SIGNUP_CAPACITY")
+            .map_or(3, |capacity| capacity.parse().unwrap()),
+        rate_limit_signup_refill_seconds: env::var("RATE_LIMIT_SIGNUP_REFILL_SECONDS")
+            .map_or(60, |seconds| seconds.parse().unwrap()),
+        email_verification_token_secret: env::var("EMAIL_VERIFICATION_TOKEN_SECRET")
+            .unwrap()
+            .as_bytes()
+            .to_vec(),
+        email_verification_token_valid_seconds: env::var("EMAIL_VERIFICATION_TOKEN_VALID_SECONDS")
+            .map_or(86400, |seconds| seconds.parse().unwrap()),
+        email_verification_url: env::var("EMAIL_VERIFICATION_URL")
+            .unwrap_or("http://localhost:8080/verify-email".to_string()),
+        require_verified_email: env::var("REQUIRE_VERIFIED_EMAIL")
+            .is_ok_and(|flag| flag.parse().unwrap()),
+        password_reset_url: env::var("PASSWORD_RESET_URL")
+            .unwrap_or("http://localhost:8080/password/reset".to_string()),
+        password_reset_token_valid_seconds: env::var("PASSWORD_RESET_TOKEN_VALID_SECONDS")
+            .map_or(900, |seconds| seconds.parse().unwrap()),
+        mfa_token_secret: env::var("MFA_TOKEN_SECRET").unwrap().as_bytes().to_vec(),
+        mfa_token_valid_seconds: env::var("MFA_TOKEN_VALID_SECONDS")
+            .map_or(300, |seconds| seconds.parse().unwrap()),
+        magic_link_token_secret: env::var("MAGIC_LINK_TOKEN_SECRET")
+            .unwrap()
+            .as_bytes()
+            .to_vec(),
+        magic_link_url: env::var("MAGIC_LINK_URL")
+            .unwrap_or("http://localhost:8080/signin/magic-link/callback".to_string()),
+        magic_link_token_valid_seconds: env::var("MAGIC_LINK_TOKEN_VALID_SECONDS")
+            .map_or(600, |seconds| seconds.parse().unwrap()),
+        webauthn_rp_id: env::var("WEBAUTHN_RP_ID").unwrap_or("localhost".to_string()),
+        webauthn_rp_name: env::var("WEBAUTHN_RP_NAME").ok(),
+        webauthn_origin: env::var("WEBAUTHN_ORIGIN").unwrap_or("http://localhost:8080".to_string()),
+        passkey_challenge_valid_seconds: env::var("PASSKEY_CHALLENGE_VALID_SECONDS")
+            .map_or(300, |seconds| seconds.parse().unwrap()),
+        oauth_clients_path: env::var("OAUTH_CLIENTS_PATH").ok(),
+        authorization_code_valid_seconds: env::var("AUTHORIZATION_CODE_VALID_SECONDS")
+            .map_or(60, |seconds| seconds.parse().unwrap()),
+        mail_sender: env::var("MAIL_SENDER").unwrap_or("outbox".to_string()),
+        mail_from: env::var("MAIL_FROM").unwrap_or("no-reply@localhost".to_string()),
+        mail_outbox_dir: env::var("MAIL_OUTBOX_DIR").unwrap_or("outbox".to_string()),
+        smtp_host: env::var("SMTP_HOST").ok(),
+        smtp_port: env::var("SMTP_PORT").map_or(587, |port| port.parse().unwrap()),
+        smtp_username: env::var("SMTP_USERNAME").ok(),
+        smtp_password: env::var("SMTP_PASSWORD").ok(),
     }
 }
@@ -0,0 +1,5 @@
+use uuid::Uuid;
+
+pub fn generate_id() -> String {
+    Uuid::new_v4().to_string()
+}
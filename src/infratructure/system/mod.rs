@@ -1,5 +1,13 @@
+mod blocking_pool;
 mod envvar;
+mod id;
+mod logger;
+mod secret;
 mod time;
 
+pub use blocking_pool::{BlockingPool, PoolUnavailable};
 pub use envvar::{EnvVar, get_envvar};
+pub use id::generate_id;
+pub use logger::init_logger;
+pub use secret::{generate_recovery_code, generate_secret, hash_secret};
 pub use time::get_systime;
@@ -0,0 +1,68 @@
+use argon2::password_hash::rand_core::{OsRng, RngCore};
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use sha2::{Digest, Sha256};
+
+const RECOVERY_CODE_ALPHABET: &[u8; 32] = b"abcdefghijkmnpqrstuvwxyz23456789";
+
+pub fn generate_secret() -> String {
+    let mut bytes = [0u8; 32];
+    OsRng.fill_bytes(&mut bytes);
+    URL_SAFE_NO_PAD.encode(bytes)
+}
+
+pub fn generate_recovery_code() -> String {
+    let mut bytes = [0u8; 10];
+    OsRng.fill_bytes(&mut bytes);
+    let code: String = bytes
+        .iter()
+        .map(|byte| RECOVERY_CODE_ALPHABET[*byte as usize % RECOVERY_CODE_ALPHABET.len()] as char)
+        .collect();
+    format!("{}-{}", &code[..5], &code[5..])
+}
+
+pub fn hash_secret(secret: &str) -> String {
+    URL_SAFE_NO_PAD.encode(Sha256::digest(secret.as_bytes()))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn generate_secret_should_return_distinct_256_bit_secrets() {
+        let secret = generate_secret();
+
+        assert_eq!(URL_SAFE_NO_PAD.decode(&secret).unwrap().len(), 32);
+        assert_ne!(secret, generate_secret());
+    }
+
+    #[test]
+    fn generate_recovery_code_should_return_distinct_grouped_codes() {
+        let code = generate_recovery_code();
+
+        assert_eq!(code.len(), 11);
+        assert_eq!(code.as_bytes()[5], b'-');
+        assert!(
+            code.bytes()
+                .filter(|b| *b != b'-')
+                .all(|b| RECOVERY_CODE_ALPHABET.contains(&b))
+        );
+        assert_ne!(code, generate_recovery_code());
+    }
+
+    #[test]
+    fn hash_secret_given_secret_should_return_sha256_digest() {
+        assert_eq!(
+            hash_secret("secret"),
+            "K7gNU3sdo-OL0wNhqoVWhr3g6s1xYv72ol_pe_Unols"
+        );
+    }
+
+    #[test]
+    fn hash_secret_given_code_verifier_should_derive_pkce_s256_challenge() {
+        assert_eq!(
+            hash_secret("dBjftJeZ4CVP-mB92K27uhbUJU1p1r_wW1gFWFOEjXk"),
+            "E9Melhoa2OwvFrEMTJguCHaoeK1t8URWbuGJSstw-cM"
+        );
+    }
+}
@@ -0,0 +1,389 @@
+use actix_web::{
+    HttpRequest, HttpResponse, ResponseError,
+    error::{JsonPayloadError, UrlencodedError},
+    http::{
+        StatusCode,
+        header::{CACHE_CONTROL, RETRY_AFTER, WWW_AUTHENTICATE},
+    },
+};
+use serde::Serialize;
+use std::fmt::Display;
+
+use crate::domain::error::ValidationError;
+
+const RETRY_AFTER_SECONDS: u64 = 1;
+
+#[derive(Debug)]
+pub enum ApiError {
+    InvalidRequest(String),
+    InvalidEmail(ValidationError),
+    InvalidPassword(ValidationError),
+    EmailTaken,
+    InvalidCredentials,
+    EmailNotVerified,
+    MfaRequired(String),
+    InvalidMfaCode,
+    MfaAlreadyEnabled,
+    MfaNotEnrolled,
+    InvalidPasskey,
+    PasskeyTaken,
+    InvalidToken,
+    InsufficientScope,
+    TooManyAttempts(u64),
+    RateLimited(u64),
+    ServiceBusy,
+}
+
+impl ApiError {
+    pub fn code(&self) -> &'static str {
+        match self {
+            ApiError::InvalidRequest(_) => "invalid_request",
+            ApiError::InvalidEmail(_) => "invalid_email",
+            ApiError::InvalidPassword(_) => "invalid_password",
+            ApiError::EmailTaken => "email_taken",
+            ApiError::InvalidCredentials => "invalid_credentials",
+            ApiError::EmailNotVerified => "email_not_verified",
+            ApiError::MfaRequired(_) => "mfa_required",
+            ApiError::InvalidMfaCode => "invalid_mfa_code",
+            ApiError::MfaAlreadyEnabled => "mfa_already_enabled",
+            ApiError::MfaNotEnrolled => "mfa_not_enrolled",
+            ApiError::InvalidPasskey => "invalid_passkey",
+            ApiError::PasskeyTaken => "passkey_taken",
+            ApiError::InvalidToken => "invalid_token",
+            ApiError::InsufficientScope => "insufficient_scope",
+            ApiError::TooManyAttempts(_) => "too_many_attempts",
+            ApiError::RateLimited(_) => "rate_limited",
+            ApiError::ServiceBusy => "service_busy",
+        }
+    }
+}
+
+impl Display for ApiError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ApiError::InvalidRequest(message) => write!(f, "{}", message),
+            ApiError::InvalidEmail(err) | ApiError::InvalidPassword(err) => {
+                write!(f, "{}", err.message())
+            }
+            ApiError::EmailTaken => write!(f, "Email address is already registered"),
+            ApiError::InvalidCredentials => write!(f, "Email or password is incorrect"),
+            ApiError::EmailNotVerified => write!(f, "Email address is not verified"),
+            ApiError::MfaRequired(_) => write!(f, "Second factor is required to sign in"),
+            ApiError::InvalidMfaCode => write!(f, "Authentication code is incorrect"),
+            ApiError::MfaAlreadyEnabled => {
+                write!(f, "Two-factor authentication is already enabled")
+            }
+            ApiError::MfaNotEnrolled => write!(f, "Two-factor enrollment has not been started"),
+            ApiError::InvalidPasskey => write!(f, "Passkey could not be verified"),
+            ApiError::PasskeyTaken => write!(f, "Passkey is already registered"),
+            ApiError::InvalidToken => write!(f, "Token is invalid"),
+            ApiError::InsufficientScope => {
+                write!(f, "Token does not grant the required scope")
+            }
+            ApiError::TooManyAttempts(retry_after) => write!(
+                f,
+                "Too many failed sign-in attempts, retry in {} seconds",
+                retry_after
+            ),
+            ApiError::RateLimited(retry_after) => {
+                write!(f, "Too many requests, retry in {} seconds", retry_after)
+            }
+            ApiError::ServiceBusy => write!(f, "Server is busy, please retry later"),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct ProblemDetails<'a> {
+    #[serde(rename = "type")]
+    pub problem_type: &'static str,
+    pub title: &'static str,
+    pub status: u16,
+    pub detail: String,
+    pub code: &'static str,
+    #[serde(skip_serializing_if = "<[_]>::is_empty")]
+    pub violations: &'a [String],
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mfa_token: Option<&'a str>,
+}
+
+impl ResponseError for ApiError {
+    fn status_code(&self) -> StatusCode {
+        match self {
+            ApiError::InvalidRequest(_) => StatusCode::BAD_REQUEST,
+            ApiError::InvalidEmail(_) | ApiError::InvalidPassword(_) => {
+                StatusCode::UNPROCESSABLE_ENTITY
+            }
+            ApiError::EmailTaken
+            | ApiError::MfaAlreadyEnabled
+            | ApiError::MfaNotEnrolled
+            | ApiError::PasskeyTaken => StatusCode::CONFLICT,
+            ApiError::InvalidCredentials
+            | ApiError::MfaRequired(_)
+            | ApiError::InvalidMfaCode
+            | ApiError::InvalidPasskey
+            | ApiError::InvalidToken => StatusCode::UNAUTHORIZED,
+            ApiError::EmailNotVerified | ApiError::InsufficientScope => StatusCode::FORBIDDEN,
+            ApiError::TooManyAttempts(_) | ApiError::RateLimited(_) => {
+                StatusCode::TOO_MANY_REQUESTS
+            }
+            ApiError::ServiceBusy => StatusCode::SERVICE_UNAVAILABLE,
+        }
+    }
+
+    fn error_response(&self) -> HttpResponse {
+        let status = self.status_code();
+        let mut response = HttpResponse::build(status);
+        match self {
+            ApiError::TooManyAttempts(retry_after) | ApiError::RateLimited(retry_after) => {
+                response.insert_header((RETRY_AFTER, *retry_after));
+            }
+            ApiError::ServiceBusy => {
+                response.insert_header((RETRY_AFTER, RETRY_AFTER_SECONDS));
+            }
+            _ => {}
+        }
+        response
+            .content_type("application/problem+json")
+            .json(ProblemDetails {
+                problem_type: "about:blank",
+                title: status.canonical_reason().unwrap_or_default(),
+                status: status.as_u16(),
+                detail: self.to_string(),
+                code: self.code(),
+                violations: match self {
+                    ApiError::InvalidEmail(err) | ApiError::InvalidPassword(err) => {
+                        err.violations()
+                    }
+                    _ => &[],
+                },
+                mfa_token: match self {
+                    ApiError::MfaRequired(mfa_token) => Some(mfa_token),
+                    _ => None,
+                },
+            })
+    }
+}
+
+pub fn json_error_handler(err: JsonPayloadError, _: &HttpRequest) -> actix_web::Error {
+    ApiError::InvalidRequest(err.to_string()).into()
+}
+
+#[derive(Debug)]
+pub enum OAuthError {
+    InvalidRequest(String),
+    InvalidClient,
+    InvalidGrant,
+    InvalidScope,
+    UnsupportedGrantType,
+    ServiceBusy,
+}
+
+impl OAuthError {
+    pub fn code(&self) -> &'static str {
+        match self {
+            OAuthError::InvalidRequest(_) => "invalid_request",
+            OAuthError::InvalidClient => "invalid_client",
+            OAuthError::InvalidGrant => "invalid_grant",
+            OAuthError::InvalidScope => "invalid_scope",
+            OAuthError::UnsupportedGrantType => "unsupported_grant_type",
+            OAuthError::ServiceBusy => "temporarily_unavailable",
+        }
+    }
+}
+
+impl Display for OAuthError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OAuthError::InvalidRequest(message) => write!(f, "{}", message),
+            OAuthError::InvalidClient => write!(f, "Client authentication failed"),
+            OAuthError::InvalidGrant => {
+                write!(f, "Authorization grant is invalid, expired or already used")
+            }
+            OAuthError::InvalidScope => {
+                write!(f, "Requested scope is not allowed for this client")
+            }
+            OAuthError::UnsupportedGrantType => write!(f, "Grant type is not supported"),
+            OAuthError::ServiceBusy => write!(f, "Server is busy, please retry later"),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct OAuthErrorBody {
+    pub error: &'static str,
+    pub error_description: String,
+}
+
+impl ResponseError for OAuthError {
+    fn status_code(&self) -> StatusCode {
+        match self {
+            OAuthError::InvalidClient => StatusCode::UNAUTHORIZED,
+            OAuthError::ServiceBusy => StatusCode::SERVICE_UNAVAILABLE,
+            _ => StatusCode::BAD_REQUEST,
+        }
+    }
+
+    fn error_response(&self) -> HttpResponse {
+        let mut response = HttpResponse::build(self.status_code());
+        match self {
+            OAuthError::InvalidClient => {
+                response.insert_header((WWW_AUTHENTICATE, "Basic realm=\"token\""));
+            }
+            OAuthError::ServiceBusy => {
+                response.insert_header((RETRY_AFTER, RETRY_AFTER_SECONDS));
+            }
+            _ => {}
+        }
+        response
+            .insert_header((CACHE_CONTROL, "no-store"))
+            .json(OAuthErrorBody {
+                error: self.code(),
+                error_description: self.to_string(),
+            })
+    }
+}
+
+pub fn form_error_handler(err: UrlencodedError, _: &HttpRequest) -> actix_web::Error {
+    OAuthError::InvalidRequest(err.to_string()).into()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use actix_web::{body::to_bytes, http::header::CONTENT_TYPE};
+
+    #[actix_web::test]
+    async fn error_response_given_invalid_email_should_return_problem_details() {
+        let err = ApiError::InvalidEmail(ValidationError::new("Invalid email address"));
+
+        let response = err.error_response();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(
+            response.headers().get(CONTENT_TYPE).unwrap(),
+            "application/problem+json"
+        );
+        let body: serde_json::Value =
+            serde_json::from_slice(&to_bytes(response.into_body()).await.unwrap()).unwrap();
+        assert_eq!(
+            body,
+            serde_json::json!({
+                "type": "about:blank",
+                "title": "Unprocessable Entity",
+                "status": 422,
+                "detail": "Invalid email address",
+                "code": "invalid_email",
+            })
+        );
+    }
+
+    #[actix_web::test]
+    async fn error_response_given_invalid_password_should_list_violations() {
+        let err = ApiError::InvalidPassword(ValidationError::with_violations(
+            "Password does not satisfy the password policy",
+            vec![
+                "Password must be at least 8 characters".to_string(),
+                "Password is too common".to_string(),
+            ],
+        ));
+
+        let response = err.error_response();
+
+        let body: serde_json::Value =
+            serde_json::from_slice(&to_bytes(response.into_body()).await.unwrap()).unwrap();
+        assert_eq!(body["code"], "invalid_password");
+        assert_eq!(
+            body["violations"],
+            serde_json::json!([
+                "Password must be at least 8 characters",
+                "Password is too common",
+            ])
+        );
+    }
+
+    #[actix_web::test]
+    async fn error_response_given_mfa_required_should_include_mfa_token() {
+        let err = ApiError::MfaRequired("mfa_token".to_string());
+
+        let response = err.error_response();
+
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        let body: serde_json::Value =
+            serde_json::from_slice(&to_bytes(response.into_body()).await.unwrap()).unwrap();
+        assert_eq!(body["code"], "mfa_required");
+        assert_eq!(body["mfa_token"], "mfa_token");
+    }
+
+    #[test]
+    fn error_response_given_service_busy_should_ask_to_retry() {
+        let response = ApiError::ServiceBusy.error_response();
+
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(response.headers().get(RETRY_AFTER).unwrap(), "1");
+    }
+
+    #[test]
+    fn error_response_given_too_many_attempts_should_set_retry_after() {
+        let response = ApiError::TooManyAttempts(30).error_response();
+
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(response.headers().get(RETRY_AFTER).unwrap(), "30");
+    }
+
+    #[test]
+    fn status_code_given_each_error_should_map_to_http_status() {
+        let test_cases = vec![
+            (ApiError::InvalidRequest("bad".to_string()), 400),
+            (ApiError::EmailTaken, 409),
+            (ApiError::InvalidCredentials, 401),
+            (ApiError::EmailNotVerified, 403),
+            (ApiError::MfaRequired("mfa_token".to_string()), 401),
+            (ApiError::InvalidMfaCode, 401),
+            (ApiError::MfaAlreadyEnabled, 409),
+            (ApiError::MfaNotEnrolled, 409),
+            (ApiError::InvalidPasskey, 401),
+            (ApiError::PasskeyTaken, 409),
+            (ApiError::InvalidToken, 401),
+            (ApiError::InsufficientScope, 403),
+            (ApiError::TooManyAttempts(30), 429),
+            (ApiError::RateLimited(30), 429),
+            (ApiError::ServiceBusy, 503),
+        ];
+
+        for (err, status) in test_cases {
+            assert_eq!(err.status_code().as_u16(), status);
+        }
+    }
+
+    #[actix_web::test]
+    async fn oauth_error_response_given_invalid_grant_should_follow_rfc_6749() {
+        let response = OAuthError::InvalidGrant.error_response();
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(response.headers().get(CACHE_CONTROL).unwrap(), "no-store");
+        let body: serde_json::Value =
+            serde_json::from_slice(&to_bytes(response.into_body()).await.unwrap()).unwrap();
+        assert_eq!(
+            body,
+            serde_json::json!({
+                "error": "invalid_grant",
+                "error_description": "Authorization grant is invalid, expired or already used",
+            })
+        );
+    }
+
+    #[actix_web::test]
+    async fn oauth_error_response_given_invalid_client_should_challenge_basic_auth() {
+        let response = OAuthError::InvalidClient.error_response();
+
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(
+            response.headers().get(WWW_AUTHENTICATE).unwrap(),
+            "Basic realm=\"token\""
+        );
+        let body: serde_json::Value =
+            serde_json::from_slice(&to_bytes(response.into_body()).await.unwrap()).unwrap();
+        assert_eq!(body["error"], "invalid_client");
+    }
+}
@@ -0,0 +1,232 @@
+use actix_web::{FromRequest, HttpRequest, dev::Payload, http::header::AUTHORIZATION, web};
+use base64::{Engine, engine::general_purpose::STANDARD};
+use percent_encoding::percent_decode_str;
+use std::{
+    convert::Infallible,
+    future::{Future, Ready, ready},
+};
+
+use super::{error::ApiError, rate_limit::RateLimiter};
+use crate::application::service::auth::{TokenClaims, TokenVerifier};
+use crate::domain::value_object::UserId;
+use crate::infratructure::{
+    auth::{Argon2Hasher, ExpectedClaims, JWTVerifier, TokenKeys},
+    repository::SessionStorage,
+    system::{BlockingPool, EnvVar, PoolUnavailable, get_systime},
+};
+
+pub struct CurrentUser {
+    pub id: UserId,
+}
+
+impl FromRequest for CurrentUser {
+    type Error = ApiError;
+    type Future = Ready<Result<CurrentUser, ApiError>>;
+
+    fn from_request(request: &HttpRequest, _: &mut Payload) -> Self::Future {
+        ready(authenticate(request))
+    }
+}
+
+pub struct AccessToken {
+    pub scopes: Vec<String>,
+}
+
+impl AccessToken {
+    pub fn require_scope(&self, scope: &str) -> Result<(), ApiError> {
+        if self.scopes.iter().any(|granted| granted == scope) {
+            Ok(())
+        } else {
+            Err(ApiError::InsufficientScope)
+        }
+    }
+}
+
+impl FromRequest for AccessToken {
+    type Error = ApiError;
+    type Future = Ready<Result<AccessToken, ApiError>>;
+
+    fn from_request(request: &HttpRequest, _: &mut Payload) -> Self::Future {
+        ready(verify_access_token(request).map(|claims| {
+            AccessToken {
+                scopes: claims
+                    .scope
+                    .unwrap_or_default()
+                    .split_whitespace()
+                    .map(String::from)
+                    .collect(),
+            }
+        }))
+    }
+}
+
+pub struct ClientIp {
+    pub ip: String,
+}
+
+impl FromRequest for ClientIp {
+    type Error = Infallible;
+    type Future = Ready<Result<ClientIp, Infallible>>;
+
+    fn from_request(request: &HttpRequest, _: &mut Payload) -> Self::Future {
+        let rate_limiter = request
+            .app_data::<web::Data<RateLimiter>>()
+            .expect("RateLimiter should be registered");
+
+        ready(Ok(ClientIp {
+            ip: rate_limiter.client_ip(request),
+        }))
+    }
+}
+
+pub struct PasswordHashing {
+    hasher: web::Data<Argon2Hasher>,
+    pool: web::Data<BlockingPool>,
+}
+
+impl PasswordHashing {
+    pub fn run<T, F>(&self, job: F) -> impl Future<Output = Result<T, PoolUnavailable>> + 'static
+    where
+        T: Send + 'static,
+        F: FnOnce(&Argon2Hasher) -> T + Send + 'static,
+    {
+        let hasher = self.hasher.clone();
+        self.pool.run(move || job(&hasher))
+    }
+}
+
+impl FromRequest for PasswordHashing {
+    type Error = Infallible;
+    type Future = Ready<Result<PasswordHashing, Infallible>>;
+
+    fn from_request(request: &HttpRequest, _: &mut Payload) -> Self::Future {
+        ready(Ok(PasswordHashing {
+            hasher: request
+                .app_data::<web::Data<Argon2Hasher>>()
+                .expect("Argon2Hasher should be registered")
+                .clone(),
+            pool: request
+                .app_data::<web::Data<BlockingPool>>()
+                .expect("BlockingPool should be registered")
+                .clone(),
+        }))
+    }
+}
+
+pub fn bearer_token(request: &HttpRequest) -> Option<&str> {
+    request
+        .headers()
+        .get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+}
+
+pub fn basic_credentials(request: &HttpRequest) -> Option<(String, String)> {
+    let encoded = request
+        .headers()
+        .get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Basic "))?;
+    let decoded = String::from_utf8(STANDARD.decode(encoded).ok()?).ok()?;
+    let (username, password) = decoded.split_once(':')?;
+
+    Some((form_decode(username)?, form_decode(password)?))
+}
+
+fn form_decode(value: &str) -> Option<String> {
+    percent_decode_str(&value.replace('+', " "))
+        .decode_utf8()
+        .ok()
+        .map(String::from)
+}
+
+fn authenticate(request: &HttpRequest) -> Result<CurrentUser, ApiError> {
+    let claims = verify_access_token(request)?;
+    let id = UserId::new(&claims.sub).map_err(|_| ApiError::InvalidToken)?;
+
+    Ok(CurrentUser { id })
+}
+
+fn verify_access_token(request: &HttpRequest) -> Result<TokenClaims, ApiError> {
+    let access_token = bearer_token(request).ok_or(ApiError::InvalidToken)?;
+    let envvar = request
+        .app_data::<web::Data<EnvVar>>()
+        .expect("EnvVar should be registered");
+    let token_keys = request
+        .app_data::<web::Data<TokenKeys>>()
+        .expect("TokenKeys should be registered");
+    let session_storage = request
+        .app_data::<web::Data<SessionStorage>>()
+        .expect("SessionStorage should be registered");
+    let revoked_token_repository = session_storage.revoked_token_repository();
+    let access_token_keys = token_keys.access_token.read().unwrap();
+    let access_token_verifier = JWTVerifier::new(
+        &access_token_keys,
+        ExpectedClaims {
+            iss: envvar.app_name.clone(),
+            aud: envvar.app_name.clone(),
+            now: get_systime(),
+        },
+        &revoked_token_repository,
+    );
+
+    access_token_verifier
+        .verify(access_token)
+        .map_err(|_| ApiError::InvalidToken)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use actix_web::test::TestRequest;
+
+    #[test]
+    fn bearer_token_given_authorization_header_should_return_token() {
+        let test_cases = vec![
+            (Some("Bearer access_token"), Some("access_token")),
+            (Some("Basic dXNlcjpwYXNz"), None),
+            (None, None),
+        ];
+
+        for (authorization, expected) in test_cases {
+            let mut request = TestRequest::default();
+            if let Some(authorization) = authorization {
+                request = request.insert_header((AUTHORIZATION, authorization));
+            }
+            let request = request.to_http_request();
+
+            assert_eq!(bearer_token(&request), expected);
+        }
+    }
+
+    #[test]
+    fn basic_credentials_given_authorization_header_should_return_decoded_credentials() {
+        let test_cases = vec![
+            (
+                Some("Basic YmlsbGluZy1qb2I6czNjcmV0"),
+                Some(("billing-job", "s3cret")),
+            ),
+            (
+                Some("Basic YmlsbGluZyUzQWpvYjpzJTJCM2NyZXQrJTNB"),
+                Some(("billing:job", "s+3cret :")),
+            ),
+            (Some("Basic bm8tc2VwYXJhdG9y"), None),
+            (Some("Basic !!!"), None),
+            (Some("Bearer access_token"), None),
+            (None, None),
+        ];
+
+        for (authorization, expected) in test_cases {
+            let mut request = TestRequest::default();
+            if let Some(authorization) = authorization {
+                request = request.insert_header((AUTHORIZATION, authorization));
+            }
+            let request = request.to_http_request();
+
+            assert_eq!(
+                basic_credentials(&request),
+                expected.map(|(username, password)| (username.to_string(), password.to_string()))
+            );
+        }
+    }
+}
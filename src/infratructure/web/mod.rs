@@ -1,3 +1,6 @@
+mod error;
+mod extractor;
+mod rate_limit;
 mod scope;
 mod server;
 
@@ -0,0 +1,388 @@
+use actix_web::{
+    Error, HttpRequest, ResponseError,
+    body::{EitherBody, MessageBody},
+    dev::{ServiceRequest, ServiceResponse},
+    http::header::{HeaderMap, HeaderName, HeaderValue},
+    middleware::Next,
+    web,
+};
+use std::{
+    collections::HashMap,
+    net::IpAddr,
+    sync::{Arc, Mutex},
+};
+
+use super::error::ApiError;
+use crate::infratructure::repository::GenericTableManager;
+
+const RATELIMIT_LIMIT: HeaderName = HeaderName::from_static("ratelimit-limit");
+const RATELIMIT_REMAINING: HeaderName = HeaderName::from_static("ratelimit-remaining");
+const RATELIMIT_RESET: HeaderName = HeaderName::from_static("ratelimit-reset");
+const RATELIMIT_POLICY: HeaderName = HeaderName::from_static("ratelimit-policy");
+const X_FORWARDED_FOR: &str = "x-forwarded-for";
+const DEFAULT_ROUTE: &str = "*";
+
+#[derive(Clone, Copy)]
+pub struct RateLimitRule {
+    pub capacity: u32,
+    pub refill_seconds: u64,
+}
+
+#[derive(Clone)]
+pub struct TokenBucket {
+    tokens: u32,
+    refilled_at: u64,
+}
+
+impl TokenBucket {
+    fn full(rule: &RateLimitRule, now: u64) -> TokenBucket {
+        TokenBucket {
+            tokens: rule.capacity,
+            refilled_at: now,
+        }
+    }
+
+    fn refill(&mut self, rule: &RateLimitRule, now: u64) {
+        let refills = now.saturating_sub(self.refilled_at) / rule.refill_seconds;
+        if refills == 0 {
+            return;
+        }
+        let tokens = (self.tokens as u64 + refills).min(rule.capacity as u64);
+        self.tokens = tokens as u32;
+        self.refilled_at = if self.tokens == rule.capacity {
+            now
+        } else {
+            self.refilled_at + refills * rule.refill_seconds
+        };
+    }
+
+    fn full_at(&self, rule: &RateLimitRule) -> u64 {
+        self.refilled_at + rule.capacity.saturating_sub(self.tokens) as u64 * rule.refill_seconds
+    }
+}
+
+pub struct RateLimitDecision {
+    pub allowed: bool,
+    pub limit: u32,
+    pub remaining: u32,
+    pub reset: u64,
+    pub retry_after: u64,
+    pub window: u64,
+}
+
+pub struct RateLimiter {
+    default_rule: RateLimitRule,
+    route_rules: HashMap<String, RateLimitRule>,
+    trusted_proxies: Vec<IpAddr>,
+    buckets: Arc<Mutex<HashMap<String, TokenBucket>>>,
+    get_timestamp: fn() -> u64,
+}
+
+impl RateLimiter {
+    pub fn new(
+        default_rule: RateLimitRule,
+        trusted_proxies: Vec<IpAddr>,
+        bucket_table: &GenericTableManager<TokenBucket>,
+        get_timestamp: fn() -> u64,
+    ) -> RateLimiter {
+        RateLimiter {
+            default_rule: RateLimiter::checked(default_rule),
+            route_rules: HashMap::new(),
+            trusted_proxies,
+            buckets: bucket_table.get_table(),
+            get_timestamp,
+        }
+    }
+
+    pub fn route(mut self, path: &str, rule: RateLimitRule) -> RateLimiter {
+        self.route_rules
+            .insert(path.to_string(), RateLimiter::checked(rule));
+        self
+    }
+
+    fn checked(rule: RateLimitRule) -> RateLimitRule {
+        assert!(rule.capacity > 0, "rate limit capacity should be positive");
+        assert!(
+            rule.refill_seconds > 0,
+            "rate limit refill period should be positive"
+        );
+        rule
+    }
+
+    pub fn check(&self, path: &str, client: &str) -> RateLimitDecision {
+        let (route, rule) = match self.route_rules.get_key_value(path) {
+            Some((route, rule)) => (route.as_str(), rule),
+            None => (DEFAULT_ROUTE, &self.default_rule),
+        };
+        let key = format!("{}:{}", route, client);
+        let now = (self.get_timestamp)();
+        let mut buckets = self.buckets.lock().unwrap();
+        if !buckets.contains_key(&key) {
+            buckets.retain(|key, bucket| {
+                let rule = key
+                    .split_once(':')
+                    .and_then(|(route, _)| self.route_rules.get(route))
+                    .unwrap_or(&self.default_rule);
+                bucket.full_at(rule) > now
+            });
+        }
+        let bucket = buckets
+            .entry(key)
+            .or_insert_with(|| TokenBucket::full(rule, now));
+        bucket.refill(rule, now);
+
+        let allowed = bucket.tokens > 0;
+        if allowed {
+            bucket.tokens -= 1;
+        }
+
+        RateLimitDecision {
+            allowed,
+            limit: rule.capacity,
+            remaining: bucket.tokens,
+            reset: bucket.full_at(rule).saturating_sub(now),
+            retry_after: (bucket.refilled_at + rule.refill_seconds).saturating_sub(now),
+            window: rule.capacity as u64 * rule.refill_seconds,
+        }
+    }
+
+    pub fn client_ip(&self, request: &HttpRequest) -> String {
+        let forwarded_for = request
+            .headers()
+            .get_all(X_FORWARDED_FOR)
+            .filter_map(|value| value.to_str().ok())
+            .collect::<Vec<_>>()
+            .join(",");
+
+        client_ip(
+            request.peer_addr().map(|addr| addr.ip()),
+            &forwarded_for,
+            &self.trusted_proxies,
+        )
+        .map(|ip| ip.to_string())
+        .unwrap_or_default()
+    }
+}
+
+pub fn client_ip(
+    peer: Option<IpAddr>,
+    forwarded_for: &str,
+    trusted_proxies: &[IpAddr],
+) -> Option<IpAddr> {
+    let mut client = peer?;
+    if !trusted_proxies.contains(&client) || forwarded_for.is_empty() {
+        return Some(client);
+    }
+
+    for hop in forwarded_for.rsplit(',') {
+        match hop.trim().parse() {
+            Ok(ip) => {
+                client = ip;
+                if !trusted_proxies.contains(&ip) {
+                    break;
+                }
+            }
+            Err(_) => break,
+        }
+    }
+
+    Some(client)
+}
+
+fn insert_headers(headers: &mut HeaderMap, decision: &RateLimitDecision) {
+    headers.insert(RATELIMIT_LIMIT, HeaderValue::from(decision.limit));
+    headers.insert(RATELIMIT_REMAINING, HeaderValue::from(decision.remaining));
+    headers.insert(RATELIMIT_RESET, HeaderValue::from(decision.reset));
+    headers.insert(
+        RATELIMIT_POLICY,
+        HeaderValue::from_str(&format!("{};w={}", decision.limit, decision.window)).unwrap(),
+    );
+}
+
+pub async fn rate_limit(
+    rate_limiter: web::Data<RateLimiter>,
+    request: ServiceRequest,
+    next: Next<impl MessageBody>,
+) -> Result<ServiceResponse<EitherBody<impl MessageBody>>, Error> {
+    let client = rate_limiter.client_ip(request.request());
+    let decision = rate_limiter.check(request.path(), &client);
+
+    let mut response = if decision.allowed {
+        next.call(request).await?.map_into_left_body()
+    } else {
+        request
+            .into_response(ApiError::RateLimited(decision.retry_after).error_response())
+            .map_into_right_body()
+    };
+    insert_headers(response.headers_mut(), &decision);
+
+    Ok(response)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use actix_web::{
+        App, HttpResponse,
+        http::StatusCode,
+        middleware::from_fn,
+        test::{TestRequest, call_service, init_service},
+    };
+
+    const SIGNUP_RULE: RateLimitRule = RateLimitRule {
+        capacity: 2,
+        refill_seconds: 60,
+    };
+    const DEFAULT_RULE: RateLimitRule = RateLimitRule {
+        capacity: 5,
+        refill_seconds: 1,
+    };
+
+    fn fake_get_timestamp() -> u64 {
+        1747636936
+    }
+
+    fn create_rate_limiter(table: &GenericTableManager<TokenBucket>) -> RateLimiter {
+        RateLimiter::new(
+            DEFAULT_RULE,
+            vec!["10.0.0.1".parse().unwrap(), "10.0.0.2".parse().unwrap()],
+            table,
+            fake_get_timestamp,
+        )
+        .route("/signup", SIGNUP_RULE)
+    }
+
+    #[test]
+    fn check_given_empty_bucket_should_reject_until_refilled() {
+        let table = GenericTableManager::<TokenBucket>::new();
+        let rate_limiter = create_rate_limiter(&table);
+
+        assert!(rate_limiter.check("/signup", "1.1.1.1").allowed);
+        let decision = rate_limiter.check("/signup", "1.1.1.1");
+        assert!(decision.allowed);
+        assert_eq!(decision.remaining, 0);
+        assert_eq!(decision.reset, 120);
+        let decision = rate_limiter.check("/signup", "1.1.1.1");
+        assert!(!decision.allowed);
+        assert_eq!(decision.retry_after, 60);
+    }
+
+    #[test]
+    fn check_given_elapsed_time_should_refill_tokens_up_to_capacity() {
+        let table = GenericTableManager::<TokenBucket>::new();
+        table.get_table().lock().unwrap().insert(
+            "/signup:1.1.1.1".to_string(),
+            TokenBucket {
+                tokens: 0,
+                refilled_at: fake_get_timestamp() - 90,
+            },
+        );
+        let rate_limiter = create_rate_limiter(&table);
+
+        let decision = rate_limiter.check("/signup", "1.1.1.1");
+
+        assert!(decision.allowed);
+        assert_eq!(decision.remaining, 0);
+        assert_eq!(decision.retry_after, 30);
+    }
+
+    #[test]
+    fn check_given_different_routes_and_clients_should_use_separate_buckets() {
+        let table = GenericTableManager::<TokenBucket>::new();
+        let rate_limiter = create_rate_limiter(&table);
+
+        rate_limiter.check("/signup", "1.1.1.1");
+        rate_limiter.check("/signup", "1.1.1.1");
+
+        assert!(!rate_limiter.check("/signup", "1.1.1.1").allowed);
+        assert!(rate_limiter.check("/signup", "2.2.2.2").allowed);
+        let decision = rate_limiter.check("/signin", "1.1.1.1");
+        assert!(decision.allowed);
+        assert_eq!(decision.limit, DEFAULT_RULE.capacity);
+    }
+
+    #[test]
+    fn check_given_new_key_should_prune_full_buckets() {
+        let table = GenericTableManager::<TokenBucket>::new();
+        table.get_table().lock().unwrap().insert(
+            "/signup:1.1.1.1".to_string(),
+            TokenBucket {
+                tokens: 1,
+                refilled_at: fake_get_timestamp() - 60,
+            },
+        );
+        let rate_limiter = create_rate_limiter(&table);
+
+        rate_limiter.check("/signup", "2.2.2.2");
+
+        assert!(
+            !table
+                .get_table()
+                .lock()
+                .unwrap()
+                .contains_key("/signup:1.1.1.1")
+        );
+    }
+
+    #[test]
+    fn client_ip_given_forwarded_for_should_trust_only_configured_proxies() {
+        let trusted_proxies: Vec<IpAddr> =
+            vec!["10.0.0.1".parse().unwrap(), "10.0.0.2".parse().unwrap()];
+        let test_cases = vec![
+            ("1.1.1.1", "9.9.9.9", "1.1.1.1"),
+            ("10.0.0.1", "", "10.0.0.1"),
+            ("10.0.0.1", "9.9.9.9", "9.9.9.9"),
+            ("10.0.0.1", "8.8.8.8, 9.9.9.9, 10.0.0.2", "9.9.9.9"),
+            ("10.0.0.1", "10.0.0.2", "10.0.0.2"),
+            ("10.0.0.1", "9.9.9.9, garbage", "10.0.0.1"),
+        ];
+
+        for (peer, forwarded_for, expected) in test_cases {
+            assert_eq!(
+                client_ip(Some(peer.parse().unwrap()), forwarded_for, &trusted_proxies),
+                Some(expected.parse().unwrap())
+            );
+        }
+    }
+
+    #[actix_web::test]
+    async fn rate_limit_given_requests_over_capacity_should_return_too_many_requests() {
+        let table = GenericTableManager::<TokenBucket>::new();
+        let app = init_service(
+            App::new()
+                .app_data(web::Data::new(create_rate_limiter(&table)))
+                .service(
+                    web::scope("")
+                        .wrap(from_fn(rate_limit))
+                        .route("/signup", web::post().to(HttpResponse::Ok)),
+                ),
+        )
+        .await;
+        let request = || {
+            TestRequest::post()
+                .uri("/signup")
+                .peer_addr("10.0.0.1:1234".parse().unwrap())
+                .insert_header((X_FORWARDED_FOR, "9.9.9.9"))
+                .to_request()
+        };
+
+        let response = call_service(&app, request()).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers().get(RATELIMIT_LIMIT).unwrap(), "2");
+        assert_eq!(response.headers().get(RATELIMIT_REMAINING).unwrap(), "1");
+        assert_eq!(response.headers().get(RATELIMIT_POLICY).unwrap(), "2;w=120");
+        call_service(&app, request()).await;
+        let response = call_service(&app, request()).await;
+
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(response.headers().get("retry-after").unwrap(), "60");
+        assert_eq!(response.headers().get(RATELIMIT_REMAINING).unwrap(), "0");
+        assert!(
+            table
+                .get_table()
+                .lock()
+                .unwrap()
+                .contains_key("/signup:9.9.9.9")
+        );
+    }
+}
@@ -1,16 +1,127 @@
-use actix_web::{HttpResponse, Responder, Scope, http::header::ContentType, post, web};
+use actix_web::{HttpRequest, HttpResponse, Scope, get, http::header::ContentType, post, web};
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
 use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
 
-use crate::application::use_case::{CreateUserDTO, SignInUseCase, SignUpUseCase};
-use crate::domain::{entity::User, value_object::EmailAddress};
+use crate::application::use_case::{
+    BeginPasskeySignInUseCase, CreateUserDTO, FailReason, LoginThrottle, MagicLinkSignInFailReason,
+    MagicLinkSignInUseCase, PasskeySignInFailReason, PasskeySignInUseCase, PasswordAuthenticator,
+    PasswordRules, SecondFactor, SendEmailVerificationUseCase, SendMagicLinkUseCase, SessionIssuer,
+    SignInMfaFailReason, SignInMfaUseCase, SignInResult, SignInUseCase, SignOutUseCase,
+    SignUpFailReason, SignUpUseCase, VerifyEmailUseCase,
+};
+use crate::domain::{
+    error,
+    value_object::{EmailAddress, UserId},
+};
 use crate::infratructure::{
-    auth::{BcryptHasher, BcryptValidator, InfraClaims, JWTIssuer},
-    repository::{GenericTableManager, InMemoryUserRepository},
-    system::{EnvVar, get_systime},
+    auth::{
+        Argon2Validator, ExpectedClaims, InfraClaims, JWTIssuer, JWTVerifier, PwnedPasswordsCorpus,
+        TokenKeys, Totp, WebAuthn,
+    },
+    mail::Mailer,
+    repository::{SessionStorage, UserStorage},
+    system::{BlockingPool, EnvVar, generate_id, generate_secret, get_systime, hash_secret},
+    web::{
+        error::ApiError,
+        extractor::{ClientIp, PasswordHashing, bearer_token},
+    },
 };
 
 pub fn scope(path: &str) -> Scope {
-    web::scope(path).service(signin).service(signup)
+    web::scope(path)
+        .service(signin)
+        .service(signin_mfa)
+        .service(begin_passkey_signin)
+        .service(passkey_signin)
+        .service(send_magic_link)
+        .service(magic_link_signin)
+        .service(magic_link_callback)
+        .service(signup)
+        .service(signout)
+        .service(verify_email)
+        .service(resend_email_verification)
+}
+
+fn email_verification_audience(envvar: &EnvVar) -> String {
+    format!("{}:email-verification", envvar.app_name)
+}
+
+fn mfa_audience(envvar: &EnvVar) -> String {
+    format!("{}:mfa", envvar.app_name)
+}
+
+fn magic_link_audience(envvar: &EnvVar) -> String {
+    format!("{}:magic-link", envvar.app_name)
+}
+
+pub(super) fn send_in_background<F>(job: F)
+where
+    F: FnOnce() + Send + 'static,
+{
+    actix_web::rt::spawn(async move {
+        if let Err(err) = web::block(job).await {
+            log::error!("failed to run mail delivery: {}", err);
+        }
+    });
+}
+
+pub(super) fn send_email_verification(
+    email: EmailAddress,
+    user_storage: &UserStorage,
+    envvar: &EnvVar,
+    token_keys: &TokenKeys,
+    mailer: &Mailer,
+) {
+    run_email_verification(user_storage, envvar, token_keys, mailer, |use_case| {
+        use_case.execute(email)
+    });
+}
+
+pub(super) fn send_pending_email_verification(
+    id: UserId,
+    user_storage: &UserStorage,
+    envvar: &EnvVar,
+    token_keys: &TokenKeys,
+    mailer: &Mailer,
+) {
+    run_email_verification(user_storage, envvar, token_keys, mailer, |use_case| {
+        use_case.execute_for_pending_email(&id)
+    });
+}
+
+fn run_email_verification<F>(
+    user_storage: &UserStorage,
+    envvar: &EnvVar,
+    token_keys: &TokenKeys,
+    mailer: &Mailer,
+    send: F,
+) where
+    F: FnOnce(&SendEmailVerificationUseCase) -> Result<(), error::MailDeliveryFailed>,
+{
+    let user_repository = user_storage.user_repository();
+    let now = get_systime();
+    let verification_token_keys = token_keys.email_verification_token.read().unwrap();
+    let verification_token_issuer = JWTIssuer::new(
+        verification_token_keys.current(),
+        InfraClaims {
+            iss: envvar.app_name.clone(),
+            aud: email_verification_audience(envvar),
+            iat: now,
+            exp: now + envvar.email_verification_token_valid_seconds,
+        },
+    );
+    let send_email_verification = SendEmailVerificationUseCase::new(
+        &verification_token_issuer,
+        mailer.mail_sender(),
+        &*user_repository,
+        &envvar.email_verification_url,
+        generate_id,
+    );
+
+    if let Err(err) = send(&send_email_verification) {
+        log::error!("failed to send email verification: {}", err);
+    }
 }
 
 #[derive(Deserialize)]
@@ -23,26 +134,58 @@ struct SignUpRequestBody {
 #[post("/signup")]
 async fn signup(
     body: web::Json<SignUpRequestBody>,
-    user_inmemory_table: web::Data<GenericTableManager<User>>,
-) -> impl Responder {
-    let password_hasher = BcryptHasher::new(12);
-    let mut user_repository = InMemoryUserRepository::new(user_inmemory_table.get_table());
-    let mut sign_up = SignUpUseCase::new(&password_hasher, &mut user_repository, get_systime);
-
-    let email = match EmailAddress::new(&body.email) {
-        Ok(email) => email,
-        Err(_) => return HttpResponse::UnprocessableEntity(),
-    };
-    let result = sign_up.execute(CreateUserDTO {
-        email_address: email,
-        username: body.username.clone(),
-        password: body.password.clone(),
-    });
+    user_storage: web::Data<UserStorage>,
+    envvar: web::Data<EnvVar>,
+    token_keys: web::Data<TokenKeys>,
+    mailer: web::Data<Mailer>,
+    password_hashing: PasswordHashing,
+) -> Result<HttpResponse, ApiError> {
+    let body = body.into_inner();
+    let email = EmailAddress::new(&body.email).map_err(ApiError::InvalidEmail)?;
+
+    let result = password_hashing
+        .run({
+            let email = email.clone();
+            let user_storage = user_storage.clone();
+            let envvar = envvar.clone();
+            move |password_hasher| {
+                let password_policy = envvar.password_policy();
+                let breached_password_checker = PwnedPasswordsCorpus::new(
+                    envvar.pwned_passwords_dir.as_ref().map(PathBuf::from),
+                );
+                let mut user_repository = user_storage.user_repository();
+                let mut sign_up = SignUpUseCase::new(
+                    password_hasher,
+                    &mut *user_repository,
+                    PasswordRules::new(&password_policy, &breached_password_checker),
+                    get_systime,
+                    generate_id,
+                );
+
+                sign_up.execute(CreateUserDTO {
+                    email_address: email,
+                    username: body.username,
+                    password: body.password,
+                })
+            }
+        })
+        .await
+        .map_err(|_| ApiError::ServiceBusy)?;
 
     match result {
-        Ok(_) => HttpResponse::Ok(),
-        Err(_) => HttpResponse::Conflict(),
+        Ok(_) => {}
+        Err(SignUpFailReason::InvalidPassword(err)) => return Err(ApiError::InvalidPassword(err)),
+        Err(SignUpFailReason::EmailConflict(_)) => return Err(ApiError::EmailTaken),
+    }
+    if let Err(err) = web::block(move || {
+        send_email_verification(email, &user_storage, &envvar, &token_keys, &mailer)
+    })
+    .await
+    {
+        log::error!("failed to send email verification: {}", err);
     }
+
+    Ok(HttpResponse::Ok().finish())
 }
 
 #[derive(Deserialize)]
@@ -55,58 +198,621 @@ struct SignInRequestBody {
 struct SignInResponse {
     pub access_token: String,
     pub refresh_token: String,
+    pub id: String,
     pub username: String,
     pub email: String,
 }
 
+impl From<SignInResult> for SignInResponse {
+    fn from(result: SignInResult) -> Self {
+        SignInResponse {
+            access_token: result.access_token,
+            refresh_token: result.refresh_token,
+            id: result.id,
+            username: result.username,
+            email: result.email,
+        }
+    }
+}
+
 #[post("/signin")]
 async fn signin(
+    client_ip: ClientIp,
     body: web::Json<SignInRequestBody>,
-    user_inmemory_table: web::Data<GenericTableManager<User>>,
+    user_storage: web::Data<UserStorage>,
+    session_storage: web::Data<SessionStorage>,
+    envvar: web::Data<EnvVar>,
+    token_keys: web::Data<TokenKeys>,
+    password_hashing: PasswordHashing,
+) -> Result<HttpResponse, ApiError> {
+    let body = body.into_inner();
+    let email = EmailAddress::new(&body.email).map_err(ApiError::InvalidEmail)?;
+
+    let result = password_hashing
+        .run(move |password_hasher| {
+            let mut user_repository = user_storage.user_repository();
+            let totp_credential_repository = user_storage.totp_credential_repository();
+            let mut login_attempt_repository = session_storage.login_attempt_repository();
+            let login_throttle_policy = envvar.login_throttle_policy();
+            let mut refresh_token_family_repository =
+                session_storage.refresh_token_family_repository();
+            let now = get_systime();
+            let access_token_keys = token_keys.access_token.read().unwrap();
+            let refresh_token_keys = token_keys.refresh_token.read().unwrap();
+            let mfa_token_keys = token_keys.mfa_token.read().unwrap();
+            let access_token_issuer = JWTIssuer::new(
+                access_token_keys.current(),
+                InfraClaims {
+                    iss: envvar.app_name.clone(),
+                    aud: envvar.app_name.clone(),
+                    iat: now,
+                    exp: now + envvar.access_token_valid_seconds,
+                },
+            );
+            let refresh_token_issuer = JWTIssuer::new(
+                refresh_token_keys.current(),
+                InfraClaims {
+                    iss: envvar.app_name.clone(),
+                    aud: envvar.app_name.clone(),
+                    iat: now,
+                    exp: now + envvar.refresh_token_valid_seconds,
+                },
+            );
+            let mfa_token_issuer = JWTIssuer::new(
+                mfa_token_keys.current(),
+                InfraClaims {
+                    iss: envvar.app_name.clone(),
+                    aud: mfa_audience(&envvar),
+                    iat: now,
+                    exp: now + envvar.mfa_token_valid_seconds,
+                },
+            );
+            let sign_in = SignInUseCase::new(
+                PasswordAuthenticator::new(
+                    password_hasher,
+                    &Argon2Validator {},
+                    &mut *user_repository,
+                ),
+                SessionIssuer::new(
+                    &access_token_issuer,
+                    &refresh_token_issuer,
+                    &mut refresh_token_family_repository,
+                    generate_id,
+                ),
+                &mfa_token_issuer,
+                LoginThrottle::new(&mut login_attempt_repository, &login_throttle_policy),
+                &*totp_credential_repository,
+                envvar.require_verified_email,
+                get_systime,
+            );
+
+            sign_in.execute(email, &body.password, &client_ip.ip)
+        })
+        .await
+        .map_err(|_| ApiError::ServiceBusy)?;
+
+    match result {
+        Ok(res) => Ok(HttpResponse::Ok()
+            .content_type(ContentType::json())
+            .json(SignInResponse::from(res))),
+        Err(FailReason::UserNotExist | FailReason::InvalidPassowrd) => {
+            Err(ApiError::InvalidCredentials)
+        }
+        Err(FailReason::AccountLocked { retry_after }) => {
+            Err(ApiError::TooManyAttempts(retry_after))
+        }
+        Err(FailReason::EmailNotVerified) => Err(ApiError::EmailNotVerified),
+        Err(FailReason::MfaRequired { mfa_token }) => Err(ApiError::MfaRequired(mfa_token)),
+    }
+}
+
+#[derive(Deserialize)]
+struct SignInMfaRequestBody {
+    mfa_token: String,
+    code: String,
+}
+
+#[post("/signin/mfa")]
+async fn signin_mfa(
+    body: web::Json<SignInMfaRequestBody>,
+    user_storage: web::Data<UserStorage>,
+    session_storage: web::Data<SessionStorage>,
     envvar: web::Data<EnvVar>,
-) -> HttpResponse {
-    let user_repository = InMemoryUserRepository::new(user_inmemory_table.get_table());
+    token_keys: web::Data<TokenKeys>,
+    blocking_pool: web::Data<BlockingPool>,
+) -> Result<HttpResponse, ApiError> {
+    let body = body.into_inner();
+
+    let result = blocking_pool
+        .run(move || {
+            let user_repository = user_storage.user_repository();
+            let mut totp_credential_repository = user_storage.totp_credential_repository();
+            let mut refresh_token_family_repository =
+                session_storage.refresh_token_family_repository();
+            let mut revoked_token_repository = session_storage.revoked_token_repository();
+            let verifier_revoked_token_repository = session_storage.revoked_token_repository();
+            let mut login_attempt_repository = session_storage.login_attempt_repository();
+            let login_throttle_policy = envvar.login_throttle_policy();
+            let now = get_systime();
+            let access_token_keys = token_keys.access_token.read().unwrap();
+            let refresh_token_keys = token_keys.refresh_token.read().unwrap();
+            let mfa_token_keys = token_keys.mfa_token.read().unwrap();
+            let mfa_token_verifier = JWTVerifier::new(
+                &mfa_token_keys,
+                ExpectedClaims {
+                    iss: envvar.app_name.clone(),
+                    aud: mfa_audience(&envvar),
+                    now,
+                },
+                &verifier_revoked_token_repository,
+            );
+            let access_token_issuer = JWTIssuer::new(
+                access_token_keys.current(),
+                InfraClaims {
+                    iss: envvar.app_name.clone(),
+                    aud: envvar.app_name.clone(),
+                    iat: now,
+                    exp: now + envvar.access_token_valid_seconds,
+                },
+            );
+            let refresh_token_issuer = JWTIssuer::new(
+                refresh_token_keys.current(),
+                InfraClaims {
+                    iss: envvar.app_name.clone(),
+                    aud: envvar.app_name.clone(),
+                    iat: now,
+                    exp: now + envvar.refresh_token_valid_seconds,
+                },
+            );
+            let totp_authenticator = Totp::new(&envvar.app_name);
+            let sign_in_mfa = SignInMfaUseCase::new(
+                &mfa_token_verifier,
+                SecondFactor::new(
+                    &totp_authenticator,
+                    &mut *totp_credential_repository,
+                    hash_secret,
+                ),
+                SessionIssuer::new(
+                    &access_token_issuer,
+                    &refresh_token_issuer,
+                    &mut refresh_token_family_repository,
+                    generate_id,
+                ),
+                &*user_repository,
+                &mut revoked_token_repository,
+                LoginThrottle::new(&mut login_attempt_repository, &login_throttle_policy),
+                get_systime,
+            );
+
+            sign_in_mfa.execute(&body.mfa_token, &body.code)
+        })
+        .await
+        .map_err(|_| ApiError::ServiceBusy)?;
+
+    match result {
+        Ok(res) => Ok(HttpResponse::Ok()
+            .content_type(ContentType::json())
+            .json(SignInResponse::from(res))),
+        Err(SignInMfaFailReason::InvalidToken) => Err(ApiError::InvalidToken),
+        Err(SignInMfaFailReason::InvalidCode) => Err(ApiError::InvalidMfaCode),
+        Err(SignInMfaFailReason::AccountLocked { retry_after }) => {
+            Err(ApiError::TooManyAttempts(retry_after))
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PasskeySignInOptionsResponse {
+    pub challenge: String,
+    pub rp_id: String,
+    pub timeout: u64,
+    pub user_verification: &'static str,
+}
+
+#[post("/signin/passkey/options")]
+async fn begin_passkey_signin(
+    session_storage: web::Data<SessionStorage>,
+    envvar: web::Data<EnvVar>,
+) -> Result<HttpResponse, ApiError> {
+    let mut passkey_challenge_repository = session_storage.passkey_challenge_repository();
+    let begin_passkey_signin = BeginPasskeySignInUseCase::new(
+        &mut passkey_challenge_repository,
+        envvar.passkey_challenge_valid_seconds,
+        generate_secret,
+        get_systime,
+    );
+
+    Ok(HttpResponse::Ok()
+        .content_type(ContentType::json())
+        .json(PasskeySignInOptionsResponse {
+            challenge: begin_passkey_signin.execute(),
+            rp_id: envvar.webauthn_rp_id.clone(),
+            timeout: envvar.passkey_challenge_valid_seconds * 1000,
+            user_verification: "required",
+        }))
+}
+
+#[derive(Deserialize)]
+struct PasskeySignInRequestBody {
+    challenge: String,
+    credential_id: String,
+    client_data_json: String,
+    authenticator_data: String,
+    signature: String,
+}
+
+#[post("/signin/passkey")]
+async fn passkey_signin(
+    body: web::Json<PasskeySignInRequestBody>,
+    user_storage: web::Data<UserStorage>,
+    session_storage: web::Data<SessionStorage>,
+    envvar: web::Data<EnvVar>,
+    token_keys: web::Data<TokenKeys>,
+    blocking_pool: web::Data<BlockingPool>,
+) -> Result<HttpResponse, ApiError> {
+    let body = body.into_inner();
+    let decode = |field: &str| {
+        URL_SAFE_NO_PAD
+            .decode(field)
+            .map_err(|_| ApiError::InvalidRequest("Passkey fields must be base64url".to_string()))
+    };
+    let client_data_json = decode(&body.client_data_json)?;
+    let authenticator_data = decode(&body.authenticator_data)?;
+    let signature = decode(&body.signature)?;
+
+    let result = blocking_pool
+        .run(move || {
+            let user_repository = user_storage.user_repository();
+            let mut passkey_credential_repository = user_storage.passkey_credential_repository();
+            let mut passkey_challenge_repository = session_storage.passkey_challenge_repository();
+            let mut refresh_token_family_repository =
+                session_storage.refresh_token_family_repository();
+            let now = get_systime();
+            let access_token_keys = token_keys.access_token.read().unwrap();
+            let refresh_token_keys = token_keys.refresh_token.read().unwrap();
+            let access_token_issuer = JWTIssuer::new(
+                access_token_keys.current(),
+                InfraClaims {
+                    iss: envvar.app_name.clone(),
+                    aud: envvar.app_name.clone(),
+                    iat: now,
+                    exp: now + envvar.access_token_valid_seconds,
+                },
+            );
+            let refresh_token_issuer = JWTIssuer::new(
+                refresh_token_keys.current(),
+                InfraClaims {
+                    iss: envvar.app_name.clone(),
+                    aud: envvar.app_name.clone(),
+                    iat: now,
+                    exp: now + envvar.refresh_token_valid_seconds,
+                },
+            );
+            let passkey_verifier = WebAuthn::new(&envvar.webauthn_rp_id, &envvar.webauthn_origin);
+            let passkey_signin = PasskeySignInUseCase::new(
+                &passkey_verifier,
+                SessionIssuer::new(
+                    &access_token_issuer,
+                    &refresh_token_issuer,
+                    &mut refresh_token_family_repository,
+                    generate_id,
+                ),
+                &*user_repository,
+                &mut *passkey_credential_repository,
+                &mut passkey_challenge_repository,
+                envvar.require_verified_email,
+            );
+
+            passkey_signin.execute(
+                &body.challenge,
+                &body.credential_id,
+                &client_data_json,
+                &authenticator_data,
+                &signature,
+            )
+        })
+        .await
+        .map_err(|_| ApiError::ServiceBusy)?;
+
+    match result {
+        Ok(res) => Ok(HttpResponse::Ok()
+            .content_type(ContentType::json())
+            .json(SignInResponse::from(res))),
+        Err(
+            PasskeySignInFailReason::InvalidChallenge | PasskeySignInFailReason::InvalidCredential,
+        ) => Err(ApiError::InvalidPasskey),
+        Err(PasskeySignInFailReason::EmailNotVerified) => Err(ApiError::EmailNotVerified),
+    }
+}
+
+#[derive(Deserialize)]
+struct SendMagicLinkRequestBody {
+    email: String,
+}
+
+#[post("/signin/magic-link")]
+async fn send_magic_link(
+    body: web::Json<SendMagicLinkRequestBody>,
+    user_storage: web::Data<UserStorage>,
+    envvar: web::Data<EnvVar>,
+    token_keys: web::Data<TokenKeys>,
+    mailer: web::Data<Mailer>,
+) -> Result<HttpResponse, ApiError> {
+    let email = EmailAddress::new(&body.email).map_err(ApiError::InvalidEmail)?;
+
+    send_in_background(move || {
+        let user_repository = user_storage.user_repository();
+        let now = get_systime();
+        let magic_link_token_keys = token_keys.magic_link_token.read().unwrap();
+        let magic_link_token_issuer = JWTIssuer::new(
+            magic_link_token_keys.current(),
+            InfraClaims {
+                iss: envvar.app_name.clone(),
+                aud: magic_link_audience(&envvar),
+                iat: now,
+                exp: now + envvar.magic_link_token_valid_seconds,
+            },
+        );
+        let send_magic_link = SendMagicLinkUseCase::new(
+            &magic_link_token_issuer,
+            mailer.mail_sender(),
+            &*user_repository,
+            &envvar.magic_link_url,
+            generate_id,
+        );
+
+        if let Err(err) = send_magic_link.execute(email) {
+            log::error!("failed to send magic link: {}", err);
+        }
+    });
+
+    Ok(HttpResponse::Accepted().finish())
+}
+
+#[derive(Deserialize)]
+struct MagicLinkSignInRequestBody {
+    token: String,
+}
+
+#[post("/signin/magic-link/callback")]
+async fn magic_link_signin(
+    body: web::Json<MagicLinkSignInRequestBody>,
+    user_storage: web::Data<UserStorage>,
+    session_storage: web::Data<SessionStorage>,
+    envvar: web::Data<EnvVar>,
+    token_keys: web::Data<TokenKeys>,
+    blocking_pool: web::Data<BlockingPool>,
+) -> Result<HttpResponse, ApiError> {
+    sign_in_with_magic_link(
+        body.into_inner(),
+        user_storage,
+        session_storage,
+        envvar,
+        token_keys,
+        blocking_pool,
+    )
+    .await
+}
+
+#[get("/signin/magic-link/callback")]
+async fn magic_link_callback(
+    query: web::Query<MagicLinkSignInRequestBody>,
+    user_storage: web::Data<UserStorage>,
+    session_storage: web::Data<SessionStorage>,
+    envvar: web::Data<EnvVar>,
+    token_keys: web::Data<TokenKeys>,
+    blocking_pool: web::Data<BlockingPool>,
+) -> Result<HttpResponse, ApiError> {
+    sign_in_with_magic_link(
+        query.into_inner(),
+        user_storage,
+        session_storage,
+        envvar,
+        token_keys,
+        blocking_pool,
+    )
+    .await
+}
+
+async fn sign_in_with_magic_link(
+    body: MagicLinkSignInRequestBody,
+    user_storage: web::Data<UserStorage>,
+    session_storage: web::Data<SessionStorage>,
+    envvar: web::Data<EnvVar>,
+    token_keys: web::Data<TokenKeys>,
+    blocking_pool: web::Data<BlockingPool>,
+) -> Result<HttpResponse, ApiError> {
+    let result = blocking_pool
+        .run(move || {
+            let mut user_repository = user_storage.user_repository();
+            let totp_credential_repository = user_storage.totp_credential_repository();
+            let mut refresh_token_family_repository =
+                session_storage.refresh_token_family_repository();
+            let mut revoked_token_repository = session_storage.revoked_token_repository();
+            let verifier_revoked_token_repository = session_storage.revoked_token_repository();
+            let now = get_systime();
+            let magic_link_token_keys = token_keys.magic_link_token.read().unwrap();
+            let access_token_keys = token_keys.access_token.read().unwrap();
+            let refresh_token_keys = token_keys.refresh_token.read().unwrap();
+            let mfa_token_keys = token_keys.mfa_token.read().unwrap();
+            let magic_link_token_verifier = JWTVerifier::new(
+                &magic_link_token_keys,
+                ExpectedClaims {
+                    iss: envvar.app_name.clone(),
+                    aud: magic_link_audience(&envvar),
+                    now,
+                },
+                &verifier_revoked_token_repository,
+            );
+            let access_token_issuer = JWTIssuer::new(
+                access_token_keys.current(),
+                InfraClaims {
+                    iss: envvar.app_name.clone(),
+                    aud: envvar.app_name.clone(),
+                    iat: now,
+                    exp: now + envvar.access_token_valid_seconds,
+                },
+            );
+            let refresh_token_issuer = JWTIssuer::new(
+                refresh_token_keys.current(),
+                InfraClaims {
+                    iss: envvar.app_name.clone(),
+                    aud: envvar.app_name.clone(),
+                    iat: now,
+                    exp: now + envvar.refresh_token_valid_seconds,
+                },
+            );
+            let mfa_token_issuer = JWTIssuer::new(
+                mfa_token_keys.current(),
+                InfraClaims {
+                    iss: envvar.app_name.clone(),
+                    aud: mfa_audience(&envvar),
+                    iat: now,
+                    exp: now + envvar.mfa_token_valid_seconds,
+                },
+            );
+            let magic_link_sign_in = MagicLinkSignInUseCase::new(
+                &magic_link_token_verifier,
+                SessionIssuer::new(
+                    &access_token_issuer,
+                    &refresh_token_issuer,
+                    &mut refresh_token_family_repository,
+                    generate_id,
+                ),
+                &mfa_token_issuer,
+                &mut *user_repository,
+                &mut revoked_token_repository,
+                &*totp_credential_repository,
+                get_systime,
+            );
+
+            magic_link_sign_in.execute(&body.token)
+        })
+        .await
+        .map_err(|_| ApiError::ServiceBusy)?;
+
+    match result {
+        Ok(res) => Ok(HttpResponse::Ok()
+            .content_type(ContentType::json())
+            .json(SignInResponse::from(res))),
+        Err(MagicLinkSignInFailReason::InvalidToken) => Err(ApiError::InvalidToken),
+        Err(MagicLinkSignInFailReason::MfaRequired { mfa_token }) => {
+            Err(ApiError::MfaRequired(mfa_token))
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct SignOutRequestBody {
+    refresh_token: Option<String>,
+}
+
+#[post("/signout")]
+async fn signout(
+    request: HttpRequest,
+    body: web::Json<SignOutRequestBody>,
+    session_storage: web::Data<SessionStorage>,
+    envvar: web::Data<EnvVar>,
+    token_keys: web::Data<TokenKeys>,
+) -> Result<HttpResponse, ApiError> {
+    let access_token = bearer_token(&request).ok_or(ApiError::InvalidToken)?;
+    let mut refresh_token_family_repository = session_storage.refresh_token_family_repository();
+    let mut revoked_token_repository = session_storage.revoked_token_repository();
+    let verifier_revoked_token_repository = session_storage.revoked_token_repository();
     let now = get_systime();
-    let access_token_issuer = JWTIssuer::new(
-        &envvar.access_token_secret,
-        InfraClaims {
+    let access_token_keys = token_keys.access_token.read().unwrap();
+    let refresh_token_keys = token_keys.refresh_token.read().unwrap();
+    let access_token_verifier = JWTVerifier::new(
+        &access_token_keys,
+        ExpectedClaims {
             iss: envvar.app_name.clone(),
             aud: envvar.app_name.clone(),
-            iat: now,
-            exp: now + envvar.access_token_valid_seconds,
+            now,
         },
+        &verifier_revoked_token_repository,
     );
-    let refresh_token_issuer = JWTIssuer::new(
-        &envvar.refresh_token_secret,
-        InfraClaims {
+    let refresh_token_verifier = JWTVerifier::new(
+        &refresh_token_keys,
+        ExpectedClaims {
             iss: envvar.app_name.clone(),
             aud: envvar.app_name.clone(),
-            iat: now,
-            exp: now + envvar.refresh_token_valid_seconds,
+            now,
         },
+        &verifier_revoked_token_repository,
     );
-    let sign_in = SignInUseCase::new(
-        &BcryptValidator {},
-        &access_token_issuer,
-        &refresh_token_issuer,
-        &user_repository,
+    let sign_out = SignOutUseCase::new(
+        &access_token_verifier,
+        &refresh_token_verifier,
+        &mut revoked_token_repository,
+        &mut refresh_token_family_repository,
     );
-    let email = match EmailAddress::new(&body.email) {
-        Ok(email) => email,
-        Err(_) => return HttpResponse::UnprocessableEntity().finish(),
-    };
 
-    let result = sign_in.execute(email, &body.password);
+    sign_out
+        .execute(access_token, body.refresh_token.as_deref())
+        .map_err(|_| ApiError::InvalidToken)?;
 
-    match result {
-        Ok(res) => HttpResponse::Ok()
-            .content_type(ContentType::json())
-            .json(SignInResponse {
-                access_token: res.access_token,
-                refresh_token: res.refresh_token,
-                username: res.username,
-                email: res.email,
-            }),
-        Err(_) => HttpResponse::Unauthorized().finish(),
-    }
+    Ok(HttpResponse::NoContent().finish())
+}
+
+#[derive(Deserialize)]
+struct VerifyEmailRequestBody {
+    token: String,
+}
+
+#[post("/verify-email")]
+async fn verify_email(
+    body: web::Json<VerifyEmailRequestBody>,
+    user_storage: web::Data<UserStorage>,
+    session_storage: web::Data<SessionStorage>,
+    envvar: web::Data<EnvVar>,
+    token_keys: web::Data<TokenKeys>,
+) -> Result<HttpResponse, ApiError> {
+    let mut user_repository = user_storage.user_repository();
+    let mut revoked_token_repository = session_storage.revoked_token_repository();
+    let verifier_revoked_token_repository = session_storage.revoked_token_repository();
+    let verification_token_keys = token_keys.email_verification_token.read().unwrap();
+    let verification_token_verifier = JWTVerifier::new(
+        &verification_token_keys,
+        ExpectedClaims {
+            iss: envvar.app_name.clone(),
+            aud: email_verification_audience(&envvar),
+            now: get_systime(),
+        },
+        &verifier_revoked_token_repository,
+    );
+    let verify_email = VerifyEmailUseCase::new(
+        &verification_token_verifier,
+        &mut *user_repository,
+        &mut revoked_token_repository,
+        get_systime,
+    );
+
+    verify_email
+        .execute(&body.token)
+        .map_err(|_| ApiError::InvalidToken)?;
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+#[derive(Deserialize)]
+struct ResendEmailVerificationRequestBody {
+    email: String,
+}
+
+#[post("/verify-email/resend")]
+async fn resend_email_verification(
+    body: web::Json<ResendEmailVerificationRequestBody>,
+    user_storage: web::Data<UserStorage>,
+    envvar: web::Data<EnvVar>,
+    token_keys: web::Data<TokenKeys>,
+    mailer: web::Data<Mailer>,
+) -> Result<HttpResponse, ApiError> {
+    let email = EmailAddress::new(&body.email).map_err(ApiError::InvalidEmail)?;
+
+    send_in_background(move || {
+        send_email_verification(email, &user_storage, &envvar, &token_keys, &mailer)
+    });
+
+    Ok(HttpResponse::Accepted().finish())
 }
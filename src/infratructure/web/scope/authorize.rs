@@ -0,0 +1,362 @@
+use actix_web::{
+    HttpResponse, Scope, get,
+    http::{
+        StatusCode,
+        header::{CACHE_CONTROL, CONTENT_SECURITY_POLICY, ContentType, LOCATION, RETRY_AFTER},
+    },
+    post, web,
+};
+use serde::Deserialize;
+
+use crate::application::use_case::{
+    AuthorizationCodeIssuer, AuthorizationRequest, AuthorizationRequestError, AuthorizeFailReason,
+    AuthorizeUseCase, BeginAuthorizationUseCase, LoginThrottle, PasswordAuthenticator,
+    SecondFactor,
+};
+use crate::domain::value_object::EmailAddress;
+use crate::infratructure::{
+    auth::{Argon2Validator, Totp},
+    repository::{SessionStorage, UserStorage},
+    system::{EnvVar, generate_secret, get_systime, hash_secret},
+    web::{
+        error::ApiError,
+        extractor::{ClientIp, PasswordHashing},
+    },
+};
+
+pub fn scope(path: &str) -> Scope {
+    web::scope(path)
+        .service(authorization_page)
+        .service(authorize)
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct AuthorizationParams {
+    response_type: String,
+    client_id: String,
+    redirect_uri: String,
+    code_challenge: String,
+    code_challenge_method: String,
+    state: String,
+}
+
+impl AuthorizationParams {
+    fn to_request(&self) -> AuthorizationRequest {
+        AuthorizationRequest {
+            response_type: self.response_type.clone(),
+            client_id: self.client_id.clone(),
+            redirect_uri: self.redirect_uri.clone(),
+            code_challenge: self.code_challenge.clone(),
+            code_challenge_method: self.code_challenge_method.clone(),
+        }
+    }
+}
+
+#[get("")]
+async fn authorization_page(
+    query: web::Query<AuthorizationParams>,
+    user_storage: web::Data<UserStorage>,
+) -> HttpResponse {
+    let params = query.into_inner();
+    let oauth_client_repository = user_storage.oauth_client_repository();
+    let begin_authorization = BeginAuthorizationUseCase::new(&*oauth_client_repository);
+
+    match begin_authorization.execute(&params.to_request()) {
+        Ok(client) => render_page(StatusCode::OK, &client.name, &params, "", None),
+        Err(err) => reject_request(&params, err),
+    }
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct AuthorizeForm {
+    #[serde(flatten)]
+    params: AuthorizationParams,
+    email: String,
+    password: String,
+    mfa_code: String,
+    decision: String,
+}
+
+#[post("")]
+async fn authorize(
+    client_ip: ClientIp,
+    form: web::Form<AuthorizeForm>,
+    user_storage: web::Data<UserStorage>,
+    session_storage: web::Data<SessionStorage>,
+    envvar: web::Data<EnvVar>,
+    password_hashing: PasswordHashing,
+) -> Result<HttpResponse, ApiError> {
+    let form = form.into_inner();
+    let params = form.params;
+    let client = {
+        let oauth_client_repository = user_storage.oauth_client_repository();
+        let begin_authorization = BeginAuthorizationUseCase::new(&*oauth_client_repository);
+        match begin_authorization.execute(&params.to_request()) {
+            Ok(client) => client,
+            Err(err) => return Ok(reject_request(&params, err)),
+        }
+    };
+    if form.decision != "allow" {
+        return Ok(redirect_to_client(&params, &[("error", "access_denied")]));
+    }
+    let Ok(email) = EmailAddress::new(&form.email) else {
+        return Ok(render_page(
+            StatusCode::OK,
+            &client.name,
+            &params,
+            &form.email,
+            Some("Email or password is incorrect"),
+        ));
+    };
+    let authorization_request = params.to_request();
+    let password = form.password;
+    let mfa_code = form.mfa_code;
+
+    let result = password_hashing
+        .run(move |password_hasher| {
+            let oauth_client_repository = user_storage.oauth_client_repository();
+            let mut user_repository = user_storage.user_repository();
+            let mut totp_credential_repository = user_storage.totp_credential_repository();
+            let mut login_attempt_repository = session_storage.login_attempt_repository();
+            let mut authorization_code_repository = session_storage.authorization_code_repository();
+            let login_throttle_policy = envvar.login_throttle_policy();
+            let totp_authenticator = Totp::new(&envvar.app_name);
+            let authorization = AuthorizeUseCase::new(
+                PasswordAuthenticator::new(
+                    password_hasher,
+                    &Argon2Validator {},
+                    &mut *user_repository,
+                ),
+                SecondFactor::new(
+                    &totp_authenticator,
+                    &mut *totp_credential_repository,
+                    hash_secret,
+                ),
+                &*oauth_client_repository,
+                LoginThrottle::new(&mut login_attempt_repository, &login_throttle_policy),
+                AuthorizationCodeIssuer::new(
+                    &mut authorization_code_repository,
+                    envvar.authorization_code_valid_seconds,
+                    generate_secret,
+                    hash_secret,
+                ),
+                envvar.require_verified_email,
+                get_systime,
+            );
+
+            authorization.execute(
+                &authorization_request,
+                email,
+                &password,
+                Some(mfa_code.trim()).filter(|code| !code.is_empty()),
+                &client_ip.ip,
+            )
+        })
+        .await
+        .map_err(|_| ApiError::ServiceBusy)?;
+
+    let message = match result {
+        Ok(code) => return Ok(redirect_to_client(&params, &[("code", &code)])),
+        Err(AuthorizeFailReason::InvalidRequest(err)) => return Ok(reject_request(&params, err)),
+        Err(AuthorizeFailReason::AccountLocked { retry_after }) => {
+            let mut response = render_page(
+                StatusCode::TOO_MANY_REQUESTS,
+                &client.name,
+                &params,
+                &form.email,
+                Some("Too many failed attempts, please retry later"),
+            );
+            response
+                .headers_mut()
+                .insert(RETRY_AFTER, retry_after.into());
+            return Ok(response);
+        }
+        Err(AuthorizeFailReason::InvalidCredentials) => "Email or password is incorrect",
+        Err(AuthorizeFailReason::EmailNotVerified) => "Verify your email address before signing in",
+        Err(AuthorizeFailReason::MfaRequired) => {
+            "Enter the code from your authenticator app or a recovery code"
+        }
+        Err(AuthorizeFailReason::InvalidMfaCode) => "Authentication code is incorrect",
+    };
+    Ok(render_page(
+        StatusCode::OK,
+        &client.name,
+        &params,
+        &form.email,
+        Some(message),
+    ))
+}
+
+fn reject_request(params: &AuthorizationParams, err: AuthorizationRequestError) -> HttpResponse {
+    match err {
+        AuthorizationRequestError::InvalidClient
+        | AuthorizationRequestError::InvalidRedirectUri => {
+            html_response(StatusCode::BAD_REQUEST).body(ERROR_PAGE)
+        }
+        AuthorizationRequestError::UnsupportedResponseType => {
+            redirect_to_client(params, &[("error", "unsupported_response_type")])
+        }
+        AuthorizationRequestError::InvalidCodeChallenge => redirect_to_client(
+            params,
+            &[
+                ("error", "invalid_request"),
+                (
+                    "error_description",
+                    "code_challenge with code_challenge_method S256 is required",
+                ),
+            ],
+        ),
+    }
+}
+
+fn redirect_to_client(params: &AuthorizationParams, query: &[(&str, &str)]) -> HttpResponse {
+    let mut query = query.to_vec();
+    if !params.state.is_empty() {
+        query.push(("state", &params.state));
+    }
+    let separator = if params.redirect_uri.contains('?') {
+        '&'
+    } else {
+        '?'
+    };
+    let location = format!(
+        "{}{}{}",
+        params.redirect_uri,
+        separator,
+        serde_urlencoded::to_string(query).unwrap()
+    );
+    HttpResponse::Found()
+        .insert_header((LOCATION, location))
+        .insert_header((CACHE_CONTROL, "no-store"))
+        .finish()
+}
+
+fn html_response(status: StatusCode) -> actix_web::HttpResponseBuilder {
+    let mut response = HttpResponse::build(status);
+    response
+        .content_type(ContentType::html())
+        .insert_header((CACHE_CONTROL, "no-store"))
+        .insert_header((
+            CONTENT_SECURITY_POLICY,
+            "default-src 'none'; frame-ancestors 'none'",
+        ));
+    response
+}
+
+fn render_page(
+    status: StatusCode,
+    client_name: &str,
+    params: &AuthorizationParams,
+    email: &str,
+    message: Option<&str>,
+) -> HttpResponse {
+    let message = message.map_or(String::new(), |message| {
+        format!("<p role=\"alert\">{}</p>", escape_html(message))
+    });
+    let hidden_fields: String = [
+        ("response_type", &params.response_type),
+        ("client_id", &params.client_id),
+        ("redirect_uri", &params.redirect_uri),
+        ("code_challenge", &params.code_challenge),
+        ("code_challenge_method", &params.code_challenge_method),
+        ("state", &params.state),
+    ]
+    .iter()
+    .map(|(name, value)| {
+        format!(
+            "<input type=\"hidden\" name=\"{}\" value=\"{}\">",
+            name,
+            escape_html(value)
+        )
+    })
+    .collect();
+    html_response(status).body(format!(
+        r#"<!DOCTYPE html>
+<html>
+<head><meta charset="utf-8"><title>Sign in</title></head>
+<body>
+<h1>Sign in to continue to {client_name}</h1>
+{message}
+<form method="post">
+{hidden_fields}
+<label>Email <input type="email" name="email" value="{email}" autocomplete="username" required></label>
+<label>Password <input type="password" name="password" autocomplete="current-password" required></label>
+<label>Authentication code <input type="text" name="mfa_code" autocomplete="one-time-code"></label>
+<p>{client_name} will be able to access your account.</p>
+<button type="submit" name="decision" value="allow">Allow</button>
+<button type="submit" name="decision" value="deny" formnovalidate>Deny</button>
+</form>
+</body>
+</html>
+"#,
+        client_name = escape_html(client_name),
+        message = message,
+        hidden_fields = hidden_fields,
+        email = escape_html(email),
+    ))
+}
+
+const ERROR_PAGE: &str = r#"<!DOCTYPE html>
+<html>
+<head><meta charset="utf-8"><title>Invalid request</title></head>
+<body>
+<h1>Invalid authorization request</h1>
+<p>The application or its redirect URI is not registered.</p>
+</body>
+</html>
+"#;
+
+fn escape_html(value: &str) -> String {
+    value
+        .chars()
+        .map(|c| match c {
+            '&' => "&amp;".to_string(),
+            '<' => "&lt;".to_string(),
+            '>' => "&gt;".to_string(),
+            '"' => "&quot;".to_string(),
+            '\'' => "&#x27;".to_string(),
+            c => c.to_string(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn escape_html_given_markup_should_escape_special_characters() {
+        assert_eq!(
+            escape_html(r#"<a href="x">Tom & 'Jerry'</a>"#),
+            "&lt;a href=&quot;x&quot;&gt;Tom &amp; &#x27;Jerry&#x27;&lt;/a&gt;"
+        );
+    }
+
+    #[test]
+    fn redirect_to_client_given_redirect_uri_with_query_should_append_params() {
+        let test_cases = vec![
+            (
+                "https://client.example/cb",
+                "https://client.example/cb?code=abc&state=xyz",
+            ),
+            (
+                "https://client.example/cb?tenant=1",
+                "https://client.example/cb?tenant=1&code=abc&state=xyz",
+            ),
+        ];
+        for (redirect_uri, expected) in test_cases {
+            let params = AuthorizationParams {
+                redirect_uri: redirect_uri.to_string(),
+                state: "xyz".to_string(),
+                ..Default::default()
+            };
+
+            let response = redirect_to_client(&params, &[("code", "abc")]);
+
+            assert_eq!(response.status(), StatusCode::FOUND);
+            assert_eq!(response.headers().get(LOCATION).unwrap(), expected);
+        }
+    }
+}
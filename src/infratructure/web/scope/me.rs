@@ -0,0 +1,403 @@
+use actix_web::{HttpResponse, Scope, delete, http::header::ContentType, post, web};
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+use super::auth::{send_in_background, send_pending_email_verification};
+use crate::application::use_case::{
+    BeginPasskeyRegistrationUseCase, ChangeEmailFailReason, ChangeEmailUseCase,
+    ChangePasswordFailReason, ChangePasswordUseCase, ConfirmTotpFailReason, ConfirmTotpUseCase,
+    DeleteAccountFailReason, DeleteAccountUseCase, EnrollTotpFailReason, EnrollTotpUseCase,
+    PasswordRules, RegisterPasskeyFailReason, RegisterPasskeyUseCase,
+};
+use crate::domain::value_object::EmailAddress;
+use crate::infratructure::{
+    auth::{Argon2Validator, PwnedPasswordsCorpus, TokenKeys, Totp, WebAuthn},
+    mail::Mailer,
+    repository::{SessionStorage, UserStorage},
+    system::{EnvVar, generate_recovery_code, generate_secret, get_systime, hash_secret},
+    web::{
+        error::ApiError,
+        extractor::{CurrentUser, PasswordHashing},
+    },
+};
+
+pub fn scope(path: &str) -> Scope {
+    web::scope(path)
+        .service(delete_account)
+        .service(change_password)
+        .service(change_email)
+        .service(enroll_totp)
+        .service(confirm_totp)
+        .service(begin_passkey_registration)
+        .service(register_passkey)
+}
+
+#[derive(Deserialize)]
+struct DeleteAccountRequestBody {
+    password: String,
+}
+
+#[delete("")]
+async fn delete_account(
+    current_user: CurrentUser,
+    body: web::Json<DeleteAccountRequestBody>,
+    user_storage: web::Data<UserStorage>,
+    session_storage: web::Data<SessionStorage>,
+    password_hashing: PasswordHashing,
+) -> Result<HttpResponse, ApiError> {
+    let body = body.into_inner();
+
+    let result = password_hashing
+        .run(move |_| {
+            let mut user_repository = user_storage.user_repository();
+            let mut refresh_token_family_repository =
+                session_storage.refresh_token_family_repository();
+            let delete_account = DeleteAccountUseCase::new(
+                &Argon2Validator {},
+                &mut *user_repository,
+                &mut refresh_token_family_repository,
+            );
+
+            delete_account.execute(&current_user.id, &body.password)
+        })
+        .await
+        .map_err(|_| ApiError::ServiceBusy)?;
+
+    match result {
+        Ok(_) => Ok(HttpResponse::NoContent().finish()),
+        Err(DeleteAccountFailReason::UserNotExist) => Err(ApiError::InvalidToken),
+        Err(DeleteAccountFailReason::IncorrectPassword) => Err(ApiError::InvalidCredentials),
+    }
+}
+
+#[derive(Deserialize)]
+struct ChangePasswordRequestBody {
+    current_password: String,
+    new_password: String,
+}
+
+#[post("/password")]
+async fn change_password(
+    current_user: CurrentUser,
+    body: web::Json<ChangePasswordRequestBody>,
+    user_storage: web::Data<UserStorage>,
+    envvar: web::Data<EnvVar>,
+    password_hashing: PasswordHashing,
+) -> Result<HttpResponse, ApiError> {
+    let body = body.into_inner();
+
+    let result = password_hashing
+        .run(move |password_hasher| {
+            let password_policy = envvar.password_policy();
+            let breached_password_checker =
+                PwnedPasswordsCorpus::new(envvar.pwned_passwords_dir.as_ref().map(PathBuf::from));
+            let mut user_repository = user_storage.user_repository();
+            let change_password = ChangePasswordUseCase::new(
+                password_hasher,
+                &Argon2Validator {},
+                PasswordRules::new(&password_policy, &breached_password_checker),
+                &mut *user_repository,
+                get_systime,
+            );
+
+            change_password.execute(&current_user.id, &body.current_password, &body.new_password)
+        })
+        .await
+        .map_err(|_| ApiError::ServiceBusy)?;
+
+    match result {
+        Ok(_) => Ok(HttpResponse::NoContent().finish()),
+        Err(ChangePasswordFailReason::UserNotExist) => Err(ApiError::InvalidToken),
+        Err(ChangePasswordFailReason::IncorrectPassword) => Err(ApiError::InvalidCredentials),
+        Err(ChangePasswordFailReason::InvalidPassword(err)) => Err(ApiError::InvalidPassword(err)),
+    }
+}
+
+#[derive(Deserialize)]
+struct ChangeEmailRequestBody {
+    current_password: String,
+    email: String,
+}
+
+#[post("/email")]
+async fn change_email(
+    current_user: CurrentUser,
+    body: web::Json<ChangeEmailRequestBody>,
+    user_storage: web::Data<UserStorage>,
+    envvar: web::Data<EnvVar>,
+    token_keys: web::Data<TokenKeys>,
+    mailer: web::Data<Mailer>,
+    password_hashing: PasswordHashing,
+) -> Result<HttpResponse, ApiError> {
+    let body = body.into_inner();
+    let email = EmailAddress::new(&body.email).map_err(ApiError::InvalidEmail)?;
+    let id = current_user.id.clone();
+
+    let result = password_hashing
+        .run({
+            let user_storage = user_storage.clone();
+            move |_| {
+                let mut user_repository = user_storage.user_repository();
+                let change_email = ChangeEmailUseCase::new(
+                    &Argon2Validator {},
+                    &mut *user_repository,
+                    get_systime,
+                );
+
+                change_email.execute(&current_user.id, &body.current_password, email)
+            }
+        })
+        .await
+        .map_err(|_| ApiError::ServiceBusy)?;
+
+    match result {
+        Ok(Some(_)) => {
+            send_in_background(move || {
+                send_pending_email_verification(id, &user_storage, &envvar, &token_keys, &mailer)
+            });
+            Ok(HttpResponse::Accepted().finish())
+        }
+        Ok(None) => Ok(HttpResponse::NoContent().finish()),
+        Err(ChangeEmailFailReason::UserNotExist) => Err(ApiError::InvalidToken),
+        Err(ChangeEmailFailReason::IncorrectPassword) => Err(ApiError::InvalidCredentials),
+        Err(ChangeEmailFailReason::EmailConflict) => Err(ApiError::EmailTaken),
+    }
+}
+
+#[derive(Serialize)]
+struct EnrollTotpResponse {
+    pub secret: String,
+    pub provisioning_uri: String,
+}
+
+#[post("/mfa/totp")]
+async fn enroll_totp(
+    current_user: CurrentUser,
+    user_storage: web::Data<UserStorage>,
+    envvar: web::Data<EnvVar>,
+) -> Result<HttpResponse, ApiError> {
+    let user_repository = user_storage.user_repository();
+    let mut totp_credential_repository = user_storage.totp_credential_repository();
+    let totp_authenticator = Totp::new(&envvar.app_name);
+    let enroll_totp = EnrollTotpUseCase::new(
+        &totp_authenticator,
+        &*user_repository,
+        &mut *totp_credential_repository,
+    );
+
+    match enroll_totp.execute(&current_user.id) {
+        Ok(enrollment) => {
+            Ok(HttpResponse::Ok()
+                .content_type(ContentType::json())
+                .json(EnrollTotpResponse {
+                    secret: enrollment.secret,
+                    provisioning_uri: enrollment.provisioning_uri,
+                }))
+        }
+        Err(EnrollTotpFailReason::UserNotExist) => Err(ApiError::InvalidToken),
+        Err(EnrollTotpFailReason::AlreadyEnabled) => Err(ApiError::MfaAlreadyEnabled),
+    }
+}
+
+#[derive(Deserialize)]
+struct ConfirmTotpRequestBody {
+    code: String,
+}
+
+#[derive(Serialize)]
+struct ConfirmTotpResponse {
+    pub recovery_codes: Vec<String>,
+}
+
+#[post("/mfa/totp/confirm")]
+async fn confirm_totp(
+    current_user: CurrentUser,
+    body: web::Json<ConfirmTotpRequestBody>,
+    user_storage: web::Data<UserStorage>,
+    envvar: web::Data<EnvVar>,
+) -> Result<HttpResponse, ApiError> {
+    let mut totp_credential_repository = user_storage.totp_credential_repository();
+    let totp_authenticator = Totp::new(&envvar.app_name);
+    let confirm_totp = ConfirmTotpUseCase::new(
+        &totp_authenticator,
+        &mut *totp_credential_repository,
+        generate_recovery_code,
+        hash_secret,
+        get_systime,
+    );
+
+    match confirm_totp.execute(&current_user.id, &body.code) {
+        Ok(recovery_codes) => Ok(HttpResponse::Ok()
+            .content_type(ContentType::json())
+            .json(ConfirmTotpResponse { recovery_codes })),
+        Err(ConfirmTotpFailReason::NotEnrolled) => Err(ApiError::MfaNotEnrolled),
+        Err(ConfirmTotpFailReason::AlreadyEnabled) => Err(ApiError::MfaAlreadyEnabled),
+        Err(ConfirmTotpFailReason::InvalidCode) => Err(ApiError::InvalidMfaCode),
+    }
+}
+
+#[derive(Serialize)]
+struct RelyingParty {
+    pub id: String,
+    pub name: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PasskeyUser {
+    pub id: String,
+    pub name: String,
+    pub display_name: String,
+}
+
+#[derive(Serialize)]
+struct PublicKeyCredentialParameters {
+    #[serde(rename = "type")]
+    pub credential_type: &'static str,
+    pub alg: i64,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct AuthenticatorSelection {
+    pub resident_key: &'static str,
+    pub user_verification: &'static str,
+}
+
+#[derive(Serialize)]
+struct PublicKeyCredentialDescriptor {
+    #[serde(rename = "type")]
+    pub credential_type: &'static str,
+    pub id: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PasskeyRegistrationOptionsResponse {
+    pub rp: RelyingParty,
+    pub user: PasskeyUser,
+    pub challenge: String,
+    pub pub_key_cred_params: Vec<PublicKeyCredentialParameters>,
+    pub timeout: u64,
+    pub attestation: &'static str,
+    pub authenticator_selection: AuthenticatorSelection,
+    pub exclude_credentials: Vec<PublicKeyCredentialDescriptor>,
+}
+
+#[post("/passkeys/registration/options")]
+async fn begin_passkey_registration(
+    current_user: CurrentUser,
+    user_storage: web::Data<UserStorage>,
+    session_storage: web::Data<SessionStorage>,
+    envvar: web::Data<EnvVar>,
+) -> Result<HttpResponse, ApiError> {
+    let user_repository = user_storage.user_repository();
+    let passkey_credential_repository = user_storage.passkey_credential_repository();
+    let mut passkey_challenge_repository = session_storage.passkey_challenge_repository();
+    let begin_passkey_registration = BeginPasskeyRegistrationUseCase::new(
+        &*user_repository,
+        &*passkey_credential_repository,
+        &mut passkey_challenge_repository,
+        envvar.passkey_challenge_valid_seconds,
+        generate_secret,
+        get_systime,
+    );
+
+    let options = begin_passkey_registration
+        .execute(&current_user.id)
+        .map_err(|_| ApiError::InvalidToken)?;
+
+    Ok(HttpResponse::Ok().content_type(ContentType::json()).json(
+        PasskeyRegistrationOptionsResponse {
+            rp: RelyingParty {
+                id: envvar.webauthn_rp_id.clone(),
+                name: envvar
+                    .webauthn_rp_name
+                    .clone()
+                    .unwrap_or(envvar.app_name.clone()),
+            },
+            user: PasskeyUser {
+                id: URL_SAFE_NO_PAD.encode(options.user_id),
+                name: options.email,
+                display_name: options.username,
+            },
+            challenge: options.challenge,
+            pub_key_cred_params: WebAuthn::supported_algorithms()
+                .into_iter()
+                .map(|alg| PublicKeyCredentialParameters {
+                    credential_type: "public-key",
+                    alg,
+                })
+                .collect(),
+            timeout: envvar.passkey_challenge_valid_seconds * 1000,
+            attestation: "none",
+            authenticator_selection: AuthenticatorSelection {
+                resident_key: "required",
+                user_verification: "required",
+            },
+            exclude_credentials: options
+                .exclude_credentials
+                .into_iter()
+                .map(|id| PublicKeyCredentialDescriptor {
+                    credential_type: "public-key",
+                    id,
+                })
+                .collect(),
+        },
+    ))
+}
+
+#[derive(Deserialize)]
+struct RegisterPasskeyRequestBody {
+    challenge: String,
+    client_data_json: String,
+    attestation_object: String,
+}
+
+#[derive(Serialize)]
+struct RegisterPasskeyResponse {
+    pub credential_id: String,
+}
+
+#[post("/passkeys/registration")]
+async fn register_passkey(
+    current_user: CurrentUser,
+    body: web::Json<RegisterPasskeyRequestBody>,
+    user_storage: web::Data<UserStorage>,
+    session_storage: web::Data<SessionStorage>,
+    envvar: web::Data<EnvVar>,
+) -> Result<HttpResponse, ApiError> {
+    let decode = |field: &str| {
+        URL_SAFE_NO_PAD
+            .decode(field)
+            .map_err(|_| ApiError::InvalidRequest("Passkey fields must be base64url".to_string()))
+    };
+    let client_data_json = decode(&body.client_data_json)?;
+    let attestation_object = decode(&body.attestation_object)?;
+    let mut passkey_credential_repository = user_storage.passkey_credential_repository();
+    let mut passkey_challenge_repository = session_storage.passkey_challenge_repository();
+    let passkey_verifier = WebAuthn::new(&envvar.webauthn_rp_id, &envvar.webauthn_origin);
+    let register_passkey = RegisterPasskeyUseCase::new(
+        &passkey_verifier,
+        &mut *passkey_credential_repository,
+        &mut passkey_challenge_repository,
+        get_systime,
+    );
+
+    match register_passkey.execute(
+        &current_user.id,
+        &body.challenge,
+        &client_data_json,
+        &attestation_object,
+    ) {
+        Ok(credential_id) => Ok(HttpResponse::Created()
+            .content_type(ContentType::json())
+            .json(RegisterPasskeyResponse { credential_id })),
+        Err(RegisterPasskeyFailReason::InvalidChallenge) => Err(ApiError::InvalidPasskey),
+        Err(RegisterPasskeyFailReason::InvalidResponse(err)) => {
+            Err(ApiError::InvalidRequest(err.to_string()))
+        }
+        Err(RegisterPasskeyFailReason::CredentialConflict) => Err(ApiError::PasskeyTaken),
+    }
+}
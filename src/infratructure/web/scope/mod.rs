@@ -1,2 +1,8 @@
 pub mod auth;
+pub mod authorize;
 pub mod healthz;
+pub mod me;
+pub mod password;
+pub mod token;
+pub mod users;
+pub mod well_known;
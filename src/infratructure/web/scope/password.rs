@@ -0,0 +1,108 @@
+use actix_web::{HttpResponse, Scope, post, web};
+use serde::Deserialize;
+use std::path::PathBuf;
+
+use super::auth::send_in_background;
+use crate::application::use_case::{
+    ForgotPasswordUseCase, PasswordRules, ResetPasswordFailReason, ResetPasswordUseCase,
+};
+use crate::domain::value_object::EmailAddress;
+use crate::infratructure::{
+    auth::PwnedPasswordsCorpus,
+    mail::Mailer,
+    repository::{SessionStorage, UserStorage},
+    system::{EnvVar, generate_secret, get_systime, hash_secret},
+    web::{error::ApiError, extractor::PasswordHashing},
+};
+
+pub fn scope(path: &str) -> Scope {
+    web::scope(path).service(forgot).service(reset)
+}
+
+#[derive(Deserialize)]
+struct ForgotPasswordRequestBody {
+    email: String,
+}
+
+#[post("/forgot")]
+async fn forgot(
+    body: web::Json<ForgotPasswordRequestBody>,
+    user_storage: web::Data<UserStorage>,
+    session_storage: web::Data<SessionStorage>,
+    envvar: web::Data<EnvVar>,
+    mailer: web::Data<Mailer>,
+) -> Result<HttpResponse, ApiError> {
+    let email = match EmailAddress::new(&body.email) {
+        Ok(email) => email,
+        Err(_) => return Ok(HttpResponse::Accepted().finish()),
+    };
+
+    send_in_background(move || {
+        let user_repository = user_storage.user_repository();
+        let mut password_reset_token_repository = session_storage.password_reset_token_repository();
+        let password_reset_policy = envvar.password_reset_policy();
+        let forgot_password = ForgotPasswordUseCase::new(
+            mailer.mail_sender(),
+            &*user_repository,
+            &mut password_reset_token_repository,
+            &password_reset_policy,
+            generate_secret,
+            hash_secret,
+            get_systime,
+        );
+
+        if let Err(err) = forgot_password.execute(email) {
+            log::error!("failed to send password reset: {}", err);
+        }
+    });
+
+    Ok(HttpResponse::Accepted().finish())
+}
+
+#[derive(Deserialize)]
+struct ResetPasswordRequestBody {
+    token: String,
+    password: String,
+}
+
+#[post("/reset")]
+async fn reset(
+    body: web::Json<ResetPasswordRequestBody>,
+    user_storage: web::Data<UserStorage>,
+    session_storage: web::Data<SessionStorage>,
+    envvar: web::Data<EnvVar>,
+    password_hashing: PasswordHashing,
+) -> Result<HttpResponse, ApiError> {
+    let body = body.into_inner();
+
+    let result = password_hashing
+        .run(move |password_hasher| {
+            let password_policy = envvar.password_policy();
+            let breached_password_checker =
+                PwnedPasswordsCorpus::new(envvar.pwned_passwords_dir.as_ref().map(PathBuf::from));
+            let mut user_repository = user_storage.user_repository();
+            let mut password_reset_token_repository =
+                session_storage.password_reset_token_repository();
+            let mut refresh_token_family_repository =
+                session_storage.refresh_token_family_repository();
+            let reset_password = ResetPasswordUseCase::new(
+                password_hasher,
+                PasswordRules::new(&password_policy, &breached_password_checker),
+                &mut *user_repository,
+                &mut password_reset_token_repository,
+                &mut refresh_token_family_repository,
+                hash_secret,
+                get_systime,
+            );
+
+            reset_password.execute(&body.token, &body.password)
+        })
+        .await
+        .map_err(|_| ApiError::ServiceBusy)?;
+
+    match result {
+        Ok(_) => Ok(HttpResponse::NoContent().finish()),
+        Err(ResetPasswordFailReason::InvalidToken) => Err(ApiError::InvalidToken),
+        Err(ResetPasswordFailReason::InvalidPassword(err)) => Err(ApiError::InvalidPassword(err)),
+    }
+}
@@ -0,0 +1,520 @@
+use actix_web::{
+    HttpRequest, HttpResponse, Scope,
+    http::header::{CACHE_CONTROL, ContentType},
+    post, web,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::application::use_case::{
+    ClientAuthenticator, ClientCredentialsFailReason, ClientCredentialsUseCase,
+    ExchangeAuthorizationCodeFailReason, ExchangeAuthorizationCodeUseCase, IntrospectFailReason,
+    IntrospectResult, IntrospectUseCase, RefreshUseCase, RevokeUseCase, SessionIssuer,
+    TokenTypeHint,
+};
+use crate::infratructure::{
+    auth::{Argon2Validator, ExpectedClaims, InfraClaims, JWTIssuer, JWTVerifier, TokenKeys},
+    repository::{SessionStorage, UserStorage},
+    system::{EnvVar, generate_id, get_systime, hash_secret},
+    web::{
+        error::{ApiError, OAuthError},
+        extractor::{PasswordHashing, basic_credentials},
+    },
+};
+
+pub fn scope(path: &str) -> Scope {
+    web::scope(path)
+        .service(token)
+        .service(refresh)
+        .service(introspect)
+        .service(revoke)
+}
+
+#[derive(Deserialize)]
+struct TokenRequestBody {
+    grant_type: Option<String>,
+    code: Option<String>,
+    redirect_uri: Option<String>,
+    client_id: Option<String>,
+    client_secret: Option<String>,
+    code_verifier: Option<String>,
+    scope: Option<String>,
+}
+
+#[derive(Serialize)]
+struct TokenResponse {
+    pub access_token: String,
+    pub token_type: &'static str,
+    pub expires_in: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub refresh_token: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scope: Option<String>,
+}
+
+#[post("")]
+async fn token(
+    request: HttpRequest,
+    body: web::Form<TokenRequestBody>,
+    user_storage: web::Data<UserStorage>,
+    session_storage: web::Data<SessionStorage>,
+    envvar: web::Data<EnvVar>,
+    token_keys: web::Data<TokenKeys>,
+    password_hashing: PasswordHashing,
+) -> Result<HttpResponse, OAuthError> {
+    let body = body.into_inner();
+    let response = match body.grant_type.as_deref() {
+        Some("authorization_code") => {
+            exchange_authorization_code(
+                &request,
+                body,
+                user_storage,
+                session_storage,
+                envvar,
+                token_keys,
+                password_hashing,
+            )
+            .await?
+        }
+        Some("client_credentials") => {
+            let (client_id, client_secret) =
+                client_authentication(&request, &body.client_id, &body.client_secret)?;
+            client_credentials(
+                client_id,
+                client_secret,
+                body.scope,
+                user_storage,
+                envvar,
+                token_keys,
+                password_hashing,
+            )
+            .await?
+        }
+        Some(_) => return Err(OAuthError::UnsupportedGrantType),
+        None => {
+            return Err(OAuthError::InvalidRequest(
+                "grant_type is required".to_string(),
+            ));
+        }
+    };
+
+    Ok(HttpResponse::Ok()
+        .content_type(ContentType::json())
+        .insert_header((CACHE_CONTROL, "no-store"))
+        .json(response))
+}
+
+async fn exchange_authorization_code(
+    request: &HttpRequest,
+    body: TokenRequestBody,
+    user_storage: web::Data<UserStorage>,
+    session_storage: web::Data<SessionStorage>,
+    envvar: web::Data<EnvVar>,
+    token_keys: web::Data<TokenKeys>,
+    password_hashing: PasswordHashing,
+) -> Result<TokenResponse, OAuthError> {
+    let (client_id, client_secret) =
+        optional_client_authentication(request, &body.client_id, &body.client_secret)?;
+    let (Some(code), Some(redirect_uri), Some(client_id), Some(code_verifier)) =
+        (body.code, body.redirect_uri, client_id, body.code_verifier)
+    else {
+        return Err(OAuthError::InvalidRequest(
+            "code, redirect_uri, client_id and code_verifier are required".to_string(),
+        ));
+    };
+    let access_token_valid_seconds = envvar.access_token_valid_seconds;
+
+    let result = password_hashing
+        .run(move |password_hasher| {
+            let oauth_client_repository = user_storage.oauth_client_repository();
+            let user_repository = user_storage.user_repository();
+            let mut authorization_code_repository = session_storage.authorization_code_repository();
+            let mut refresh_token_family_repository =
+                session_storage.refresh_token_family_repository();
+            let now = get_systime();
+            let access_token_keys = token_keys.access_token.read().unwrap();
+            let refresh_token_keys = token_keys.refresh_token.read().unwrap();
+            let access_token_issuer = JWTIssuer::new(
+                access_token_keys.current(),
+                InfraClaims {
+                    iss: envvar.app_name.clone(),
+                    aud: envvar.app_name.clone(),
+                    iat: now,
+                    exp: now + envvar.access_token_valid_seconds,
+                },
+            );
+            let refresh_token_issuer = JWTIssuer::new(
+                refresh_token_keys.current(),
+                InfraClaims {
+                    iss: envvar.app_name.clone(),
+                    aud: envvar.app_name.clone(),
+                    iat: now,
+                    exp: now + envvar.refresh_token_valid_seconds,
+                },
+            );
+            let exchange_authorization_code = ExchangeAuthorizationCodeUseCase::new(
+                SessionIssuer::new(
+                    &access_token_issuer,
+                    &refresh_token_issuer,
+                    &mut refresh_token_family_repository,
+                    generate_id,
+                ),
+                ClientAuthenticator::new(
+                    password_hasher,
+                    &Argon2Validator {},
+                    &*oauth_client_repository,
+                ),
+                &*user_repository,
+                &mut authorization_code_repository,
+                hash_secret,
+                get_systime,
+            );
+
+            exchange_authorization_code.execute(
+                &code,
+                &client_id,
+                client_secret.as_deref(),
+                &redirect_uri,
+                &code_verifier,
+            )
+        })
+        .await
+        .map_err(|_| OAuthError::ServiceBusy)?;
+
+    match result {
+        Ok(res) => Ok(TokenResponse {
+            access_token: res.access_token,
+            token_type: "Bearer",
+            expires_in: access_token_valid_seconds,
+            refresh_token: Some(res.refresh_token),
+            scope: None,
+        }),
+        Err(ExchangeAuthorizationCodeFailReason::InvalidClient) => Err(OAuthError::InvalidClient),
+        Err(ExchangeAuthorizationCodeFailReason::InvalidGrant) => Err(OAuthError::InvalidGrant),
+    }
+}
+
+fn optional_client_authentication(
+    request: &HttpRequest,
+    client_id: &Option<String>,
+    client_secret: &Option<String>,
+) -> Result<(Option<String>, Option<String>), OAuthError> {
+    match (basic_credentials(request), client_id) {
+        (Some((basic_client_id, _)), Some(client_id)) if basic_client_id != *client_id => {
+            Err(OAuthError::InvalidRequest(
+                "client_id does not match the authenticated client".to_string(),
+            ))
+        }
+        (None, _) if client_secret.is_none() => Ok((client_id.clone(), None)),
+        _ => client_authentication(request, client_id, client_secret)
+            .map(|(client_id, client_secret)| (Some(client_id), Some(client_secret))),
+    }
+}
+
+fn client_authentication(
+    request: &HttpRequest,
+    client_id: &Option<String>,
+    client_secret: &Option<String>,
+) -> Result<(String, String), OAuthError> {
+    match (basic_credentials(request), client_id, client_secret) {
+        (Some(_), _, Some(_)) => Err(OAuthError::InvalidRequest(
+            "Client must use only one authentication method".to_string(),
+        )),
+        (Some(credentials), _, None) => Ok(credentials),
+        (None, Some(client_id), Some(client_secret)) => {
+            Ok((client_id.clone(), client_secret.clone()))
+        }
+        _ => Err(OAuthError::InvalidClient),
+    }
+}
+
+async fn client_credentials(
+    client_id: String,
+    client_secret: String,
+    scope: Option<String>,
+    user_storage: web::Data<UserStorage>,
+    envvar: web::Data<EnvVar>,
+    token_keys: web::Data<TokenKeys>,
+    password_hashing: PasswordHashing,
+) -> Result<TokenResponse, OAuthError> {
+    let access_token_valid_seconds = envvar.access_token_valid_seconds;
+
+    let result = password_hashing
+        .run(move |password_hasher| {
+            let oauth_client_repository = user_storage.oauth_client_repository();
+            let now = get_systime();
+            let access_token_keys = token_keys.access_token.read().unwrap();
+            let access_token_issuer = JWTIssuer::new(
+                access_token_keys.current(),
+                InfraClaims {
+                    iss: envvar.app_name.clone(),
+                    aud: envvar.app_name.clone(),
+                    iat: now,
+                    exp: now + envvar.access_token_valid_seconds,
+                },
+            );
+            let client_credentials = ClientCredentialsUseCase::new(
+                password_hasher,
+                &Argon2Validator {},
+                &access_token_issuer,
+                &*oauth_client_repository,
+                generate_id,
+            );
+
+            client_credentials.execute(&client_id, &client_secret, scope.as_deref())
+        })
+        .await
+        .map_err(|_| OAuthError::ServiceBusy)?;
+
+    match result {
+        Ok(res) => Ok(TokenResponse {
+            access_token: res.access_token,
+            token_type: "Bearer",
+            expires_in: access_token_valid_seconds,
+            refresh_token: None,
+            scope: res.scope,
+        }),
+        Err(ClientCredentialsFailReason::InvalidClient) => Err(OAuthError::InvalidClient),
+        Err(ClientCredentialsFailReason::InvalidScope) => Err(OAuthError::InvalidScope),
+    }
+}
+
+#[derive(Deserialize)]
+struct RefreshRequestBody {
+    refresh_token: String,
+}
+
+#[derive(Serialize)]
+struct RefreshResponse {
+    pub access_token: String,
+    pub refresh_token: String,
+}
+
+#[post("/refresh")]
+async fn refresh(
+    body: web::Json<RefreshRequestBody>,
+    session_storage: web::Data<SessionStorage>,
+    envvar: web::Data<EnvVar>,
+    token_keys: web::Data<TokenKeys>,
+) -> Result<HttpResponse, ApiError> {
+    let mut refresh_token_family_repository = session_storage.refresh_token_family_repository();
+    let revoked_token_repository = session_storage.revoked_token_repository();
+    let now = get_systime();
+    let access_token_keys = token_keys.access_token.read().unwrap();
+    let refresh_token_keys = token_keys.refresh_token.read().unwrap();
+    let refresh_token_verifier = JWTVerifier::new(
+        &refresh_token_keys,
+        ExpectedClaims {
+            iss: envvar.app_name.clone(),
+            aud: envvar.app_name.clone(),
+            now,
+        },
+        &revoked_token_repository,
+    );
+    let access_token_issuer = JWTIssuer::new(
+        access_token_keys.current(),
+        InfraClaims {
+            iss: envvar.app_name.clone(),
+            aud: envvar.app_name.clone(),
+            iat: now,
+            exp: now + envvar.access_token_valid_seconds,
+        },
+    );
+    let refresh_token_issuer = JWTIssuer::new(
+        refresh_token_keys.current(),
+        InfraClaims {
+            iss: envvar.app_name.clone(),
+            aud: envvar.app_name.clone(),
+            iat: now,
+            exp: now + envvar.refresh_token_valid_seconds,
+        },
+    );
+    let refresh = RefreshUseCase::new(
+        &refresh_token_verifier,
+        &access_token_issuer,
+        &refresh_token_issuer,
+        &mut refresh_token_family_repository,
+        generate_id,
+    );
+
+    let res = refresh
+        .execute(&body.refresh_token)
+        .map_err(|_| ApiError::InvalidToken)?;
+
+    Ok(HttpResponse::Ok()
+        .content_type(ContentType::json())
+        .json(RefreshResponse {
+            access_token: res.access_token,
+            refresh_token: res.refresh_token,
+        }))
+}
+
+#[derive(Deserialize)]
+struct IntrospectRequestBody {
+    token: String,
+    token_type_hint: Option<String>,
+    client_id: Option<String>,
+    client_secret: Option<String>,
+}
+
+#[derive(Serialize)]
+struct IntrospectResponse {
+    pub active: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sub: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exp: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub iat: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub iss: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub aud: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scope: Option<String>,
+}
+
+#[post("/introspect")]
+async fn introspect(
+    request: HttpRequest,
+    body: web::Form<IntrospectRequestBody>,
+    user_storage: web::Data<UserStorage>,
+    session_storage: web::Data<SessionStorage>,
+    envvar: web::Data<EnvVar>,
+    token_keys: web::Data<TokenKeys>,
+    password_hashing: PasswordHashing,
+) -> Result<HttpResponse, OAuthError> {
+    let body = body.into_inner();
+    let (client_id, client_secret) =
+        client_authentication(&request, &body.client_id, &body.client_secret)?;
+
+    let result = password_hashing
+        .run(move |password_hasher| {
+            let oauth_client_repository = user_storage.oauth_client_repository();
+            let revoked_token_repository = session_storage.revoked_token_repository();
+            let now = get_systime();
+            let access_token_keys = token_keys.access_token.read().unwrap();
+            let refresh_token_keys = token_keys.refresh_token.read().unwrap();
+            let access_token_verifier = JWTVerifier::new(
+                &access_token_keys,
+                ExpectedClaims {
+                    iss: envvar.app_name.clone(),
+                    aud: envvar.app_name.clone(),
+                    now,
+                },
+                &revoked_token_repository,
+            );
+            let refresh_token_verifier = JWTVerifier::new(
+                &refresh_token_keys,
+                ExpectedClaims {
+                    iss: envvar.app_name.clone(),
+                    aud: envvar.app_name.clone(),
+                    now,
+                },
+                &revoked_token_repository,
+            );
+            let introspect = IntrospectUseCase::new(
+                password_hasher,
+                &Argon2Validator {},
+                &*oauth_client_repository,
+                &access_token_verifier,
+                &refresh_token_verifier,
+            );
+
+            introspect.execute(
+                &client_id,
+                &client_secret,
+                &body.token,
+                parse_token_type_hint(&body.token_type_hint),
+            )
+        })
+        .await
+        .map_err(|_| OAuthError::ServiceBusy)?;
+
+    match result {
+        Ok(result) => Ok(introspect_response(result)),
+        Err(IntrospectFailReason::InvalidClient) => Err(OAuthError::InvalidClient),
+    }
+}
+
+fn introspect_response(result: IntrospectResult) -> HttpResponse {
+    let response = match result {
+        IntrospectResult::Active(claims) => IntrospectResponse {
+            active: true,
+            sub: Some(claims.sub),
+            exp: Some(claims.exp),
+            iat: Some(claims.iat),
+            iss: Some(claims.iss),
+            aud: Some(claims.aud),
+            scope: claims.scope,
+        },
+        IntrospectResult::Inactive => IntrospectResponse {
+            active: false,
+            sub: None,
+            exp: None,
+            iat: None,
+            iss: None,
+            aud: None,
+            scope: None,
+        },
+    };
+    HttpResponse::Ok()
+        .content_type(ContentType::json())
+        .json(response)
+}
+
+#[derive(Deserialize)]
+struct RevokeRequestBody {
+    token: String,
+    token_type_hint: Option<String>,
+}
+
+#[post("/revoke")]
+async fn revoke(
+    body: web::Form<RevokeRequestBody>,
+    session_storage: web::Data<SessionStorage>,
+    envvar: web::Data<EnvVar>,
+    token_keys: web::Data<TokenKeys>,
+) -> HttpResponse {
+    let mut refresh_token_family_repository = session_storage.refresh_token_family_repository();
+    let mut revoked_token_repository = session_storage.revoked_token_repository();
+    let verifier_revoked_token_repository = session_storage.revoked_token_repository();
+    let now = get_systime();
+    let access_token_keys = token_keys.access_token.read().unwrap();
+    let refresh_token_keys = token_keys.refresh_token.read().unwrap();
+    let access_token_verifier = JWTVerifier::new(
+        &access_token_keys,
+        ExpectedClaims {
+            iss: envvar.app_name.clone(),
+            aud: envvar.app_name.clone(),
+            now,
+        },
+        &verifier_revoked_token_repository,
+    );
+    let refresh_token_verifier = JWTVerifier::new(
+        &refresh_token_keys,
+        ExpectedClaims {
+            iss: envvar.app_name.clone(),
+            aud: envvar.app_name.clone(),
+            now,
+        },
+        &verifier_revoked_token_repository,
+    );
+    let revoke = RevokeUseCase::new(
+        &access_token_verifier,
+        &refresh_token_verifier,
+        &mut revoked_token_repository,
+        &mut refresh_token_family_repository,
+    );
+
+    revoke.execute(&body.token, parse_token_type_hint(&body.token_type_hint));
+
+    HttpResponse::Ok().finish()
+}
+
+fn parse_token_type_hint(token_type_hint: &Option<String>) -> Option<TokenTypeHint> {
+    match token_type_hint.as_deref() {
+        Some("access_token") => Some(TokenTypeHint::AccessToken),
+        Some("refresh_token") => Some(TokenTypeHint::RefreshToken),
+        _ => None,
+    }
+}
@@ -0,0 +1,62 @@
+use actix_web::{HttpResponse, Scope, get, web};
+use serde::{Deserialize, Serialize};
+
+use crate::application::use_case::{ListUsersQuery, ListUsersUseCase};
+use crate::infratructure::{
+    repository::UserStorage,
+    web::{error::ApiError, extractor::AccessToken},
+};
+
+const USERS_READ_SCOPE: &str = "users:read";
+const DEFAULT_PAGE_SIZE: usize = 20;
+
+pub fn scope(path: &str) -> Scope {
+    web::scope(path).service(list_users)
+}
+
+#[derive(Deserialize)]
+struct ListUsersRequestQuery {
+    offset: Option<usize>,
+    limit: Option<usize>,
+    username: Option<String>,
+}
+
+#[derive(Serialize)]
+struct UserResponse {
+    id: String,
+    username: String,
+    email: String,
+    email_verified: bool,
+    create_at: u64,
+}
+
+#[get("")]
+async fn list_users(
+    access_token: AccessToken,
+    query: web::Query<ListUsersRequestQuery>,
+    user_storage: web::Data<UserStorage>,
+) -> Result<HttpResponse, ApiError> {
+    access_token.require_scope(USERS_READ_SCOPE)?;
+    let query = query.into_inner();
+
+    let user_repository = user_storage.user_repository();
+    let list_users = ListUsersUseCase::new(&*user_repository);
+    let users = list_users.execute(ListUsersQuery {
+        offset: query.offset.unwrap_or_default(),
+        limit: query.limit.unwrap_or(DEFAULT_PAGE_SIZE),
+        username: query.username,
+    });
+
+    Ok(HttpResponse::Ok().json(
+        users
+            .into_iter()
+            .map(|user| UserResponse {
+                id: user.id.as_str().to_string(),
+                username: user.username,
+                email: user.email.as_str().to_string(),
+                email_verified: user.email_verified,
+                create_at: user.create_at,
+            })
+            .collect::<Vec<_>>(),
+    ))
+}
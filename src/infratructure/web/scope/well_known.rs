@@ -0,0 +1,22 @@
+use actix_web::{HttpResponse, Scope, get, http::header::ContentType, web};
+use serde::Serialize;
+
+use crate::infratructure::auth::{Jwk, TokenKeys};
+
+pub fn scope(path: &str) -> Scope {
+    web::scope(path).service(jwks)
+}
+
+#[derive(Serialize)]
+struct JwksResponse {
+    pub keys: Vec<Jwk>,
+}
+
+#[get("/jwks.json")]
+async fn jwks(token_keys: web::Data<TokenKeys>) -> HttpResponse {
+    HttpResponse::Ok()
+        .content_type(ContentType::json())
+        .json(JwksResponse {
+            keys: token_keys.access_token.read().unwrap().public_jwks(),
+        })
+}
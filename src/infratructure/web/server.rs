@@ -1,21 +1,232 @@
-use super::scope::{auth, healthz};
+use super::{
+    error::{form_error_handler, json_error_handler},
+    rate_limit::{RateLimitRule, RateLimiter, TokenBucket, rate_limit},
+    scope::{auth, authorize, healthz, me, password, token, users, well_known},
+};
 use crate::{
-    domain::entity::User,
-    infratructure::{repository::GenericTableManager, system::get_envvar},
+    application::service::auth::PasswordHasher,
+    domain::entity::OAuthClient,
+    infratructure::{
+        auth::{Argon2Hasher, KeyRing, SigningKey, TokenKeys},
+        mail::{Mailer, OutboxMailSender, SmtpMailSender},
+        repository::{GenericTableManager, SessionStorage, SqliteDatabase, UserStorage},
+        system::{BlockingPool, EnvVar, generate_id, get_envvar, get_systime, init_logger},
+    },
 };
-use actix_web::{App, HttpServer, web};
+use actix_web::{App, HttpServer, middleware::from_fn, rt, web};
+use serde::Deserialize;
+use std::{fs, path::PathBuf, sync::RwLock, time::Duration};
 
 pub async fn start_server(host: &str, port: u16) -> std::io::Result<()> {
-    let envvar = web::Data::new(get_envvar());
-    let user_table_manager = web::Data::new(GenericTableManager::<User>::new());
+    let envvar = get_envvar();
+    init_logger(envvar.log_level);
+    let token_keys = web::Data::new(load_token_keys(&envvar));
+    if let Some(dir) = &envvar.access_token_keys_dir {
+        spawn_key_ring_reloader(
+            token_keys.clone(),
+            PathBuf::from(dir),
+            Duration::from_secs(envvar.access_token_keys_reload_seconds),
+        );
+    }
+    let blocking_pool = web::Data::new(BlockingPool::new(
+        envvar.hashing_threads,
+        envvar.hashing_queue_size,
+    ));
+    let password_hasher = web::Data::new(Argon2Hasher::new(
+        envvar.password_hash_memory_kib,
+        envvar.password_hash_iterations,
+        envvar.password_hash_parallelism,
+    ));
+    let rate_limiter = web::Data::new(
+        RateLimiter::new(
+            RateLimitRule {
+                capacity: envvar.rate_limit_capacity,
+                refill_seconds: envvar.rate_limit_refill_seconds,
+            },
+            envvar.trusted_proxies.clone(),
+            &GenericTableManager::<TokenBucket>::new(),
+            get_systime,
+        )
+        .route(
+            "/signin",
+            RateLimitRule {
+                capacity: envvar.rate_limit_signin_capacity,
+                refill_seconds: envvar.rate_limit_signin_refill_seconds,
+            },
+        )
+        .route(
+            "/signin/magic-link",
+            RateLimitRule {
+                capacity: envvar.rate_limit_signin_capacity,
+                refill_seconds: envvar.rate_limit_signin_refill_seconds,
+            },
+        )
+        .route(
+            "/token",
+            RateLimitRule {
+                capacity: envvar.rate_limit_signin_capacity,
+                refill_seconds: envvar.rate_limit_signin_refill_seconds,
+            },
+        )
+        .route(
+            "/authorize",
+            RateLimitRule {
+                capacity: envvar.rate_limit_signin_capacity,
+                refill_seconds: envvar.rate_limit_signin_refill_seconds,
+            },
+        )
+        .route(
+            "/signup",
+            RateLimitRule {
+                capacity: envvar.rate_limit_signup_capacity,
+                refill_seconds: envvar.rate_limit_signup_refill_seconds,
+            },
+        ),
+    );
+    let mailer = web::Data::new(load_mailer(&envvar));
+    let envvar = web::Data::new(envvar);
+    let user_storage = web::Data::new(match &envvar.database_path {
+        Some(path) => UserStorage::Sqlite(
+            SqliteDatabase::open(path).expect("database should be opened and migrated"),
+        ),
+        None => UserStorage::in_memory(),
+    });
+    load_oauth_clients(&envvar, &user_storage, &password_hasher);
+    let session_storage = web::Data::new(SessionStorage::in_memory(get_systime));
     HttpServer::new(move || {
         App::new()
             .app_data(envvar.clone())
-            .app_data(user_table_manager.clone())
+            .app_data(token_keys.clone())
+            .app_data(user_storage.clone())
+            .app_data(session_storage.clone())
+            .app_data(password_hasher.clone())
+            .app_data(blocking_pool.clone())
+            .app_data(rate_limiter.clone())
+            .app_data(mailer.clone())
+            .app_data(web::JsonConfig::default().error_handler(json_error_handler))
+            .app_data(web::FormConfig::default().error_handler(form_error_handler))
             .service(healthz::scope("/healthz"))
-            .service(auth::scope(""))
+            .service(well_known::scope("/.well-known"))
+            .service(token::scope("/token").wrap(from_fn(rate_limit)))
+            .service(authorize::scope("/authorize").wrap(from_fn(rate_limit)))
+            .service(password::scope("/password").wrap(from_fn(rate_limit)))
+            .service(me::scope("/me").wrap(from_fn(rate_limit)))
+            .service(users::scope("/users").wrap(from_fn(rate_limit)))
+            .service(auth::scope("").wrap(from_fn(rate_limit)))
     })
     .bind((host, port))?
     .run()
     .await
 }
+
+fn load_token_keys(envvar: &EnvVar) -> TokenKeys {
+    let access_token = match (
+        &envvar.access_token_keys_dir,
+        &envvar.access_token_private_key_path,
+    ) {
+        (Some(dir), _) => KeyRing::load_dir(&PathBuf::from(dir))
+            .expect("access token keys directory should contain the current key"),
+        (None, Some(path)) => KeyRing::single(
+            SigningKey::from_pem(
+                &fs::read_to_string(path).expect("access token private key should be readable"),
+                envvar.access_token_key_id.clone(),
+            )
+            .expect("access token private key should be a supported PEM")
+            .with_default_kid(),
+        ),
+        (None, None) => KeyRing::single(SigningKey::hmac(
+            envvar
+                .access_token_secret
+                .as_ref()
+                .expect("ACCESS_TOKEN_SECRET is required without a private key"),
+            envvar.access_token_key_id.clone(),
+        )),
+    };
+
+    TokenKeys {
+        access_token: RwLock::new(access_token),
+        refresh_token: RwLock::new(KeyRing::single(SigningKey::hmac(
+            &envvar.refresh_token_secret,
+            None,
+        ))),
+        email_verification_token: RwLock::new(KeyRing::single(SigningKey::hmac(
+            &envvar.email_verification_token_secret,
+            None,
+        ))),
+        mfa_token: RwLock::new(KeyRing::single(SigningKey::hmac(
+            &envvar.mfa_token_secret,
+            None,
+        ))),
+        magic_link_token: RwLock::new(KeyRing::single(SigningKey::hmac(
+            &envvar.magic_link_token_secret,
+            None,
+        ))),
+    }
+}
+
+#[derive(Deserialize)]
+struct OAuthClientConfig {
+    id: String,
+    name: String,
+    #[serde(default)]
+    redirect_uris: Vec<String>,
+    secret: Option<String>,
+    #[serde(default)]
+    scopes: Vec<String>,
+}
+
+fn load_oauth_clients(envvar: &EnvVar, user_storage: &UserStorage, password_hasher: &Argon2Hasher) {
+    let Some(path) = &envvar.oauth_clients_path else {
+        return;
+    };
+    let clients: Vec<OAuthClientConfig> = serde_json::from_str(
+        &fs::read_to_string(path).expect("oauth clients file should be readable"),
+    )
+    .expect("oauth clients file should be a JSON array of clients");
+    let mut oauth_client_repository = user_storage.oauth_client_repository();
+    for client in clients {
+        oauth_client_repository.save(OAuthClient {
+            id: client.id,
+            name: client.name,
+            redirect_uris: client.redirect_uris,
+            secret_hash: client.secret.map(|secret| password_hasher.hash(&secret)),
+            scopes: client.scopes,
+        });
+    }
+}
+
+fn load_mailer(envvar: &EnvVar) -> Mailer {
+    match envvar.mail_sender.as_str() {
+        "smtp" => Mailer::Smtp(SmtpMailSender::new(
+            envvar
+                .smtp_host
+                .as_ref()
+                .expect("SMTP_HOST is required for the smtp mail sender"),
+            envvar.smtp_port,
+            envvar
+                .smtp_username
+                .clone()
+                .zip(envvar.smtp_password.clone()),
+            &envvar.mail_from,
+        )),
+        "outbox" => Mailer::Outbox(OutboxMailSender::new(
+            PathBuf::from(&envvar.mail_outbox_dir),
+            &envvar.mail_from,
+            generate_id,
+        )),
+        other => panic!("unsupported MAIL_SENDER: {}", other),
+    }
+}
+
+fn spawn_key_ring_reloader(token_keys: web::Data<TokenKeys>, dir: PathBuf, period: Duration) {
+    rt::spawn(async move {
+        let mut interval = rt::time::interval(period);
+        loop {
+            interval.tick().await;
+            match KeyRing::load_dir(&dir) {
+                Ok(key_ring) => *token_keys.access_token.write().unwrap() = key_ring,
+                Err(err) => log::warn!("keeping current access token keys: {}", err),
+            }
+        }
+    });
+}
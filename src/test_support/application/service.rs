@@ -1,13 +1,28 @@
-use crate::application::service::auth::{PasswordHasher, PasswordValidator, TokenIssuer};
+use crate::application::service::auth::{
+    AttestedCredential, BreachedPasswordChecker, PasskeyVerifier, PasswordHasher,
+    PasswordValidator, TokenClaims, TokenIssuer, TokenSubject, TokenVerifier, TotpAuthenticator,
+};
+use crate::application::service::mail::{Mail, MailSender};
+use crate::domain::error;
+use std::cell::RefCell;
 
 pub struct FakePasswordHasher {
     to_return: String,
+    needs_rehash: bool,
 }
 
 impl FakePasswordHasher {
     pub fn new(to_return: &str) -> Self {
         FakePasswordHasher {
             to_return: to_return.to_string(),
+            needs_rehash: false,
+        }
+    }
+
+    pub fn outdated(to_return: &str) -> Self {
+        FakePasswordHasher {
+            to_return: to_return.to_string(),
+            needs_rehash: true,
         }
     }
 }
@@ -16,38 +31,196 @@ impl PasswordHasher for FakePasswordHasher {
     fn hash(&self, raw: &str) -> String {
         self.to_return.clone()
     }
+
+    fn dummy_hash(&self) -> &str {
+        "dummy_hash"
+    }
+
+    fn needs_rehash(&self, hashed: &str) -> bool {
+        self.needs_rehash
+    }
 }
 
 pub struct FakeTokenIssuer {
     to_return: String,
+    pub issued_subjects: RefCell<Vec<TokenSubject>>,
 }
 
 impl FakeTokenIssuer {
     pub fn new(to_return: &str) -> Self {
         FakeTokenIssuer {
             to_return: to_return.to_string(),
+            issued_subjects: RefCell::new(Vec::new()),
         }
     }
 }
 
 impl TokenIssuer for FakeTokenIssuer {
-    fn issue(&self, username: &str) -> String {
+    fn issue(&self, subject: &TokenSubject) -> String {
+        self.issued_subjects.borrow_mut().push(subject.clone());
         self.to_return.clone()
     }
 }
 
 pub struct FakePasswordValidator {
     is_valid: bool,
+    pub verified_hashes: RefCell<Vec<String>>,
 }
 
 impl FakePasswordValidator {
     pub fn new(is_valid: bool) -> Self {
-        FakePasswordValidator { is_valid }
+        FakePasswordValidator {
+            is_valid,
+            verified_hashes: RefCell::new(Vec::new()),
+        }
     }
 }
 
 impl PasswordValidator for FakePasswordValidator {
     fn verify(&self, raw: &str, hashed: &str) -> bool {
+        self.verified_hashes.borrow_mut().push(hashed.to_string());
         self.is_valid
     }
 }
+
+pub struct FakeBreachedPasswordChecker {
+    is_breached: bool,
+}
+
+impl FakeBreachedPasswordChecker {
+    pub fn new(is_breached: bool) -> Self {
+        FakeBreachedPasswordChecker { is_breached }
+    }
+}
+
+impl BreachedPasswordChecker for FakeBreachedPasswordChecker {
+    fn is_breached(&self, raw: &str) -> bool {
+        self.is_breached
+    }
+}
+
+pub struct FakeTokenVerifier {
+    to_return: Option<TokenClaims>,
+}
+
+impl FakeTokenVerifier {
+    pub fn new(to_return: Option<TokenClaims>) -> Self {
+        FakeTokenVerifier { to_return }
+    }
+}
+
+impl TokenVerifier for FakeTokenVerifier {
+    fn verify(&self, token: &str) -> Result<TokenClaims, error::InvalidToken> {
+        self.to_return.clone().ok_or(error::InvalidToken {})
+    }
+}
+
+pub struct FakeMailSender {
+    is_delivered: bool,
+    pub sent: RefCell<Vec<Mail>>,
+}
+
+impl FakeMailSender {
+    pub fn new(is_delivered: bool) -> Self {
+        FakeMailSender {
+            is_delivered,
+            sent: RefCell::new(Vec::new()),
+        }
+    }
+}
+
+impl MailSender for FakeMailSender {
+    fn send(&self, mail: &Mail) -> Result<(), error::MailDeliveryFailed> {
+        if !self.is_delivered {
+            return Err(error::MailDeliveryFailed {
+                reason: "unreachable".to_string(),
+            });
+        }
+        self.sent.borrow_mut().push(Mail {
+            to: mail.to.clone(),
+            subject: mail.subject.clone(),
+            body: mail.body.clone(),
+        });
+        Ok(())
+    }
+}
+
+pub struct FakeTotpAuthenticator {
+    secret: String,
+    valid_code: String,
+    step: u64,
+}
+
+impl FakeTotpAuthenticator {
+    pub fn new(secret: &str, valid_code: &str, step: u64) -> Self {
+        FakeTotpAuthenticator {
+            secret: secret.to_string(),
+            valid_code: valid_code.to_string(),
+            step,
+        }
+    }
+}
+
+impl TotpAuthenticator for FakeTotpAuthenticator {
+    fn generate_secret(&self) -> String {
+        self.secret.clone()
+    }
+
+    fn provisioning_uri(&self, secret: &str, account: &str) -> String {
+        format!("otpauth://totp/{}?secret={}", account, secret)
+    }
+
+    fn verify(&self, _: &str, code: &str, _: u64) -> Option<u64> {
+        (code == self.valid_code).then_some(self.step)
+    }
+}
+
+pub struct FakePasskeyVerifier {
+    is_valid: bool,
+    sign_count: u32,
+}
+
+impl FakePasskeyVerifier {
+    pub fn new(is_valid: bool, sign_count: u32) -> Self {
+        FakePasskeyVerifier {
+            is_valid,
+            sign_count,
+        }
+    }
+}
+
+impl PasskeyVerifier for FakePasskeyVerifier {
+    fn verify_registration(
+        &self,
+        _: &str,
+        _: &[u8],
+        _: &[u8],
+    ) -> Result<AttestedCredential, error::InvalidPasskeyResponse> {
+        if !self.is_valid {
+            return Err(error::InvalidPasskeyResponse {
+                reason: "signature mismatch",
+            });
+        }
+        Ok(AttestedCredential {
+            id: "credential_id".to_string(),
+            public_key: "public_key".to_string(),
+            sign_count: self.sign_count,
+        })
+    }
+
+    fn verify_assertion(
+        &self,
+        _: &str,
+        _: &str,
+        _: &[u8],
+        _: &[u8],
+        _: &[u8],
+    ) -> Result<u32, error::InvalidPasskeyResponse> {
+        if !self.is_valid {
+            return Err(error::InvalidPasskeyResponse {
+                reason: "signature mismatch",
+            });
+        }
+        Ok(self.sign_count)
+    }
+}
@@ -1 +1,5 @@
+pub mod oauth_client_repository_suite;
+pub mod passkey_credential_repository_suite;
 pub mod repository;
+pub mod totp_credential_repository_suite;
+pub mod user_repository_suite;
@@ -0,0 +1,73 @@
+use crate::domain::{
+    entity::OAuthClient, error::EntityNotExist, repository::OAuthClientRepository,
+};
+
+fn create_client() -> OAuthClient {
+    OAuthClient {
+        id: "spa".to_string(),
+        name: "Example SPA".to_string(),
+        redirect_uris: vec!["https://app.example.com/callback".to_string()],
+        secret_hash: None,
+        scopes: vec![],
+    }
+}
+
+pub fn save_given_client_should_persist_to_data(repo: &mut dyn OAuthClientRepository) {
+    repo.save(create_client());
+
+    let client = repo.get("spa");
+
+    assert!(client.is_ok_and(|client| {
+        client.name == "Example SPA"
+            && client.redirect_uris == vec!["https://app.example.com/callback"]
+    }));
+}
+
+pub fn save_given_existing_client_should_replace_it(repo: &mut dyn OAuthClientRepository) {
+    repo.save(create_client());
+    repo.save(OAuthClient {
+        name: "Renamed SPA".to_string(),
+        redirect_uris: vec![
+            "https://app.example.com/callback".to_string(),
+            "http://localhost:3000/callback".to_string(),
+        ],
+        ..create_client()
+    });
+
+    let client = repo.get("spa");
+
+    assert!(client.is_ok_and(|client| {
+        client.name == "Renamed SPA"
+            && client.redirect_uris
+                == vec![
+                    "https://app.example.com/callback",
+                    "http://localhost:3000/callback",
+                ]
+    }));
+}
+
+pub fn save_given_confidential_client_should_persist_credentials(
+    repo: &mut dyn OAuthClientRepository,
+) {
+    repo.save(OAuthClient {
+        id: "billing-job".to_string(),
+        name: "Billing job".to_string(),
+        redirect_uris: vec![],
+        secret_hash: Some("secret_hash".to_string()),
+        scopes: vec!["invoices:read".to_string(), "invoices:write".to_string()],
+    });
+
+    let client = repo.get("billing-job");
+
+    assert!(client.is_ok_and(|client| {
+        client.secret_hash.as_deref() == Some("secret_hash")
+            && client.redirect_uris.is_empty()
+            && client.scopes == vec!["invoices:read", "invoices:write"]
+    }));
+}
+
+pub fn get_given_not_exist_id_should_return_entity_not_exist(repo: &mut dyn OAuthClientRepository) {
+    let client = repo.get("spa");
+
+    assert!(client.is_err_and(|err| matches!(err, EntityNotExist {})));
+}
@@ -0,0 +1,104 @@
+use crate::domain::{
+    entity::PasskeyCredential,
+    error::{EntityConflict, SignCountChanged},
+    repository::PasskeyCredentialRepository,
+    value_object::UserId,
+};
+
+const USER_ID: &str = "0196e2a4-5c1b-4f7e-9a3d-2b8c6d4e1f00";
+const OTHER_USER_ID: &str = "0196e2a4-5c1b-4f7e-9a3d-2b8c6d4e1f01";
+
+fn create_credential(id: &str, user_id: &str, create_at: u64) -> PasskeyCredential {
+    PasskeyCredential {
+        id: id.to_string(),
+        user_id: UserId::new(user_id).unwrap(),
+        public_key: "cose_key".to_string(),
+        sign_count: 0,
+        create_at,
+    }
+}
+
+pub fn create_given_credential_should_persist_to_data(repo: &mut dyn PasskeyCredentialRepository) {
+    repo.create(create_credential("credential_id", USER_ID, 1747636936))
+        .expect("should be ok");
+
+    let credential = repo.get("credential_id");
+
+    assert!(credential.is_ok_and(|credential| {
+        credential.user_id.as_str() == USER_ID
+            && credential.public_key == "cose_key"
+            && credential.sign_count == 0
+            && credential.create_at == 1747636936
+    }));
+}
+
+pub fn create_given_conflict_id_should_return_entity_conflict(
+    repo: &mut dyn PasskeyCredentialRepository,
+) {
+    repo.create(create_credential("credential_id", USER_ID, 1747636936))
+        .expect("should be ok");
+
+    let result = repo.create(create_credential(
+        "credential_id",
+        OTHER_USER_ID,
+        1747636937,
+    ));
+
+    assert!(result.is_err_and(|err| matches!(err, EntityConflict {})));
+}
+
+pub fn list_by_user_should_return_only_credentials_of_user(
+    repo: &mut dyn PasskeyCredentialRepository,
+) {
+    repo.create(create_credential("second", USER_ID, 1747636937))
+        .expect("should be ok");
+    repo.create(create_credential("first", USER_ID, 1747636936))
+        .expect("should be ok");
+    repo.create(create_credential("other", OTHER_USER_ID, 1747636936))
+        .expect("should be ok");
+
+    let credentials = repo.list_by_user(&UserId::new(USER_ID).unwrap());
+
+    let ids: Vec<&str> = credentials.iter().map(|c| c.id.as_str()).collect();
+    assert_eq!(ids, vec!["first", "second"]);
+}
+
+pub fn update_sign_count_given_expected_count_should_persist_sign_count(
+    repo: &mut dyn PasskeyCredentialRepository,
+) {
+    repo.create(create_credential("credential_id", USER_ID, 1747636936))
+        .expect("should be ok");
+
+    let result = repo.update_sign_count("credential_id", 0, 42);
+
+    assert!(result.is_ok());
+    assert!(
+        repo.get("credential_id")
+            .is_ok_and(|credential| credential.sign_count == 42)
+    );
+}
+
+pub fn update_sign_count_given_changed_count_should_return_sign_count_changed(
+    repo: &mut dyn PasskeyCredentialRepository,
+) {
+    repo.create(create_credential("credential_id", USER_ID, 1747636936))
+        .expect("should be ok");
+    repo.update_sign_count("credential_id", 0, 42)
+        .expect("should be ok");
+
+    let result = repo.update_sign_count("credential_id", 0, 43);
+
+    assert!(result.is_err_and(|err| matches!(err, SignCountChanged {})));
+    assert!(
+        repo.get("credential_id")
+            .is_ok_and(|credential| credential.sign_count == 42)
+    );
+}
+
+pub fn update_sign_count_given_not_exist_credential_should_return_sign_count_changed(
+    repo: &mut dyn PasskeyCredentialRepository,
+) {
+    let result = repo.update_sign_count("credential_id", 0, 42);
+
+    assert!(result.is_err_and(|err| matches!(err, SignCountChanged {})));
+}
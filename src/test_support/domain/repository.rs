@@ -1,4 +1,17 @@
-use crate::domain::{entity::User, error, repository::UserRepository, value_object::EmailAddress};
+use crate::domain::{
+    entity::{
+        AuthorizationCode, LoginAttempt, OAuthClient, PasskeyChallenge, PasskeyCredential,
+        PasswordResetToken, RefreshTokenFamily, TotpCredential, User,
+    },
+    error,
+    repository::{
+        AuthorizationCodeRepository, LoginAttemptRepository, OAuthClientRepository,
+        PasskeyChallengeRepository, PasskeyCredentialRepository, PasswordResetTokenRepository,
+        RefreshTokenFamilyRepository, RevokedTokenRepository, TotpCredentialRepository,
+        UserRepository,
+    },
+    value_object::{EmailAddress, LockoutRule, UserId},
+};
 use std::collections::HashMap;
 
 pub struct FakeUserRepository {
@@ -15,24 +28,430 @@ impl FakeUserRepository {
 
 impl UserRepository for FakeUserRepository {
     fn create(&mut self, user: User) -> Result<(), error::EntityConflict> {
-        if self.data.contains_key(user.email.as_str()) {
+        if self.data.contains_key(user.id.as_str())
+            || self
+                .data
+                .values()
+                .any(|stored| stored.email.as_str() == user.email.as_str())
+        {
             return Err(error::EntityConflict {});
         }
-        self.data.insert(user.email.as_str().to_string(), user);
+        self.data.insert(user.id.as_str().to_string(), user);
 
         Ok(())
     }
 
     fn get(&self, email: EmailAddress) -> Result<User, error::EntityNotExist> {
-        match self.data.get(email.as_str()) {
-            Some(user) => Ok(User {
-                email: EmailAddress::new(user.email.as_str()).unwrap(),
-                username: user.username.clone(),
-                password: user.password.clone(),
-                create_at: user.create_at,
-                update_at: user.update_at,
-            }),
-            None => Err(error::EntityNotExist {}),
+        self.data
+            .values()
+            .find(|user| user.email.as_str() == email.as_str())
+            .cloned()
+            .ok_or(error::EntityNotExist {})
+    }
+
+    fn get_by_id(&self, id: &UserId) -> Result<User, error::EntityNotExist> {
+        self.data
+            .get(id.as_str())
+            .cloned()
+            .ok_or(error::EntityNotExist {})
+    }
+
+    fn get_by_username(&self, username: &str) -> Result<User, error::EntityNotExist> {
+        self.data
+            .values()
+            .filter(|user| user.username == username)
+            .min_by_key(|user| (user.create_at, user.email.as_str().to_string()))
+            .cloned()
+            .ok_or(error::EntityNotExist {})
+    }
+
+    fn list(&self, offset: usize, limit: usize) -> Vec<User> {
+        let mut users: Vec<&User> = self.data.values().collect();
+        users.sort_by_key(|user| (user.create_at, user.email.as_str().to_string()));
+        users
+            .into_iter()
+            .skip(offset)
+            .take(limit)
+            .cloned()
+            .collect()
+    }
+
+    fn update(&mut self, user: User) -> Result<(), error::UpdateUserError> {
+        if self.data.values().any(|stored| {
+            stored.id.as_str() != user.id.as_str() && stored.email.as_str() == user.email.as_str()
+        }) {
+            return Err(error::UpdateUserError::EmailConflict);
+        }
+        match self.data.get_mut(user.id.as_str()) {
+            Some(stored) => {
+                *stored = user;
+                Ok(())
+            }
+            None => Err(error::UpdateUserError::NotExist),
+        }
+    }
+
+    fn update_password_if_unchanged(
+        &mut self,
+        id: &UserId,
+        expected_password: &str,
+        password: &str,
+    ) -> bool {
+        match self.data.get_mut(id.as_str()) {
+            Some(stored) if stored.password == expected_password => {
+                stored.password = password.to_string();
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn delete(&mut self, id: &UserId) -> Result<(), error::EntityNotExist> {
+        self.data
+            .remove(id.as_str())
+            .map(|_| ())
+            .ok_or(error::EntityNotExist {})
+    }
+}
+
+pub struct FakeRefreshTokenFamilyRepository {
+    pub data: HashMap<String, RefreshTokenFamily>,
+}
+
+impl FakeRefreshTokenFamilyRepository {
+    pub fn new() -> Self {
+        FakeRefreshTokenFamilyRepository {
+            data: HashMap::new(),
+        }
+    }
+}
+
+impl RefreshTokenFamilyRepository for FakeRefreshTokenFamilyRepository {
+    fn create(&mut self, family: RefreshTokenFamily) -> Result<(), error::EntityConflict> {
+        if self.data.contains_key(&family.id) {
+            return Err(error::EntityConflict {});
+        }
+        self.data.insert(family.id.clone(), family);
+
+        Ok(())
+    }
+
+    fn get(&self, id: &str) -> Result<RefreshTokenFamily, error::EntityNotExist> {
+        self.data.get(id).cloned().ok_or(error::EntityNotExist {})
+    }
+
+    fn update(&mut self, family: RefreshTokenFamily) -> Result<(), error::EntityNotExist> {
+        if !self.data.contains_key(&family.id) {
+            return Err(error::EntityNotExist {});
+        }
+        self.data.insert(family.id.clone(), family);
+
+        Ok(())
+    }
+
+    fn rotate(
+        &mut self,
+        id: &str,
+        expected_token_id: &str,
+        new_token_id: &str,
+    ) -> Result<(), error::RefreshTokenReused> {
+        let family = self.data.get_mut(id).ok_or(error::RefreshTokenReused {})?;
+        if family.revoked {
+            return Err(error::RefreshTokenReused {});
+        }
+        if family.current_token_id != expected_token_id {
+            family.revoked = true;
+            return Err(error::RefreshTokenReused {});
+        }
+        family.current_token_id = new_token_id.to_string();
+
+        Ok(())
+    }
+
+    fn revoke_by_subject(&mut self, subject: &str) {
+        self.data
+            .values_mut()
+            .filter(|family| family.subject == subject)
+            .for_each(|family| family.revoked = true);
+    }
+}
+
+pub struct FakeRevokedTokenRepository {
+    pub data: HashMap<String, u64>,
+}
+
+impl FakeRevokedTokenRepository {
+    pub fn new() -> Self {
+        FakeRevokedTokenRepository {
+            data: HashMap::new(),
+        }
+    }
+}
+
+impl RevokedTokenRepository for FakeRevokedTokenRepository {
+    fn revoke(&mut self, token_id: &str, expire_at: u64) {
+        self.data.insert(token_id.to_string(), expire_at);
+    }
+
+    fn revoke_if_absent(&mut self, token_id: &str, expire_at: u64) -> bool {
+        if self.data.contains_key(token_id) {
+            return false;
+        }
+        self.data.insert(token_id.to_string(), expire_at);
+
+        true
+    }
+
+    fn is_revoked(&self, token_id: &str) -> bool {
+        self.data.contains_key(token_id)
+    }
+}
+
+pub struct FakeLoginAttemptRepository {
+    pub data: HashMap<String, LoginAttempt>,
+}
+
+impl FakeLoginAttemptRepository {
+    pub fn new() -> Self {
+        FakeLoginAttemptRepository {
+            data: HashMap::new(),
+        }
+    }
+}
+
+impl LoginAttemptRepository for FakeLoginAttemptRepository {
+    fn locked_until(&self, key: &str, now: u64) -> Option<u64> {
+        self.data
+            .get(key)
+            .map(|attempt| attempt.blocked_until)
+            .filter(|blocked_until| *blocked_until > now)
+    }
+
+    fn record_failure(&mut self, key: &str, rule: &LockoutRule, now: u64) -> LoginAttempt {
+        let previous_failures = self
+            .data
+            .get(key)
+            .filter(|attempt| attempt.expire_at > now)
+            .map_or(0, |attempt| attempt.failures);
+        let attempt = rule.next_attempt(key, previous_failures, now);
+        self.data.insert(attempt.key.clone(), attempt.clone());
+
+        attempt
+    }
+
+    fn delete(&mut self, key: &str) {
+        self.data.remove(key);
+    }
+}
+
+pub struct FakePasswordResetTokenRepository {
+    pub data: HashMap<String, PasswordResetToken>,
+}
+
+impl FakePasswordResetTokenRepository {
+    pub fn new() -> Self {
+        FakePasswordResetTokenRepository {
+            data: HashMap::new(),
+        }
+    }
+}
+
+impl PasswordResetTokenRepository for FakePasswordResetTokenRepository {
+    fn create(&mut self, token: PasswordResetToken) -> Result<(), error::EntityConflict> {
+        if self.data.contains_key(&token.token_hash) {
+            return Err(error::EntityConflict {});
+        }
+        self.data.insert(token.token_hash.clone(), token);
+
+        Ok(())
+    }
+
+    fn get(&self, token_hash: &str) -> Result<PasswordResetToken, error::EntityNotExist> {
+        self.data
+            .get(token_hash)
+            .cloned()
+            .ok_or(error::EntityNotExist {})
+    }
+
+    fn take(&mut self, token_hash: &str) -> Result<PasswordResetToken, error::EntityNotExist> {
+        self.data.remove(token_hash).ok_or(error::EntityNotExist {})
+    }
+}
+
+pub struct FakeTotpCredentialRepository {
+    pub data: HashMap<String, TotpCredential>,
+}
+
+impl FakeTotpCredentialRepository {
+    pub fn new() -> Self {
+        FakeTotpCredentialRepository {
+            data: HashMap::new(),
+        }
+    }
+}
+
+impl TotpCredentialRepository for FakeTotpCredentialRepository {
+    fn get(&self, user_id: &UserId) -> Result<TotpCredential, error::EntityNotExist> {
+        self.data
+            .get(user_id.as_str())
+            .cloned()
+            .ok_or(error::EntityNotExist {})
+    }
+
+    fn save(&mut self, credential: TotpCredential) {
+        self.data
+            .insert(credential.user_id.as_str().to_string(), credential);
+    }
+
+    fn advance_step(&mut self, user_id: &UserId, step: u64) -> bool {
+        match self.data.get_mut(user_id.as_str()) {
+            Some(credential) if credential.confirmed && step > credential.last_used_step => {
+                credential.last_used_step = step;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn consume_recovery_code(&mut self, user_id: &UserId, recovery_code_hash: &str) -> bool {
+        let Some(credential) = self
+            .data
+            .get_mut(user_id.as_str())
+            .filter(|credential| credential.confirmed)
+        else {
+            return false;
+        };
+        let count = credential.recovery_codes.len();
+        credential
+            .recovery_codes
+            .retain(|recovery_code| recovery_code != recovery_code_hash);
+
+        credential.recovery_codes.len() < count
+    }
+}
+
+pub struct FakePasskeyCredentialRepository {
+    pub data: HashMap<String, PasskeyCredential>,
+}
+
+impl FakePasskeyCredentialRepository {
+    pub fn new() -> Self {
+        FakePasskeyCredentialRepository {
+            data: HashMap::new(),
+        }
+    }
+}
+
+impl PasskeyCredentialRepository for FakePasskeyCredentialRepository {
+    fn create(&mut self, credential: PasskeyCredential) -> Result<(), error::EntityConflict> {
+        if self.data.contains_key(&credential.id) {
+            return Err(error::EntityConflict {});
+        }
+        self.data.insert(credential.id.clone(), credential);
+
+        Ok(())
+    }
+
+    fn get(&self, id: &str) -> Result<PasskeyCredential, error::EntityNotExist> {
+        self.data.get(id).cloned().ok_or(error::EntityNotExist {})
+    }
+
+    fn list_by_user(&self, user_id: &UserId) -> Vec<PasskeyCredential> {
+        self.data
+            .values()
+            .filter(|credential| credential.user_id.as_str() == user_id.as_str())
+            .cloned()
+            .collect()
+    }
+
+    fn update_sign_count(
+        &mut self,
+        id: &str,
+        expected_sign_count: u32,
+        sign_count: u32,
+    ) -> Result<(), error::SignCountChanged> {
+        match self.data.get_mut(id) {
+            Some(credential) if credential.sign_count == expected_sign_count => {
+                credential.sign_count = sign_count;
+                Ok(())
+            }
+            _ => Err(error::SignCountChanged {}),
+        }
+    }
+}
+
+pub struct FakePasskeyChallengeRepository {
+    pub data: HashMap<String, PasskeyChallenge>,
+}
+
+impl FakePasskeyChallengeRepository {
+    pub fn new() -> Self {
+        FakePasskeyChallengeRepository {
+            data: HashMap::new(),
+        }
+    }
+}
+
+impl PasskeyChallengeRepository for FakePasskeyChallengeRepository {
+    fn create(&mut self, challenge: PasskeyChallenge) -> Result<(), error::EntityConflict> {
+        if self.data.contains_key(&challenge.challenge) {
+            return Err(error::EntityConflict {});
+        }
+        self.data.insert(challenge.challenge.clone(), challenge);
+
+        Ok(())
+    }
+
+    fn take(&mut self, challenge: &str) -> Result<PasskeyChallenge, error::EntityNotExist> {
+        self.data.remove(challenge).ok_or(error::EntityNotExist {})
+    }
+}
+
+pub struct FakeOAuthClientRepository {
+    pub data: HashMap<String, OAuthClient>,
+}
+
+impl FakeOAuthClientRepository {
+    pub fn new() -> Self {
+        FakeOAuthClientRepository {
+            data: HashMap::new(),
         }
     }
 }
+
+impl OAuthClientRepository for FakeOAuthClientRepository {
+    fn get(&self, id: &str) -> Result<OAuthClient, error::EntityNotExist> {
+        self.data.get(id).cloned().ok_or(error::EntityNotExist {})
+    }
+
+    fn save(&mut self, client: OAuthClient) {
+        self.data.insert(client.id.clone(), client);
+    }
+}
+
+pub struct FakeAuthorizationCodeRepository {
+    pub data: HashMap<String, AuthorizationCode>,
+}
+
+impl FakeAuthorizationCodeRepository {
+    pub fn new() -> Self {
+        FakeAuthorizationCodeRepository {
+            data: HashMap::new(),
+        }
+    }
+}
+
+impl AuthorizationCodeRepository for FakeAuthorizationCodeRepository {
+    fn create(&mut self, code: AuthorizationCode) -> Result<(), error::EntityConflict> {
+        if self.data.contains_key(&code.code_hash) {
+            return Err(error::EntityConflict {});
+        }
+        self.data.insert(code.code_hash.clone(), code);
+
+        Ok(())
+    }
+
+    fn take(&mut self, code_hash: &str) -> Result<AuthorizationCode, error::EntityNotExist> {
+        self.data.remove(code_hash).ok_or(error::EntityNotExist {})
+    }
+}
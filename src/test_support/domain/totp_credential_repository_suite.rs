@@ -0,0 +1,111 @@
+use crate::domain::{
+    entity::TotpCredential, error::EntityNotExist, repository::TotpCredentialRepository,
+    value_object::UserId,
+};
+
+const USER_ID: &str = "0196e2a4-5c1b-4f7e-9a3d-2b8c6d4e1f00";
+
+fn create_credential() -> TotpCredential {
+    TotpCredential {
+        user_id: UserId::new(USER_ID).unwrap(),
+        secret: "GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ".to_string(),
+        confirmed: false,
+        recovery_codes: vec![],
+        last_used_step: 0,
+    }
+}
+
+pub fn save_given_credential_should_persist_to_data(repo: &mut dyn TotpCredentialRepository) {
+    repo.save(create_credential());
+
+    let credential = repo.get(&UserId::new(USER_ID).unwrap());
+
+    assert!(credential.is_ok_and(|credential| {
+        credential.secret == "GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ"
+            && !credential.confirmed
+            && credential.recovery_codes.is_empty()
+            && credential.last_used_step == 0
+    }));
+}
+
+pub fn save_given_existing_credential_should_replace_it(repo: &mut dyn TotpCredentialRepository) {
+    repo.save(create_credential());
+    repo.save(TotpCredential {
+        confirmed: true,
+        recovery_codes: vec!["hashed_a".to_string(), "hashed_b".to_string()],
+        last_used_step: 58254185,
+        ..create_credential()
+    });
+
+    let credential = repo.get(&UserId::new(USER_ID).unwrap());
+
+    assert!(credential.is_ok_and(|credential| {
+        credential.confirmed
+            && credential.recovery_codes == vec!["hashed_a", "hashed_b"]
+            && credential.last_used_step == 58254185
+    }));
+}
+
+pub fn get_given_not_exist_user_should_return_entity_not_exist(
+    repo: &mut dyn TotpCredentialRepository,
+) {
+    let credential = repo.get(&UserId::new(USER_ID).unwrap());
+
+    assert!(credential.is_err_and(|err| matches!(err, EntityNotExist {})));
+}
+
+pub fn advance_step_given_newer_step_should_advance_only_once(
+    repo: &mut dyn TotpCredentialRepository,
+) {
+    let user_id = UserId::new(USER_ID).unwrap();
+    repo.save(TotpCredential {
+        confirmed: true,
+        last_used_step: 58254184,
+        ..create_credential()
+    });
+
+    let first = repo.advance_step(&user_id, 58254185);
+    let replayed = repo.advance_step(&user_id, 58254185);
+    let older = repo.advance_step(&user_id, 58254183);
+
+    assert!(first);
+    assert!(!replayed);
+    assert!(!older);
+    assert!(
+        repo.get(&user_id)
+            .is_ok_and(|credential| credential.last_used_step == 58254185)
+    );
+}
+
+pub fn advance_step_given_unconfirmed_credential_should_return_false(
+    repo: &mut dyn TotpCredentialRepository,
+) {
+    repo.save(create_credential());
+
+    let advanced = repo.advance_step(&UserId::new(USER_ID).unwrap(), 58254185);
+
+    assert!(!advanced);
+}
+
+pub fn consume_recovery_code_given_code_should_remove_it_only_once(
+    repo: &mut dyn TotpCredentialRepository,
+) {
+    let user_id = UserId::new(USER_ID).unwrap();
+    repo.save(TotpCredential {
+        confirmed: true,
+        recovery_codes: vec!["hashed_a".to_string(), "hashed_b".to_string()],
+        ..create_credential()
+    });
+
+    let first = repo.consume_recovery_code(&user_id, "hashed_a");
+    let replayed = repo.consume_recovery_code(&user_id, "hashed_a");
+    let unknown = repo.consume_recovery_code(&user_id, "hashed_c");
+
+    assert!(first);
+    assert!(!replayed);
+    assert!(!unknown);
+    assert!(
+        repo.get(&user_id)
+            .is_ok_and(|credential| credential.recovery_codes == vec!["hashed_b"])
+    );
+}
@@ -0,0 +1,226 @@
+use crate::domain::{
+    entity::User,
+    error::{EntityConflict, EntityNotExist, UpdateUserError},
+    repository::UserRepository,
+    value_object::{EmailAddress, UserId},
+};
+
+const USER_ID: &str = "0196e2a4-5c1b-4f7e-9a3d-2b8c6d4e1f00";
+
+fn create_user() -> User {
+    User {
+        id: UserId::new(USER_ID).unwrap(),
+        email: EmailAddress::new("example@example.com").unwrap(),
+        email_verified: false,
+        pending_email: None,
+        username: "foo".to_string(),
+        password: "bar".to_string(),
+        create_at: 1747636936,
+        update_at: 1747636936,
+    }
+}
+
+fn create_users(repo: &mut dyn UserRepository, count: u64) {
+    for i in 0..count {
+        repo.create(User {
+            id: UserId::new(&format!("00000000-0000-4000-8000-{:012}", i)).unwrap(),
+            email: EmailAddress::new(&format!("user{}@example.com", i)).unwrap(),
+            email_verified: false,
+            pending_email: None,
+            username: format!("user{}", i),
+            password: "bar".to_string(),
+            create_at: 1747636936 + i,
+            update_at: 1747636936 + i,
+        })
+        .expect("should be ok");
+    }
+}
+
+pub fn create_given_user_should_persist_to_data(repo: &mut dyn UserRepository) {
+    let user = create_user();
+
+    let result = repo.create(user);
+
+    assert!(result.is_ok());
+}
+
+pub fn create_given_conflict_email_should_return_entity_conflict(repo: &mut dyn UserRepository) {
+    repo.create(create_user()).expect("should be ok");
+    let user = User {
+        id: UserId::new("0196e2a4-5c1b-4f7e-9a3d-2b8c6d4e1f01").unwrap(),
+        ..create_user()
+    };
+
+    let result = repo.create(user);
+
+    assert!(result.is_err_and(|err| matches!(err, EntityConflict {})));
+}
+
+pub fn get_given_email_should_return_user(repo: &mut dyn UserRepository) {
+    repo.create(create_user()).expect("should be ok");
+
+    let user = repo.get(EmailAddress::new("example@example.com").unwrap());
+
+    assert!(user.is_ok_and(|u| u.username == "foo"
+        && u.password == "bar"
+        && u.create_at == 1747636936
+        && u.update_at == 1747636936));
+}
+
+pub fn get_given_not_exist_email_should_return_entity_not_exist(repo: &mut dyn UserRepository) {
+    let user = repo.get(EmailAddress::new("example@example.com").unwrap());
+
+    assert!(user.is_err_and(|err| matches!(err, EntityNotExist {})));
+}
+
+pub fn get_by_id_given_id_should_return_user(repo: &mut dyn UserRepository) {
+    repo.create(create_user()).expect("should be ok");
+
+    let user = repo.get_by_id(&UserId::new(USER_ID).unwrap());
+
+    assert!(user.is_ok_and(|u| u.email.as_str() == "example@example.com" && u.username == "foo"));
+}
+
+pub fn get_by_id_given_not_exist_id_should_return_entity_not_exist(repo: &mut dyn UserRepository) {
+    repo.create(create_user()).expect("should be ok");
+
+    let user = repo.get_by_id(&UserId::new("0196e2a4-5c1b-4f7e-9a3d-2b8c6d4e1f01").unwrap());
+
+    assert!(user.is_err_and(|err| matches!(err, EntityNotExist {})));
+}
+
+pub fn get_by_username_given_username_should_return_user(repo: &mut dyn UserRepository) {
+    repo.create(create_user()).expect("should be ok");
+
+    let user = repo.get_by_username("foo");
+
+    assert!(user.is_ok_and(|u| u.email.as_str() == "example@example.com"));
+}
+
+pub fn get_by_username_given_not_exist_username_should_return_entity_not_exist(
+    repo: &mut dyn UserRepository,
+) {
+    repo.create(create_user()).expect("should be ok");
+
+    let user = repo.get_by_username("bar");
+
+    assert!(user.is_err_and(|err| matches!(err, EntityNotExist {})));
+}
+
+pub fn list_given_offset_and_limit_should_return_page_in_creation_order(
+    repo: &mut dyn UserRepository,
+) {
+    create_users(repo, 5);
+
+    let users = repo.list(1, 3);
+
+    let usernames: Vec<String> = users.into_iter().map(|u| u.username).collect();
+    assert_eq!(usernames, vec!["user1", "user2", "user3"]);
+}
+
+pub fn list_given_offset_beyond_data_should_return_empty(repo: &mut dyn UserRepository) {
+    create_users(repo, 2);
+
+    let users = repo.list(2, 10);
+
+    assert!(users.is_empty());
+}
+
+pub fn update_given_user_should_persist_changes(repo: &mut dyn UserRepository) {
+    repo.create(create_user()).expect("should be ok");
+
+    let result = repo.update(User {
+        email: EmailAddress::new("changed@example.com").unwrap(),
+        email_verified: true,
+        pending_email: Some(EmailAddress::new("pending@example.com").unwrap()),
+        username: "baz".to_string(),
+        update_at: 1747640536,
+        ..create_user()
+    });
+
+    assert!(result.is_ok());
+    assert!(
+        repo.get_by_id(&UserId::new(USER_ID).unwrap())
+            .is_ok_and(|u| u.email.as_str() == "changed@example.com"
+                && u.email_verified
+                && u.pending_email
+                    .is_some_and(|e| e.as_str() == "pending@example.com")
+                && u.username == "baz"
+                && u.update_at == 1747640536)
+    );
+}
+
+pub fn update_password_if_unchanged_given_expected_password_should_replace_password(
+    repo: &mut dyn UserRepository,
+) {
+    repo.create(create_user()).expect("should be ok");
+
+    let result = repo.update_password_if_unchanged(&UserId::new(USER_ID).unwrap(), "bar", "baz");
+
+    assert!(result);
+    assert!(
+        repo.get_by_id(&UserId::new(USER_ID).unwrap())
+            .is_ok_and(|u| u.password == "baz")
+    );
+}
+
+pub fn update_password_if_unchanged_given_changed_password_should_keep_password(
+    repo: &mut dyn UserRepository,
+) {
+    repo.create(create_user()).expect("should be ok");
+
+    let result = repo.update_password_if_unchanged(&UserId::new(USER_ID).unwrap(), "stale", "baz");
+
+    assert!(!result);
+    assert!(
+        repo.get_by_id(&UserId::new(USER_ID).unwrap())
+            .is_ok_and(|u| u.password == "bar")
+    );
+}
+
+pub fn update_given_not_exist_user_should_return_entity_not_exist(repo: &mut dyn UserRepository) {
+    let result = repo.update(create_user());
+
+    assert!(result.is_err_and(|err| matches!(err, UpdateUserError::NotExist)));
+}
+
+pub fn update_given_email_of_another_user_should_return_email_conflict(
+    repo: &mut dyn UserRepository,
+) {
+    repo.create(create_user()).expect("should be ok");
+    let other_user = User {
+        id: UserId::new("0196e2a4-5c1b-4f7e-9a3d-2b8c6d4e1f01").unwrap(),
+        email: EmailAddress::new("other@example.com").unwrap(),
+        ..create_user()
+    };
+    repo.create(other_user.clone()).expect("should be ok");
+
+    let result = repo.update(User {
+        email: EmailAddress::new("example@example.com").unwrap(),
+        ..other_user
+    });
+
+    assert!(result.is_err_and(|err| matches!(err, UpdateUserError::EmailConflict)));
+    assert!(
+        repo.get_by_id(&UserId::new("0196e2a4-5c1b-4f7e-9a3d-2b8c6d4e1f01").unwrap())
+            .is_ok_and(|u| u.email.as_str() == "other@example.com")
+    );
+}
+
+pub fn delete_given_id_should_remove_user(repo: &mut dyn UserRepository) {
+    repo.create(create_user()).expect("should be ok");
+
+    let result = repo.delete(&UserId::new(USER_ID).unwrap());
+
+    assert!(result.is_ok());
+    assert!(
+        repo.get(EmailAddress::new("example@example.com").unwrap())
+            .is_err()
+    );
+}
+
+pub fn delete_given_not_exist_id_should_return_entity_not_exist(repo: &mut dyn UserRepository) {
+    let result = repo.delete(&UserId::new(USER_ID).unwrap());
+
+    assert!(result.is_err_and(|err| matches!(err, EntityNotExist {})));
+}
@@ -0,0 +1,173 @@
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use ed25519_dalek::pkcs8::DecodePrivateKey;
+use p256::ecdsa::signature::Signer;
+use sha2::{Digest, Sha256};
+
+use super::key::{EC_PEM, ED25519_PEM};
+
+const FLAGS: u8 = 0x01 | 0x04;
+const FLAG_ATTESTED_CREDENTIAL_DATA: u8 = 0x40;
+
+enum AuthenticatorKey {
+    Es256(p256::ecdsa::SigningKey),
+    EdDsa(ed25519_dalek::SigningKey),
+}
+
+pub struct SoftwareAuthenticator {
+    rp_id: String,
+    origin: String,
+    credential_id: Vec<u8>,
+    key: AuthenticatorKey,
+}
+
+pub struct SoftwareAssertion {
+    pub client_data_json: Vec<u8>,
+    pub authenticator_data: Vec<u8>,
+    pub signature: Vec<u8>,
+}
+
+impl SoftwareAuthenticator {
+    pub fn es256(rp_id: &str, origin: &str) -> Self {
+        SoftwareAuthenticator {
+            rp_id: rp_id.to_string(),
+            origin: origin.to_string(),
+            credential_id: b"es256-credential".to_vec(),
+            key: AuthenticatorKey::Es256(p256::ecdsa::SigningKey::from_pkcs8_pem(EC_PEM).unwrap()),
+        }
+    }
+
+    pub fn ed25519(rp_id: &str, origin: &str) -> Self {
+        SoftwareAuthenticator {
+            rp_id: rp_id.to_string(),
+            origin: origin.to_string(),
+            credential_id: b"ed25519-credential".to_vec(),
+            key: AuthenticatorKey::EdDsa(
+                ed25519_dalek::SigningKey::from_pkcs8_pem(ED25519_PEM).unwrap(),
+            ),
+        }
+    }
+
+    pub fn credential_id(&self) -> String {
+        URL_SAFE_NO_PAD.encode(&self.credential_id)
+    }
+
+    pub fn public_key(&self) -> String {
+        URL_SAFE_NO_PAD.encode(self.cose_key())
+    }
+
+    pub fn attest(&self, challenge: &str) -> (Vec<u8>, Vec<u8>) {
+        let mut authenticator_data = self.authenticator_data(FLAG_ATTESTED_CREDENTIAL_DATA, 0);
+        authenticator_data.extend_from_slice(&[0u8; 16]);
+        authenticator_data.extend_from_slice(&(self.credential_id.len() as u16).to_be_bytes());
+        authenticator_data.extend_from_slice(&self.credential_id);
+        authenticator_data.extend_from_slice(&self.cose_key());
+        let attestation_object = map(vec![
+            (text("fmt"), text("none")),
+            (text("attStmt"), map(vec![])),
+            (text("authData"), bytes(&authenticator_data)),
+        ]);
+
+        (
+            self.client_data_json("webauthn.create", challenge),
+            attestation_object,
+        )
+    }
+
+    pub fn assert(&self, challenge: &str, sign_count: u32) -> SoftwareAssertion {
+        let client_data_json = self.client_data_json("webauthn.get", challenge);
+        let authenticator_data = self.authenticator_data(0, sign_count);
+        let message = [
+            authenticator_data.as_slice(),
+            Sha256::digest(&client_data_json).as_slice(),
+        ]
+        .concat();
+        let signature = match &self.key {
+            AuthenticatorKey::Es256(key) => {
+                let signature: p256::ecdsa::Signature = key.sign(&message);
+                signature.to_der().as_bytes().to_vec()
+            }
+            AuthenticatorKey::EdDsa(key) => key.sign(&message).to_bytes().to_vec(),
+        };
+
+        SoftwareAssertion {
+            client_data_json,
+            authenticator_data,
+            signature,
+        }
+    }
+
+    fn client_data_json(&self, ceremony: &str, challenge: &str) -> Vec<u8> {
+        serde_json::to_vec(&serde_json::json!({
+            "type": ceremony,
+            "challenge": challenge,
+            "origin": self.origin,
+            "crossOrigin": false,
+        }))
+        .unwrap()
+    }
+
+    fn authenticator_data(&self, extra_flags: u8, sign_count: u32) -> Vec<u8> {
+        let mut authenticator_data = Sha256::digest(self.rp_id.as_bytes()).to_vec();
+        authenticator_data.push(FLAGS | extra_flags);
+        authenticator_data.extend_from_slice(&sign_count.to_be_bytes());
+        authenticator_data
+    }
+
+    fn cose_key(&self) -> Vec<u8> {
+        match &self.key {
+            AuthenticatorKey::Es256(key) => {
+                let point = key.verifying_key().to_encoded_point(false);
+                map(vec![
+                    (int(1), int(2)),
+                    (int(3), int(-7)),
+                    (int(-1), int(1)),
+                    (int(-2), bytes(point.x().unwrap())),
+                    (int(-3), bytes(point.y().unwrap())),
+                ])
+            }
+            AuthenticatorKey::EdDsa(key) => map(vec![
+                (int(1), int(1)),
+                (int(3), int(-8)),
+                (int(-1), int(6)),
+                (int(-2), bytes(key.verifying_key().as_bytes())),
+            ]),
+        }
+    }
+}
+
+fn header(major: u8, argument: u64) -> Vec<u8> {
+    match argument {
+        0..=23 => vec![(major << 5) | argument as u8],
+        24..=0xff => vec![(major << 5) | 24, argument as u8],
+        _ => [
+            vec![(major << 5) | 25],
+            (argument as u16).to_be_bytes().to_vec(),
+        ]
+        .concat(),
+    }
+}
+
+fn int(value: i64) -> Vec<u8> {
+    if value >= 0 {
+        header(0, value as u64)
+    } else {
+        header(1, (-1 - value) as u64)
+    }
+}
+
+fn bytes(value: &[u8]) -> Vec<u8> {
+    [header(2, value.len() as u64), value.to_vec()].concat()
+}
+
+fn text(value: &str) -> Vec<u8> {
+    [header(3, value.len() as u64), value.as_bytes().to_vec()].concat()
+}
+
+fn map(entries: Vec<(Vec<u8>, Vec<u8>)>) -> Vec<u8> {
+    let mut encoded = header(5, entries.len() as u64);
+    for (key, value) in entries {
+        encoded.extend(key);
+        encoded.extend(value);
+    }
+    encoded
+}
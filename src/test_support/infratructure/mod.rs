@@ -0,0 +1,3 @@
+pub mod authenticator;
+pub mod key;
+pub mod temp_dir;